@@ -1,155 +1,213 @@
+pub mod admin;
 pub mod event;
+pub mod limits;
 pub mod player;
 pub mod rules;
 pub mod session;
-use crate::engine::session::Lobby;
-
+use self::admin::Admin;
 use self::event::GameEvent;
-use self::player::Message;
-use self::rules::{Instantiable, RuleEngine};
-use self::session::{LobbyInterface, PlayerFromTcpStream, SessionError};
+use self::limits::{ConnectionGuard, ConnectionLimits, ConnectionStats, DisconnectReason};
+use self::player::PlayerError;
+use self::rules::{AsyncRuleEngine, Instantiable};
+use self::session::{LocalPlayer, Routes, Session, SessionManager};
+use self::event::BackendEvent;
 use std::cell::RefCell;
+use std::io::Write;
 use std::sync::Arc;
 use std::{net::TcpListener, net::TcpStream};
 use tokio::sync::{broadcast, mpsc, Mutex};
+use tracing::{debug, error, info, info_span, warn, Instrument};
 
 #[derive(Debug)]
 enum Cmd {
-    Add { user: TcpStream },
+    Add {
+        user: TcpStream,
+        guard: ConnectionGuard,
+    },
+    /// A command from the [`admin`] socket
+    Admin(admin::Request),
 }
 
 /// Wait for tcp connections pass to adder
-async fn tcp_listener(listener: TcpListener, tx: mpsc::Sender<Cmd>) {
+///
+/// The std listener blocks so this has to run on a blocking thread.
+fn tcp_listener(
+    listener: TcpListener,
+    tx: mpsc::Sender<Cmd>,
+    limits: ConnectionLimits,
+    stats: Arc<ConnectionStats>,
+    rule_set: String,
+    fingerprint: Option<String>,
+) {
+    // Tells the client which rule set to load, this is sent before any game events
+    let mut handshake: Vec<u8> = BackendEvent::RuleSet(rule_set, fingerprint).into();
+    handshake.push(0);
+
     loop {
-        println!("Waiting");
         for stream in listener.incoming() {
-            println!("{:?}", stream);
-            let stream = stream.unwrap();
-            match tx.send(Cmd::Add { user: stream }).await {
-                Ok(_) => {
-                    println!("Message sent!");
+            debug!(?stream, "Incoming connection");
+            let mut stream = stream.unwrap();
+            let guard = match stats.open(limits.max_connections) {
+                Some(guard) => guard,
+                None => {
+                    stats.record(
+                        &format!("{:?}", stream.peer_addr()),
+                        DisconnectReason::TooManyConnections,
+                    );
+                    let _ = stream.shutdown(std::net::Shutdown::Both);
+                    continue;
                 }
+            };
+            if let Err(e) = stream.write_all(&handshake) {
+                debug!(error = ?e, "Could not send the handshake");
+                continue;
+            }
+            match tx.blocking_send(Cmd::Add {
+                user: stream,
+                guard,
+            }) {
+                Ok(_) => {}
                 Err(e) => {
-                    eprintln!("Could not send add user to {:?}, error code : {:?}", tx, e);
+                    error!(error = ?e, "Could not pass the connection to the manager");
                 }
             }
         }
     }
 }
 pub async fn manager<
-    Rules: RuleEngine + Instantiable + Send + 'static,
+    Rules: AsyncRuleEngine + Instantiable + 'static,
     const BUFFER_SIZE: usize,
     const CAPACITY: usize,
 >(
     listener: TcpListener,
-) where
-    Lobby<Rules, CAPACITY>: PlayerFromTcpStream<CAPACITY, BUFFER_SIZE, Rules::Event>,
-{
-    println!("In manager");
+    limits: ConnectionLimits,
+    rule_set: &str,
+    fingerprint: Option<String>,
+    config: Rules::Config,
+    local_players: Vec<LocalPlayer<Rules::Event>>,
+    admin: Option<Admin<Rules::Event>>,
+) {
+    info!(rule_set, ?fingerprint, "Starting the session manager");
+    let stats = Arc::new(ConnectionStats::default());
     let (tx, rx) = mpsc::channel::<Cmd>(32);
-    tokio::spawn(async move {
-        tcp_listener(listener, tx).await;
+    let listener_stats = stats.clone();
+    let rule_set = rule_set.to_owned();
+    tokio::task::spawn_blocking(move || {
+        tcp_listener(listener, tx, limits, listener_stats, rule_set, fingerprint);
     });
 
-    //let manager = Arc::new(Mutex::new(RefCell::new(UserManager::new())));
-    let (event_tx, event_rx) = mpsc::channel(32);
-
-    let lobby: Arc<Mutex<RefCell<Lobby<Rules, CAPACITY>>>> =
-        Arc::new(Mutex::new(RefCell::new(session::Lobby::new(0, event_rx))));
-    // Start the lobby
-    tokio::spawn(Lobby::<Rules, CAPACITY>::start(lobby.clone()));
+    let mut session_manager = SessionManager::<Rules, BUFFER_SIZE, CAPACITY>::new()
+        .with_limits(limits, stats)
+        .with_config(config);
+    for player in local_players {
+        session_manager.add_local(player);
+    }
+    // Without an admin socket the sender is dropped and no commands arrive
+    let (admin_tx, admin_rx) = mpsc::channel(8);
+    if let Some(admin) = admin {
+        session_manager = session_manager.with_bot(admin.bot);
+        tokio::spawn(admin::listen(admin.listener, admin_tx));
+    }
+    let manager: Arc<Mutex<RefCell<SessionManager<Rules, BUFFER_SIZE, CAPACITY>>>> =
+        Arc::new(Mutex::new(RefCell::new(session_manager)));
     // Does not return until the program exists, basically a block until exit
-    tcp_manager(rx, lobby.clone(), event_tx).await;
+    tcp_manager(rx, admin_rx, manager).await;
 }
 
-async fn monitor<Event: GameEvent, T: session::LobbyInterface<Event>>(
+async fn monitor<
+    Event: GameEvent,
+    const BUFFER_SIZE: usize,
+    const CAPACITY: usize,
+    T: Session<Event, BUFFER_SIZE, CAPACITY>,
+>(
     manager: Arc<Mutex<RefCell<T>>>,
     uid: usize,
     mut rx: broadcast::Receiver<player::Message<Event>>,
-    tx: mpsc::Sender<(usize, Event)>,
+    routes: Routes<Event>,
+    _guard: ConnectionGuard,
 ) {
     loop {
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
         match rx.try_recv() {
             Ok(message) => {
-                println!("{:?}", message);
+                debug!(?message, "Received");
                 match message {
                     player::Message::Received { event, user: _ } => match event {
+                        Err(PlayerError::Kicked(reason)) => {
+                            warn!(?reason, "Player was kicked");
+                            break;
+                        }
                         Err(e) => {
-                            println!("Exiting the monitor for some reason {:?}", e);
+                            info!(error = ?e, "Player left");
                             break;
                         }
                         Ok(msg) => {
-                            println!("monitor got {:?}", msg);
-                            tx.send((uid, msg)).await.unwrap();
-                            println!("sent");
+                            // Players that are not seated in a lobby have no one to talk to
+                            let route = routes.lock().await.get(&uid).cloned();
+                            match route {
+                                Some(tx) => {
+                                    let _ = tx.send((uid, msg)).await;
+                                }
+                                None => debug!(?msg, "Player is not seated, dropping"),
+                            }
                         }
                     },
                 }
             }
+            // The receiver is gone, so is the player
+            Err(broadcast::error::TryRecvError::Closed) => break,
             _e => {}
         };
     }
-    println!("Closed");
-    let _ = manager.lock().await.borrow_mut().disconnect(uid);
+    debug!("Connection closed");
+    let _ = manager.lock().await.get_mut().delete(uid).await;
 }
 
-fn add_player<
-    Event: GameEvent + 'static,
-    const BUFFER_SIZE: usize,
-    const CAPACITY: usize,
-    T: LobbyInterface<Event>
-        + PlayerFromTcpStream<BUFFER_SIZE, CAPACITY, Event>
-        + 'static
-        + std::marker::Send,
->(
-    player: Result<(usize, broadcast::Receiver<Message<Event>>), SessionError>,
-    manager: Arc<Mutex<RefCell<T>>>,
-    event_tx: mpsc::Sender<(usize, Event)>,
-) {
-    println!("hey, new player {:?}", player);
-    match player {
-        Ok((uid, channel)) => {
-            println!("Spawning monitor for {:?} and {:?}", uid, channel);
-            tokio::spawn(async move {
-                monitor(manager, uid, channel, event_tx).await;
-            });
-        }
-        Err(e) => {
-            println!("{:?}", e);
-        }
-    }
-}
 async fn tcp_manager<
-    Event: GameEvent + 'static,
-    const CAPACITY: usize,
+    Rules: AsyncRuleEngine + Instantiable + 'static,
     const BUFFER_SIZE: usize,
-    T: LobbyInterface<Event>
-        + PlayerFromTcpStream<CAPACITY, BUFFER_SIZE, Event>
-        + 'static
-        + std::marker::Send,
+    const CAPACITY: usize,
 >(
     mut rx: mpsc::Receiver<Cmd>,
-    manager: Arc<Mutex<RefCell<T>>>,
-    event_tx: mpsc::Sender<(usize, Event)>,
+    mut admin: mpsc::Receiver<admin::Request>,
+    manager: Arc<Mutex<RefCell<SessionManager<Rules, BUFFER_SIZE, CAPACITY>>>>,
 ) {
+    let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(500));
     // Manage incoming tcp connections
-    while let Some(message) = rx.recv().await {
-        match message {
-            Cmd::Add { user } => {
-                let cloned_manager = manager.clone();
-                let locked_manager = cloned_manager.lock().await;
-                let mut borrowed_manager = match locked_manager.try_borrow_mut() {
-                    Ok(manager) => manager,
-                    Err(_) => {
-                        return;
-                    }
-                };
-
-                let user = borrowed_manager.add(user);
+    loop {
+        let cmd = tokio::select! {
+            message = rx.recv() => match message {
+                Some(cmd) => Some(cmd),
+                None => return,
+            },
+            Some(request) = admin.recv() => Some(Cmd::Admin(request)),
+            _ = interval.tick() => None,
+        };
 
-                add_player(user, manager.clone(), event_tx.clone());
+        let mut locked_manager = manager.lock().await;
+        let borrowed_manager = locked_manager.get_mut();
+        match cmd {
+            Some(Cmd::Add { user, guard }) => match borrowed_manager.add(user) {
+                Ok((uid, channel)) => {
+                    let routes = borrowed_manager.routes();
+                    let manager = manager.clone();
+                    tokio::spawn(
+                        async move {
+                            monitor(manager, uid, channel, routes, guard).await;
+                        }
+                        .instrument(info_span!("player", uid)),
+                    );
+                }
+                Err(e) => {
+                    warn!(error = ?e, "Could not add player");
+                }
+            },
+            Some(Cmd::Admin(request)) => {
+                let answer = borrowed_manager.execute(request.command);
+                // The admin may have hung up
+                let _ = request.reply.send(answer);
             }
+            None => borrowed_manager.tick().await,
         }
     }
 }
@@ -0,0 +1,175 @@
+//! Defines the admin socket of the server.
+//!
+//! The admin socket takes one command per line and answers every command with one line,
+//! so it can be driven with `nc`:
+//!
+//! ```text
+//! bots 2
+//! Added 2 bots
+//! status
+//! connections 3, waiting 1, post game 0, bots waiting 2, lobbies 1
+//! ```
+//!
+//! The socket is not authenticated, it should only be bound to a trusted interface.
+
+use std::sync::Arc;
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, info, warn};
+
+use super::session::LocalPlayer;
+
+/// Creates the players that the [`Bots`](AdminCommand::Bots) command adds.
+pub type BotFactory<Event> = Arc<dyn Fn() -> LocalPlayer<Event> + Send + Sync>;
+
+/// Settings for the admin socket.
+pub struct Admin<Event> {
+    /// The listener that admin connections are accepted on
+    pub listener: std::net::TcpListener,
+    /// Creates the bots that are added with the [`Bots`](AdminCommand::Bots) command
+    pub bot: BotFactory<Event>,
+}
+
+/// The commands that the admin socket accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdminCommand {
+    /// Adds that many bots, they are seated once a remote player joins a lobby
+    Bots(usize),
+    /// Reports the number of connections and where the players are
+    Status,
+    /// Lists the commands
+    Help,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminError {
+    /// There is no such command
+    UnknownCommand(String),
+    /// The command was given an argument that it does not accept
+    InvalidArgument(String),
+}
+
+impl std::fmt::Display for AdminError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownCommand(command) => {
+                write!(f, "unknown command {:?}, try help", command)
+            }
+            Self::InvalidArgument(argument) => write!(f, "invalid argument {:?}", argument),
+        }
+    }
+}
+
+/// The answer to the [`Help`](AdminCommand::Help) command.
+pub const HELP: &str = "commands: bots <count>, status, help";
+
+impl AdminCommand {
+    /// Parses a command line, the command and its arguments are separated by whitespace.
+    pub fn parse(line: &str) -> Result<Self, AdminError> {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or_default();
+        let argument = words.next();
+        match (command, argument) {
+            ("bots", Some(count)) => count
+                .parse()
+                .map(Self::Bots)
+                .map_err(|_| AdminError::InvalidArgument(count.to_owned())),
+            ("bots", None) => Err(AdminError::InvalidArgument(String::new())),
+            ("status", None) => Ok(Self::Status),
+            ("help", None) => Ok(Self::Help),
+            (_, Some(argument)) if ["status", "help"].contains(&command) => {
+                Err(AdminError::InvalidArgument(argument.to_owned()))
+            }
+            _ => Err(AdminError::UnknownCommand(command.to_owned())),
+        }
+    }
+}
+
+/// A parsed command and the channel that its answer is sent on.
+#[derive(Debug)]
+pub struct Request {
+    pub command: AdminCommand,
+    pub reply: oneshot::Sender<String>,
+}
+
+/// Accepts admin connections and forwards their commands to the session manager.
+pub async fn listen(listener: std::net::TcpListener, tx: mpsc::Sender<Request>) {
+    // Tokio requires the socket to be in non blocking mode
+    let listener = match listener
+        .set_nonblocking(true)
+        .and_then(|_| TcpListener::from_std(listener))
+    {
+        Ok(listener) => listener,
+        Err(e) => {
+            warn!(error = ?e, "Could not start the admin socket");
+            return;
+        }
+    };
+    info!(address = ?listener.local_addr(), "Admin socket listening");
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                warn!(error = ?e, "Could not accept an admin connection");
+                continue;
+            }
+        };
+        info!(?peer, "Admin connected");
+        tokio::spawn(serve(stream, tx.clone()));
+    }
+}
+
+/// Answers the commands of one admin connection until it is closed.
+async fn serve(stream: TcpStream, tx: mpsc::Sender<Request>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        debug!(line, "Admin command");
+        let answer = match AdminCommand::parse(&line) {
+            Ok(command) => {
+                let (reply, answer) = oneshot::channel();
+                if tx.send(Request { command, reply }).await.is_err() {
+                    return;
+                }
+                answer.await.unwrap_or_default()
+            }
+            Err(e) => e.to_string(),
+        };
+        if writer.write_all(format!("{}\n", answer).as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AdminCommand, AdminError};
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(AdminCommand::parse("bots 2"), Ok(AdminCommand::Bots(2)));
+        assert_eq!(AdminCommand::parse("  status "), Ok(AdminCommand::Status));
+        assert_eq!(AdminCommand::parse("help"), Ok(AdminCommand::Help));
+        assert_eq!(
+            AdminCommand::parse("bots two"),
+            Err(AdminError::InvalidArgument("two".to_owned()))
+        );
+        assert!(matches!(
+            AdminCommand::parse("bots"),
+            Err(AdminError::InvalidArgument(_))
+        ));
+        assert_eq!(
+            AdminCommand::parse("status now"),
+            Err(AdminError::InvalidArgument("now".to_owned()))
+        );
+        assert_eq!(
+            AdminCommand::parse("kick 1"),
+            Err(AdminError::UnknownCommand("kick".to_owned()))
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::limits::DisconnectReason;
+
 pub trait GameEvent:
     Clone
     + Serialize
@@ -19,8 +21,15 @@ pub trait GameEvent:
 /// Definition of protocol events.
 pub enum BackendEvent {
     Connected(u8),
+    /// Sent once when a client connects, names the rule set that the server is running.
+    ///
+    /// A rule set that plays content that is loaded at runtime, such as a deck, sends a
+    /// fingerprint of it as well. A client that loaded other content can not play along.
+    RuleSet(String, Option<String>),
     UnexpectedMessage,
     Resend,
+    /// Sent right before the server drops the connection
+    Kicked(DisconnectReason),
 }
 
 impl GameEvent for BackendEvent {
@@ -0,0 +1,200 @@
+//! Defines connection level limits for the server.
+//!
+//! These limits protect the server from misbehaving clients, a client that breaks
+//! any of them is disconnected and the reason is logged and counted in [`ConnectionStats`].
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use tokio::time::Instant;
+
+/// Server settings that limit what a single connection is allowed to do.
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionLimits {
+    /// Maximum number of concurrent tcp connections.
+    pub max_connections: usize,
+    /// Maximum number of messages a connection may send within [`rate_window`](Self::rate_window).
+    pub max_messages: usize,
+    /// The window that [`max_messages`](Self::max_messages) is counted over.
+    pub rate_window: Duration,
+    /// Maximum number of bytes a single, un parsed, frame may occupy.
+    pub max_frame_size: usize,
+    /// Maximum time a connection may wait for a seat before it is dropped.
+    pub idle_timeout: Duration,
+}
+
+impl Default for ConnectionLimits {
+    fn default() -> Self {
+        Self {
+            max_connections: 16,
+            max_messages: 20,
+            rate_window: Duration::from_secs(1),
+            max_frame_size: 4096,
+            idle_timeout: Duration::from_secs(120),
+        }
+    }
+}
+
+/// The reasons a connection can be dropped by the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisconnectReason {
+    /// The server already has [`max_connections`](ConnectionLimits::max_connections) connections.
+    TooManyConnections,
+    /// The client sent more than [`max_messages`](ConnectionLimits::max_messages) messages
+    /// within one [`rate_window`](ConnectionLimits::rate_window).
+    RateLimited,
+    /// The client sent a frame larger than [`max_frame_size`](ConnectionLimits::max_frame_size).
+    FrameTooLarge,
+    /// The client waited longer than [`idle_timeout`](ConnectionLimits::idle_timeout) for a seat.
+    IdleTimeout,
+}
+
+/// Counts the number of connections dropped per [`DisconnectReason`].
+#[derive(Debug, Default)]
+pub struct ConnectionStats {
+    active: AtomicUsize,
+    too_many_connections: AtomicUsize,
+    rate_limited: AtomicUsize,
+    frame_too_large: AtomicUsize,
+    idle_timeout: AtomicUsize,
+}
+
+impl ConnectionStats {
+    fn counter(&self, reason: DisconnectReason) -> &AtomicUsize {
+        match reason {
+            DisconnectReason::TooManyConnections => &self.too_many_connections,
+            DisconnectReason::RateLimited => &self.rate_limited,
+            DisconnectReason::FrameTooLarge => &self.frame_too_large,
+            DisconnectReason::IdleTimeout => &self.idle_timeout,
+        }
+    }
+
+    /// Logs and counts a dropped connection.
+    pub fn record(&self, peer: &str, reason: DisconnectReason) {
+        let count = self.counter(reason).fetch_add(1, Ordering::Relaxed) + 1;
+        warn!(peer, ?reason, count, "Disconnecting");
+    }
+
+    /// Returns the number of connections dropped for that reason.
+    pub fn count(&self, reason: DisconnectReason) -> usize {
+        self.counter(reason).load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of currently open connections.
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// Tries to reserve a connection slot.
+    ///
+    /// Returns [`None`] if the server already has `max_connections` open connections.
+    pub fn open(self: &Arc<Self>, max_connections: usize) -> Option<ConnectionGuard> {
+        let reserved = self
+            .active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| {
+                match active < max_connections {
+                    true => Some(active + 1),
+                    false => None,
+                }
+            });
+        match reserved {
+            Ok(_) => Some(ConnectionGuard {
+                stats: self.clone(),
+            }),
+            Err(_) => None,
+        }
+    }
+}
+
+/// Holds a connection slot, the slot is released when the guard is dropped.
+#[derive(Debug)]
+pub struct ConnectionGuard {
+    stats: Arc<ConnectionStats>,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.stats.active.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Fixed window message counter for a single connection.
+#[derive(Debug)]
+pub struct RateLimiter {
+    max_messages: usize,
+    window: Duration,
+    window_start: Instant,
+    count: usize,
+}
+
+impl RateLimiter {
+    pub fn new(limits: &ConnectionLimits) -> Self {
+        Self {
+            max_messages: limits.max_messages,
+            window: limits.rate_window,
+            window_start: Instant::now(),
+            count: 0,
+        }
+    }
+
+    /// Registers a message, returns false if the connection is over its limit.
+    pub fn register(&mut self) -> bool {
+        let now = Instant::now();
+        if now.duration_since(self.window_start) >= self.window {
+            self.window_start = now;
+            self.count = 0;
+        }
+        self.count += 1;
+        self.count <= self.max_messages
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::{ConnectionLimits, ConnectionStats, DisconnectReason, RateLimiter};
+
+    #[test]
+    fn test_connection_slots() {
+        let stats = Arc::new(ConnectionStats::default());
+        let first = stats.open(2);
+        let second = stats.open(2);
+        assert!(first.is_some());
+        assert!(second.is_some());
+        assert!(stats.open(2).is_none());
+        assert_eq!(stats.active(), 2);
+        drop(first);
+        assert_eq!(stats.active(), 1);
+        assert!(stats.open(2).is_some());
+    }
+
+    #[test]
+    fn test_record() {
+        let stats = ConnectionStats::default();
+        stats.record("test", DisconnectReason::RateLimited);
+        stats.record("test", DisconnectReason::RateLimited);
+        stats.record("test", DisconnectReason::FrameTooLarge);
+        assert_eq!(stats.count(DisconnectReason::RateLimited), 2);
+        assert_eq!(stats.count(DisconnectReason::FrameTooLarge), 1);
+        assert_eq!(stats.count(DisconnectReason::IdleTimeout), 0);
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let limits = ConnectionLimits {
+            max_messages: 3,
+            rate_window: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let mut limiter = RateLimiter::new(&limits);
+        assert!(limiter.register());
+        assert!(limiter.register());
+        assert!(limiter.register());
+        assert!(!limiter.register());
+    }
+}
@@ -5,6 +5,8 @@ use tokio;
 use tokio::sync::broadcast;
 
 use super::event;
+use super::limits::{ConnectionLimits, ConnectionStats, DisconnectReason};
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy)]
 pub enum PlayerError {
@@ -16,6 +18,9 @@ pub enum PlayerError {
 
     /// Thrown when a player did not respond to KeepAlive
     Disconnected,
+
+    /// Thrown when a player broke one of the [`ConnectionLimits`](super::limits::ConnectionLimits)
+    Kicked(DisconnectReason),
 }
 
 #[derive(Debug, Clone)]
@@ -29,6 +34,8 @@ pub enum Message<Event: event::GameEvent> {
 #[async_trait]
 pub trait Receiver<Event: event::GameEvent>: std::fmt::Debug {
     fn subscribe(&mut self) -> Result<broadcast::Receiver<Message<Event>>, PlayerError>;
+    /// Applies the given limits to this connection, breaches are counted in `stats`.
+    fn limit(&mut self, limits: ConnectionLimits, stats: Arc<ConnectionStats>);
     async fn receive(mut self) -> Result<(), PlayerError>;
 }
 pub trait ReassignUid {
@@ -1,13 +1,17 @@
 use super::{EqPlayer, Id, Message, New, Player, PlayerError};
 use crate::engine::event::GameEvent;
+use crate::engine::limits::{ConnectionLimits, ConnectionStats, DisconnectReason, RateLimiter};
 use async_trait::async_trait;
 use std::marker::PhantomData;
+use std::sync::Arc;
+use tokio::io::AsyncReadExt;
 use tokio::net::{
     tcp::{OwnedReadHalf, OwnedWriteHalf},
     TcpStream,
 };
 use tokio::sync::broadcast::{self, Receiver, Sender};
 use tokio::sync::Mutex;
+use tracing::debug;
 pub trait TcpPlayerState: std::fmt::Debug + Send {}
 #[derive(Debug)]
 pub struct Whole {}
@@ -34,6 +38,8 @@ pub struct TcpReceiver<const CAPACITY: usize, Event: GameEvent> {
     id: usize,
     sender: Sender<Message<Event>>,
     mutex: Mutex<PhantomData<bool>>,
+    limits: ConnectionLimits,
+    stats: Arc<ConnectionStats>,
 }
 
 #[async_trait]
@@ -42,7 +48,7 @@ impl<const CAPACITY: usize, STATE: TcpPlayerState, Event: GameEvent> Player<Even
 {
     async fn send(&mut self, event: Event) -> Result<(), PlayerError> {
         let _ = self.mutex.lock().await;
-        println!("Sending {:?}", event);
+        debug!(uid = self.id, ?event, "Sending");
         let mut json: Vec<u8> = event.into();
         json.push(0);
 
@@ -96,6 +102,8 @@ impl<Event: GameEvent, const CAPACITY: usize, const BUFFER_SIZE: usize>
                 id,
                 sender: sender,
                 mutex: Mutex::new(PhantomData),
+                limits: ConnectionLimits::default(),
+                stats: Arc::new(ConnectionStats::default()),
             },
         )
     }
@@ -123,6 +131,8 @@ impl<const CAPACITY: usize, STATE: TcpPlayerState, Event: GameEvent>
 impl<const CAPACITY: usize, Event: GameEvent> New<Event, CAPACITY> for std::net::TcpStream {
     type Output = TcpPlayer<CAPACITY, Whole, Event>;
     fn new(self, uid: usize) -> Self::Output {
+        // Tokio requires the socket to be in non blocking mode
+        self.set_nonblocking(true).unwrap();
         let stream = TcpStream::from_std(self).unwrap();
         TcpPlayer::new(stream, uid)
     }
@@ -144,50 +154,48 @@ impl<const CAPACITY: usize, Event: GameEvent + Sync> crate::engine::player::Rece
         Ok(sender.subscribe())
     }
 
+    fn limit(&mut self, limits: ConnectionLimits, stats: Arc<ConnectionStats>) {
+        self.limits = limits;
+        self.stats = stats;
+    }
+
     async fn receive(mut self) -> Result<(), PlayerError> {
+        let peer = format!("{:?}", self.reader.peer_addr());
+        let mut limiter = RateLimiter::new(&self.limits);
+        let mut pending: Vec<u8> = Vec::new();
         loop {
             let mut buffer = vec![0; 128];
-            {
-                let _ = self.mutex.lock().await;
-                match self.reader.try_read(&mut buffer) {
-                    Err(_) => {}
-                    Ok(n) => {
-                        if n == 0 {
-                            self.sender
-                                .send(Message::Received {
-                                    event: Err(PlayerError::Disconnected),
-                                    user: self.id.clone(),
-                                })
-                                .unwrap();
-                            return Ok(());
-                        }
-                    }
-                };
+            let n = self.reader.read(&mut buffer).await.unwrap_or_default();
+            if n == 0 {
+                return self.close(PlayerError::Disconnected);
             }
-            let events: Vec<Event> = {
+            // Frames are delimited by null bytes, these carry no information
+            pending.extend(buffer[..n].iter().filter(|byte| **byte != 0));
+
+            let (events, failures) = {
                 let _ = self.mutex.lock().await;
-                while let Some(&0) = buffer.last() {
-                    buffer.pop();
-                }
-                match serde_json::from_slice::<Vec<Event>>(&buffer) {
-                    Ok(vec) => vec,
-                    // If it is not a vec of events, see if it is a single event
-                    Err(_) => match serde_json::from_slice::<Event>(&buffer) {
-                        Ok(event) => vec![event],
-                        Err(_) => {
-                            continue;
-                        }
-                    },
-                }
+                Self::parse(&mut pending)
             };
 
+            // Both valid and invalid messages count towards the rate limit
+            for _ in 0..(events.len() + failures) {
+                if !limiter.register() {
+                    self.stats.record(&peer, DisconnectReason::RateLimited);
+                    return self.close(PlayerError::Kicked(DisconnectReason::RateLimited));
+                }
+            }
+            if pending.len() > self.limits.max_frame_size {
+                self.stats.record(&peer, DisconnectReason::FrameTooLarge);
+                return self.close(PlayerError::Kicked(DisconnectReason::FrameTooLarge));
+            }
+
             for event in events.iter() {
                 {
                     let _ = self.mutex.lock().await;
                     // Re package in to a nice little message
                     let msg: Message<Event> = Message::Received {
                         event: Ok(event.clone()),
-                        user: self.id.clone(),
+                        user: self.id,
                     };
                     self.sender.send(msg).unwrap();
                 }
@@ -195,3 +203,102 @@ impl<const CAPACITY: usize, Event: GameEvent + Sync> crate::engine::player::Rece
         }
     }
 }
+
+impl<const CAPACITY: usize, Event: GameEvent> TcpReceiver<CAPACITY, Event> {
+    /// Notifies the subscribers that the connection is closed.
+    fn close(&self, error: PlayerError) -> Result<(), PlayerError> {
+        // There might not be any subscribers left, that is fine
+        let _ = self.sender.send(Message::Received {
+            event: Err(error),
+            user: self.id,
+        });
+        Ok(())
+    }
+
+    /// Parses all complete frames in the buffer.
+    ///
+    /// Complete frames are removed from the buffer, a trailing partial frame is kept
+    /// until the rest of it arrives. Returns the parsed events and the number of
+    /// frames that could not be parsed.
+    fn parse(buffer: &mut Vec<u8>) -> (Vec<Event>, usize) {
+        // A vec of events
+        if let Ok(events) = serde_json::from_slice::<Vec<Event>>(buffer) {
+            buffer.clear();
+            return (events, 0);
+        }
+        // Or one or more events back to back
+        let mut events = Vec::new();
+        let mut stream = serde_json::Deserializer::from_slice(buffer).into_iter::<Event>();
+        let mut consumed = 0;
+        let mut failures = 0;
+        loop {
+            match stream.next() {
+                Some(Ok(event)) => {
+                    events.push(event);
+                    consumed = stream.byte_offset();
+                }
+                Some(Err(e)) if e.is_eof() => break,
+                Some(Err(_)) => {
+                    // Garbage, there is no way to find the next frame so drop it all
+                    failures += 1;
+                    consumed = buffer.len();
+                    break;
+                }
+                None => {
+                    consumed = buffer.len();
+                    break;
+                }
+            }
+        }
+        buffer.drain(..consumed);
+        (events, failures)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::event::BackendEvent;
+
+    use super::TcpReceiver;
+
+    type Receiver = TcpReceiver<4, BackendEvent>;
+
+    #[test]
+    fn test_parse_single_and_vec() {
+        let mut buffer = br#""Resend""#.to_vec();
+        let (events, failures) = Receiver::parse(&mut buffer);
+        assert_eq!(events, vec![BackendEvent::Resend]);
+        assert_eq!(failures, 0);
+        assert!(buffer.is_empty());
+
+        let mut buffer = br#"["Resend","UnexpectedMessage"]"#.to_vec();
+        let (events, _) = Receiver::parse(&mut buffer);
+        assert_eq!(
+            events,
+            vec![BackendEvent::Resend, BackendEvent::UnexpectedMessage]
+        );
+    }
+
+    #[test]
+    fn test_parse_partial_frame() {
+        let mut buffer = br#""Resend"{"Connec"#.to_vec();
+        let (events, failures) = Receiver::parse(&mut buffer);
+        assert_eq!(events, vec![BackendEvent::Resend]);
+        assert_eq!(failures, 0);
+        assert_eq!(buffer, br#"{"Connec"#.to_vec());
+
+        buffer.extend(br#"ted":1}"#);
+        let (events, _) = Receiver::parse(&mut buffer);
+        assert_eq!(events, vec![BackendEvent::Connected(1)]);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_parse_garbage() {
+        let mut buffer = b"asd".to_vec();
+        let (events, failures) = Receiver::parse(&mut buffer);
+        assert!(events.is_empty());
+        assert_eq!(failures, 1);
+        assert!(buffer.is_empty());
+    }
+}
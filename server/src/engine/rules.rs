@@ -1,5 +1,7 @@
 use std::marker::PhantomData;
 
+use async_trait::async_trait;
+
 use super::event::{self, GameEvent};
 
 pub trait ActionStatus {}
@@ -45,6 +47,10 @@ pub enum Error {
     UnexpectedMessage,
     /// Thrown when the selected card is out of range
     NoSuchCard,
+    /// Thrown when a player sent a message while a request was waiting for their answer.
+    ///
+    /// The message was handled, the request keeps waiting and the player is not told off.
+    NotAResponse,
 }
 
 pub trait RuleEngine {
@@ -68,10 +74,91 @@ pub trait RuleEngine {
         players: &Vec<usize>,
         message: &Action<New, Self::Event>,
     ) -> Result<(), Error>;
+    /// Returns true while the game has not started, i.e. new players can join.
+    fn accepts_players(&self) -> bool {
+        true
+    }
+    /// Returns true once the game is over and the lobby can be closed.
+    fn finished(&self) -> bool {
+        false
+    }
+}
+
+/// Async version of [`RuleEngine`].
+///
+/// This is what the [`Lobby`](super::session::Lobby) drives, it allows a rule set to await I/O,
+/// such as persisting a snapshot or asking a bot for a move, without blocking the lobby.
+/// Every [`RuleEngine`] is an [`AsyncRuleEngine`] through a blanket implementation, so purely
+/// synchronous rule sets only need to implement [`RuleEngine`].
+// Mirrors the signatures of `RuleEngine` so that the blanket implementation is a plain forward
+#[allow(clippy::ptr_arg)]
+#[async_trait]
+pub trait AsyncRuleEngine: Send {
+    type Event: event::GameEvent + Send;
+
+    /// See [`RuleEngine::get_next_action`].
+    async fn get_next_action(
+        &mut self,
+        players: &Vec<usize>,
+    ) -> (tokio::time::Duration, Vec<Action<New, Self::Event>>);
+    /// See [`RuleEngine::register_response`].
+    async fn register_response(
+        &mut self,
+        players: &Vec<usize>,
+        response: (Self::Event, &Action<Received, Self::Event>),
+    ) -> Result<Action<Completed, Self::Event>, Error>;
+    /// See [`RuleEngine::register_message`].
+    async fn register_message(
+        &mut self,
+        players: &Vec<usize>,
+        message: &Action<New, Self::Event>,
+    ) -> Result<(), Error>;
+    /// Returns true while the game has not started, i.e. new players can join.
+    fn accepts_players(&self) -> bool {
+        true
+    }
+    /// Returns true once the game is over and the lobby can be closed.
+    fn finished(&self) -> bool {
+        false
+    }
+}
+
+#[async_trait]
+impl<R: RuleEngine + Send> AsyncRuleEngine for R {
+    type Event = R::Event;
+
+    async fn get_next_action(
+        &mut self,
+        players: &Vec<usize>,
+    ) -> (tokio::time::Duration, Vec<Action<New, Self::Event>>) {
+        RuleEngine::get_next_action(self, players)
+    }
+    async fn register_response(
+        &mut self,
+        players: &Vec<usize>,
+        response: (Self::Event, &Action<Received, Self::Event>),
+    ) -> Result<Action<Completed, Self::Event>, Error> {
+        RuleEngine::register_response(self, players, response)
+    }
+    async fn register_message(
+        &mut self,
+        players: &Vec<usize>,
+        message: &Action<New, Self::Event>,
+    ) -> Result<(), Error> {
+        RuleEngine::register_message(self, players, message)
+    }
+    fn accepts_players(&self) -> bool {
+        RuleEngine::accepts_players(self)
+    }
+    fn finished(&self) -> bool {
+        RuleEngine::finished(self)
+    }
 }
 
 pub trait Instantiable {
-    fn new() -> Self;
+    /// Settings that every game created by the server starts from
+    type Config: Default + Clone + Send + Sync + 'static;
+    fn new(config: Self::Config) -> Self;
 }
 
 macro_rules! impl_status {
@@ -1,10 +1,13 @@
+mod manager;
 use super::event::BackendEvent;
 
 use super::event::GameEvent;
+use super::limits::{ConnectionLimits, ConnectionStats};
 use super::player::{
     EqPlayer, Id, New, Player, PlayerError, Receiver, Split, TcpPlayer, TcpReceiver, WriteEnabled,
 };
-use super::rules::{self, Action, RuleEngine};
+use super::rules::{self, Action, AsyncRuleEngine};
+use async_trait::async_trait;
 use std::borrow::BorrowMut;
 use std::cell::RefCell;
 use std::sync::Arc;
@@ -13,6 +16,9 @@ use tokio::sync::broadcast;
 use tokio::sync::mpsc;
 use tokio::sync::Mutex;
 use tokio::time::sleep;
+use tracing::{debug, info, info_span, warn, Instrument, Span};
+
+pub use manager::*;
 
 #[derive(Debug)]
 pub enum SessionError {
@@ -28,32 +34,39 @@ pub enum SessionError {
 
 pub type MessageBuss<Event> = mpsc::Receiver<(usize, Event)>;
 
-pub trait Session<Event: GameEvent, const BUFFER_SIZE: usize, const CAPACITY: usize> {
+/// Owns the players from the moment they connect until they leave.
+#[async_trait]
+pub trait Session<Event: GameEvent, const BUFFER_SIZE: usize, const CAPACITY: usize>: Send {
     type Error;
     fn new() -> Self;
-    fn delete(&mut self, uid: usize) -> Result<Box<RefCell<dyn Player<Event>>>, Self::Error>;
+    /// Removes the player from the session returning the [`Player`] half of it
+    async fn delete(&mut self, uid: usize) -> Result<Box<RefCell<dyn Player<Event>>>, Self::Error>;
+    /// Adds a new player to the session
+    ///
+    /// Returns the players uid and a subscription to the messages that the player sends.
     fn add<
-        R: Receiver<Event>,
-        P: Player<Event> + Split<Event, BUFFER_SIZE, ReadPart = R> + 'static,
-        T: New<Event, CAPACITY, Output = P>,
+        R: Receiver<Event> + Send + 'static,
+        P: Split<Event, BUFFER_SIZE, ReadPart = R>,
+        T: New<Event, CAPACITY, Output = P> + EqPlayer,
     >(
         &mut self,
         user: T,
-    ) -> (usize, dyn Receiver<Event>);
+    ) -> Result<(usize, broadcast::Receiver<super::player::Message<Event>>), Self::Error>
+    where
+        P::WritePart: 'static;
 }
 
 pub trait LobbyInterface<Event: GameEvent> {
     /// Connects a specific player to a specific session   
-    fn connect<P: Player<Event> + 'static>(
-        &mut self,
-        player: Box<RefCell<P>>,
-    ) -> Result<(), SessionError>;
+    fn connect(&mut self, player: Box<RefCell<dyn Player<Event>>>) -> Result<(), SessionError>;
     /// Disconnects a player from a session
     ///
     /// The player is stored in a temporary queue to allow reconnects
     fn disconnect(&mut self, player: usize) -> Result<(), SessionError>;
     /// Closes the session
     fn close(self) -> Vec<Box<RefCell<dyn Player<Event>>>>;
+    /// Returns true if no more players can join the session
+    fn full(&self) -> bool;
 }
 
 pub trait PlayerFromTcpStream<const BUFFER_SIZE: usize, const CAPACITY: usize, Event: GameEvent> {
@@ -74,7 +87,8 @@ pub trait PlayerFromTcpStream<const BUFFER_SIZE: usize, const CAPACITY: usize, E
 }
 
 /// Our concrete lobby implementation
-pub struct Lobby<R: RuleEngine, const CAPACITY: usize> {
+pub struct Lobby<R: AsyncRuleEngine, const CAPACITY: usize> {
+    id: usize,
     players: Vec<Box<RefCell<dyn Player<R::Event>>>>,
     disconnected: Vec<(usize, Box<RefCell<dyn Player<R::Event>>>)>,
     rules: R,
@@ -82,9 +96,12 @@ pub struct Lobby<R: RuleEngine, const CAPACITY: usize> {
     event_queue: Arc<Mutex<Vec<rules::Action<rules::Sent, R::Event>>>>,
     received_events: Arc<Mutex<Vec<(R::Event, rules::Action<rules::Received, R::Event>)>>>,
     user_counter: usize,
+    limits: ConnectionLimits,
+    stats: Arc<ConnectionStats>,
+    span: Span,
 }
 
-impl<R: RuleEngine, const CAPACITY: usize> LobbyInterface<R::Event> for Lobby<R, CAPACITY> {
+impl<R: AsyncRuleEngine, const CAPACITY: usize> LobbyInterface<R::Event> for Lobby<R, CAPACITY> {
     /// Closes the session
     fn close(self) -> Vec<Box<RefCell<dyn Player<R::Event>>>> {
         // Maybe we should notify the players here.
@@ -92,12 +109,7 @@ impl<R: RuleEngine, const CAPACITY: usize> LobbyInterface<R::Event> for Lobby<R,
     }
 
     /// Connects a specific player to a specific session   
-    fn connect<P: Player<R::Event> + 'static>(
-        &mut self,
-        player: Box<RefCell<P>>,
-    ) -> Result<(), SessionError> {
-        println!("{:?}", self.players.len());
-
+    fn connect(&mut self, player: Box<RefCell<dyn Player<R::Event>>>) -> Result<(), SessionError> {
         let num_players = self.players.len();
         match num_players >= CAPACITY {
             true => Err(SessionError::LobbyFull),
@@ -116,7 +128,7 @@ impl<R: RuleEngine, const CAPACITY: usize> LobbyInterface<R::Event> for Lobby<R,
                 break;
             }
         }
-        println!("Deleting user {:?}", id);
+        info!(uid = player, "Player disconnected");
         // This should move the player to some intermediate place so a player can recover their connection
         match id {
             Some(idx) => {
@@ -127,10 +139,13 @@ impl<R: RuleEngine, const CAPACITY: usize> LobbyInterface<R::Event> for Lobby<R,
             None => Err(SessionError::NoSuchPlayer),
         }
     }
+    fn full(&self) -> bool {
+        self.players.len() >= CAPACITY
+    }
 }
 
 impl<
-        R: RuleEngine + rules::Instantiable + 'static,
+        R: AsyncRuleEngine + rules::Instantiable + 'static,
         const CAPACITY: usize,
         const BUFFER_SIZE: usize,
     > PlayerFromTcpStream<BUFFER_SIZE, CAPACITY, R::Event> for Lobby<R, CAPACITY>
@@ -150,6 +165,9 @@ impl<
         user: T,
     ) -> Result<(usize, broadcast::Receiver<super::player::Message<R::Event>>), SessionError> {
         // Check if player is disconnected or not
+        if self.full() {
+            return Err(SessionError::LobbyFull);
+        }
 
         for connected_player in &self.players {
             if user.identifier() == connected_player.borrow().identifier() {
@@ -162,14 +180,9 @@ impl<
         let mut remove_idx = None;
         for (idx, (old_uid, old_player)) in self.disconnected.iter_mut().enumerate() {
             let old_player = old_player.as_mut().borrow();
-            println!(
-                "Comparing {:?} == {:?}",
-                user.identifier(),
-                old_player.identifier()
-            );
             if user.identifier() == old_player.identifier() {
                 uid = *old_uid;
-                println!("Matched!");
+                info!(uid, "Player reconnected");
                 remove_idx = Some(idx);
                 break;
             }
@@ -179,6 +192,7 @@ impl<
         }
 
         let (player, mut receiver) = user.new(uid).split();
+        receiver.limit(self.limits, self.stats.clone());
 
         self.players.push(Box::new(RefCell::new(player)));
         let subscriber = receiver.subscribe().unwrap();
@@ -189,7 +203,7 @@ impl<
     }
 }
 
-impl<R: RuleEngine + rules::Instantiable + 'static, const CAPACITY: usize> Lobby<R, CAPACITY> {
+impl<R: AsyncRuleEngine + rules::Instantiable + 'static, const CAPACITY: usize> Lobby<R, CAPACITY> {
     fn players(&self) -> Vec<usize> {
         let mut ret = Vec::new();
         for player in self.players.iter() {
@@ -198,7 +212,13 @@ impl<R: RuleEngine + rules::Instantiable + 'static, const CAPACITY: usize> Lobby
         ret
     }
 
-    pub fn new<ID: Sized>(_: ID, mut channel: MessageBuss<R::Event>) -> Self {
+    pub fn new(
+        id: usize,
+        mut channel: MessageBuss<R::Event>,
+        limits: ConnectionLimits,
+        stats: Arc<ConnectionStats>,
+        config: R::Config,
+    ) -> Self {
         let msg_queue = Arc::new(Mutex::new(Vec::with_capacity(CAPACITY)));
         let sent_events = Arc::new(Mutex::new(Vec::with_capacity(CAPACITY)));
         let received_events = Arc::new(Mutex::new(Vec::with_capacity(CAPACITY)));
@@ -206,56 +226,95 @@ impl<R: RuleEngine + rules::Instantiable + 'static, const CAPACITY: usize> Lobby
         let queue = msg_queue.clone();
         let sent_events_clone = sent_events.clone();
         let received_events_clone = received_events.clone();
+        let span = info_span!("lobby", id);
         // Monitor for events
-        tokio::spawn(async move {
-            loop {
-                Self::monitor(
+        tokio::spawn(
+            async move {
+                while Self::monitor(
                     &mut channel,
                     sent_events_clone.clone(),
                     received_events_clone.clone(),
                     queue.clone(),
                 )
                 .await
+                {}
             }
-        });
+            .instrument(span.clone()),
+        );
 
         Self {
+            id,
             players: Vec::with_capacity(CAPACITY),
             disconnected: Vec::new(),
-            rules: R::new(),
+            rules: R::new(config),
             event_queue: sent_events.clone(),
             received_events: received_events.clone(),
             message_queue: msg_queue,
             user_counter: 0,
+            limits,
+            stats,
+            span,
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns true if the lobby has a free seat and the game has not started yet
+    pub fn accepts_players(&self) -> bool {
+        !self.full() && !self.started()
+    }
+
+    /// Returns true once the game has started
+    pub fn started(&self) -> bool {
+        !self.rules.accepts_players()
+    }
+
+    /// Returns true once the game is over and the lobby can be closed
+    pub fn finished(&self) -> bool {
+        self.rules.finished()
+    }
+
+    /// Removes a player from the lobby, wether it is connected or not.
+    pub fn remove(
+        &mut self,
+        uid: usize,
+    ) -> Result<Box<RefCell<dyn Player<R::Event>>>, SessionError> {
+        if let Some(idx) = self.players.iter().position(|el| el.borrow().get_id() == uid) {
+            return Ok(self.players.remove(idx));
+        }
+        match self.disconnected.iter().position(|(id, _)| *id == uid) {
+            Some(idx) => Ok(self.disconnected.remove(idx).1),
+            None => Err(SessionError::NoSuchPlayer),
         }
     }
 
     /// Flushes the messages from the message queue returning the flushed messages
     ///
     /// Returns a [`Vec`] of events and the corresponding [`Player`] [`Id`](Player::get_id).
-    fn flush_messages(
+    async fn flush_messages(
         &mut self,
     ) -> (
         Vec<Action<rules::New, R::Event>>,
         Vec<(R::Event, Action<rules::Received, R::Event>)>,
     ) {
-        let mut msg = async_std::task::block_on(async { self.message_queue.lock().await });
-        let mut new_responses =
-            async_std::task::block_on(async { self.received_events.lock().await });
+        let mut msg = self.message_queue.lock().await;
+        let mut new_responses = self.received_events.lock().await;
         let mut messages = Vec::new();
         while let Some(message) = msg.pop() {
-            println!("flushing {:?}", message);
+            debug!(?message, "Flushing message");
             messages.push(message);
         }
         let mut responses = Vec::new();
         while let Some(message) = new_responses.pop() {
-            println!("flushing {:?}", message);
+            debug!(?message, "Flushing response");
             responses.push(message);
         }
         (messages, responses)
     }
 
-    fn send_message(
+    async fn send_message(
         &mut self,
         action: Action<rules::New, R::Event>,
     ) -> Result<Action<rules::Sent, R::Event>, Action<rules::New, R::Event>> {
@@ -270,7 +329,7 @@ impl<R: RuleEngine + rules::Instantiable + 'static, const CAPACITY: usize> Lobby
         if let Some(player) = found_player {
             let id = player.get_id();
             let action = action.action();
-            disconnect = match player.send_blocking(action) {
+            disconnect = match player.send(action).await {
                 Err(PlayerError::Disconnected) => Some(id),
                 _ => None,
             }
@@ -291,11 +350,7 @@ impl<R: RuleEngine + rules::Instantiable + 'static, const CAPACITY: usize> Lobby
         match action {
             Ok(action) => {
                 if action.action().requires_response() {
-                    println!(
-                        "Some event {:?}, requires response {:?}",
-                        action,
-                        action.action().requires_response()
-                    );
+                    debug!(?action, "Awaiting response");
                     event_queue.push(action);
                 }
             }
@@ -306,22 +361,21 @@ impl<R: RuleEngine + rules::Instantiable + 'static, const CAPACITY: usize> Lobby
     }
 
     /// Manages the game logic
-    fn main(&mut self) -> Duration {
+    async fn main(&mut self) -> Duration {
         let players = self.players();
         // We should add a broadcast channel to the game lobby that shuts it down if this panics
         // for now it is better to just panic the thread if an error occurs here
-        let (messages, responses) = self.flush_messages();
+        let (messages, responses) = self.flush_messages().await;
 
         let mut send_queue = Vec::new();
         {
-            let mut event_queue =
-                async_std::task::block_on(async { self.event_queue.lock().await });
+            let mut event_queue = self.event_queue.lock().await;
             for (event, action) in responses {
                 let rules = &mut self.rules;
                 let uid = action.player();
-                match rules.register_response(&players, (event, &action)) {
+                match rules.register_response(&players, (event, &action)).await {
                     Ok(val) => {
-                        println!("Rule engine responded with {:?}", val);
+                        debug!(response = ?val, "Rule engine accepted response");
                     }
                     // If the game did not expect that response the request is not handled and is therefore re enqueued
                     // but at the start of the queue since it is a new request
@@ -332,6 +386,10 @@ impl<R: RuleEngine + rules::Instantiable + 'static, const CAPACITY: usize> Lobby
                             BackendEvent::UnexpectedMessage.into(),
                         ));
                     }
+                    Err(rules::Error::NotAResponse) => {
+                        debug!(uid, "Rule engine handled a message, the request keeps waiting");
+                        event_queue.insert(0, action.degrade());
+                    }
                     Err(rules::Error::UnexpectedMessage) => {
                         event_queue.insert(0, action.degrade());
                         send_queue.push(Action::<rules::New, R::Event>::new(
@@ -340,7 +398,7 @@ impl<R: RuleEngine + rules::Instantiable + 'static, const CAPACITY: usize> Lobby
                         ));
                     }
                     Err(e) => {
-                        println!("{:?}", e);
+                        warn!(error = ?e, uid, "Rule engine rejected response");
                     }
                 }
             }
@@ -349,8 +407,8 @@ impl<R: RuleEngine + rules::Instantiable + 'static, const CAPACITY: usize> Lobby
         for action in messages {
             let rules = &mut self.rules;
             let uid = action.player();
-            println!("cmd : {:?}", action);
-            match rules.register_message(&players, &action) {
+            debug!(?action, "Registering message");
+            match rules.register_message(&players, &action).await {
                 Ok(_) => {}
                 Err(rules::Error::UnexpectedResponse) => {
                     send_queue.push(action);
@@ -362,20 +420,18 @@ impl<R: RuleEngine + rules::Instantiable + 'static, const CAPACITY: usize> Lobby
                     ));
                 }
                 Err(e) => {
-                    println!("{:?}", e);
+                    warn!(error = ?e, uid, "Rule engine rejected message");
                 }
             }
         }
 
-        let (time_to_wait, requested_actions) = self.rules.get_next_action(&players);
+        let (time_to_wait, requested_actions) = self.rules.get_next_action(&players).await;
         send_queue.extend(requested_actions);
         for action in send_queue.iter_mut() {
-            let ret = self.send_message((*action).clone());
-            let msg_queue: &mut Vec<Action<rules::New, R::Event>> =
-                &mut async_std::task::block_on(async { self.message_queue.lock().await });
-            let event_queue: &mut Vec<Action<rules::Sent, R::Event>> =
-                &mut async_std::task::block_on(async { self.event_queue.lock().await });
-            Self::enqueue(msg_queue, event_queue, ret);
+            let ret = self.send_message((*action).clone()).await;
+            let mut msg_queue = self.message_queue.lock().await;
+            let mut event_queue = self.event_queue.lock().await;
+            Self::enqueue(&mut msg_queue, &mut event_queue, ret);
         }
         time_to_wait
     }
@@ -383,19 +439,21 @@ impl<R: RuleEngine + rules::Instantiable + 'static, const CAPACITY: usize> Lobby
 
 // Split all of the async logic from the sync logic for readability
 
-impl<R: RuleEngine + rules::Instantiable + 'static, const CAPACITY: usize> Lobby<R, CAPACITY> {
+impl<R: AsyncRuleEngine + rules::Instantiable + 'static, const CAPACITY: usize> Lobby<R, CAPACITY> {
     /// Monitors the incoming messages and manages the enqueue operations
+    ///
+    /// Returns false once the channel is closed.
     async fn monitor(
         channel: &mut MessageBuss<R::Event>,
         mut sent_events: Arc<Mutex<Vec<Action<rules::Sent, R::Event>>>>,
         mut received_events: Arc<Mutex<Vec<(R::Event, Action<rules::Received, R::Event>)>>>,
         mut queue: Arc<Mutex<Vec<Action<rules::New, R::Event>>>>,
-    ) {
+    ) -> bool {
         let (player, event) = match channel.recv().await {
             //Ok(value) => value,
             //Err(_) => return,
             Some(msg) => msg,
-            None => return,
+            None => return false,
         };
 
         // Lock all the needed mutexes
@@ -420,39 +478,41 @@ impl<R: RuleEngine + rules::Instantiable + 'static, const CAPACITY: usize> Lobby
         if let Some(idx) = found_at {
             // The actions should be handled in order
             let action = sent_locked.remove(idx);
-            println!(
-                "player {:?} responded to {:?} with {:?}",
-                player, action, event
-            );
+            debug!(uid = player, ?action, ?event, "Player responded");
             received_locked.push((event, action.transition()));
         } else {
-            println!(
-                "player {:?} had no action requests and sent {:?}",
-                player, event
-            );
+            debug!(uid = player, ?event, "Player sent an unrequested message");
             queue_locked.push(Action::<rules::New, R::Event>::new(player, event));
         }
+        true
     }
     /// Starts the game.
     ///
     /// This function allows the usage of tokio to spawn game tasks.
+    /// It returns once the game is [`finished`](Self::finished).
     pub async fn start(lobby_ref: Arc<Mutex<RefCell<Self>>>) {
-        loop {
-            let delay = Self::_start(lobby_ref.clone()).await;
-            sleep(match delay {
-                Some(delay) => delay,
-                _ => Duration::from_millis(500),
-            }).await;
+        let span = lobby_ref.lock().await.borrow().span.clone();
+        async move {
+            info!("Lobby started");
+            loop {
+                let delay = Self::_start(lobby_ref.clone()).await;
+                if lobby_ref.lock().await.borrow().finished() {
+                    info!("Game finished");
+                    return;
+                }
+                sleep(match delay {
+                    Some(delay) => delay,
+                    _ => Duration::from_millis(500),
+                })
+                .await;
+            }
         }
+        .instrument(span)
+        .await
     }
     async fn _start(lobby_ref: Arc<Mutex<RefCell<Self>>>) -> Option<Duration> {
-        let lobby_lock = lobby_ref.lock().await;
-        let mut lobby = match lobby_lock.try_borrow_mut() {
-            Ok(lobby) => lobby,
-            Err(_) => {
-                return None;
-            }
-        };
-        Some(lobby.main())
+        let mut lobby_lock = lobby_ref.lock().await;
+        // The mutex guarantees exclusive access, no need to go through the RefCell
+        Some(lobby_lock.get_mut().main().await)
     }
 }
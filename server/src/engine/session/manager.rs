@@ -0,0 +1,528 @@
+//! Defines a session manager that sits above the [`Lobby`].
+//!
+//! The manager owns the players from the moment they connect until they leave.
+//! New players are placed in a waiting room, from there they are seated in the
+//! currently open lobby. Once a game is over the lobby is closed and the players
+//! are moved to a post game room, after a short while they are moved back to the
+//! waiting room so that they can join the next game without reconnecting.
+//!
+//! Players that run inside the server, such as bots, wait in a room of their own and
+//! are only seated in a lobby once a remote player has been seated in it.
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::{broadcast, mpsc, Mutex};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{info, info_span, Instrument};
+
+use super::{Lobby, LobbyInterface, SessionError};
+use crate::engine::admin::{AdminCommand, BotFactory, HELP};
+use crate::engine::event::{BackendEvent, GameEvent};
+use crate::engine::limits::{ConnectionLimits, ConnectionStats, DisconnectReason};
+use crate::engine::player::{EqPlayer, Message, New, Player, Receiver, Split};
+use crate::engine::rules::{AsyncRuleEngine, Instantiable};
+
+/// Maps a player uid to the lobby that it is currently seated in.
+pub type Routes<Event> = Arc<Mutex<HashMap<usize, mpsc::Sender<(usize, Event)>>>>;
+
+/// Creates a player that runs inside the server from its uid and the routing table.
+///
+/// The player answers the lobby by sending its responses through its route.
+pub type LocalPlayer<Event> =
+    Box<dyn FnOnce(usize, Routes<Event>) -> Box<RefCell<dyn Player<Event>>> + Send>;
+
+/// A player that is not seated in any lobby.
+struct Seat<Event: GameEvent> {
+    uid: usize,
+    player: Box<RefCell<dyn Player<Event>>>,
+    since: Instant,
+}
+
+/// A lobby and the task that drives it.
+struct LobbyHandle<R: AsyncRuleEngine, const CAPACITY: usize> {
+    lobby: Arc<Mutex<RefCell<Lobby<R, CAPACITY>>>>,
+    channel: mpsc::Sender<(usize, R::Event)>,
+    task: JoinHandle<()>,
+}
+
+pub struct SessionManager<R: AsyncRuleEngine + Instantiable, const BUFFER_SIZE: usize, const CAPACITY: usize> {
+    /// Players that are connected but not yet seated
+    waiting: Vec<Seat<R::Event>>,
+    /// Players whose game has ended
+    post_game: Vec<Seat<R::Event>>,
+    /// Local players that are not yet seated
+    local: Vec<Seat<R::Event>>,
+    /// Uids of all local players, used to send them back to their room after a game
+    local_uids: HashSet<usize>,
+    /// The lobby that new players are seated in
+    open: Option<LobbyHandle<R, CAPACITY>>,
+    /// True once a remote player has been seated in the open lobby
+    open_has_remote: bool,
+    /// Lobbies with a game in progress
+    running: Vec<LobbyHandle<R, CAPACITY>>,
+    routes: Routes<R::Event>,
+    /// The tasks that read from the connections of the remote players
+    receivers: HashMap<usize, JoinHandle<()>>,
+    user_counter: usize,
+    lobby_counter: usize,
+    limits: ConnectionLimits,
+    stats: Arc<ConnectionStats>,
+    post_game_delay: Duration,
+    /// Configuration handed to every new lobby
+    config: R::Config,
+    /// Creates the bots that are added from the admin socket
+    bot: Option<BotFactory<R::Event>>,
+}
+
+#[async_trait]
+impl<
+        R: AsyncRuleEngine + Instantiable + 'static,
+        const BUFFER_SIZE: usize,
+        const CAPACITY: usize,
+    > super::Session<R::Event, BUFFER_SIZE, CAPACITY> for SessionManager<R, BUFFER_SIZE, CAPACITY>
+{
+    type Error = SessionError;
+
+    fn new() -> Self {
+        Self {
+            waiting: Vec::new(),
+            post_game: Vec::new(),
+            local: Vec::new(),
+            local_uids: HashSet::new(),
+            open: None,
+            open_has_remote: false,
+            running: Vec::new(),
+            routes: Arc::new(Mutex::new(HashMap::new())),
+            receivers: HashMap::new(),
+            user_counter: 0,
+            lobby_counter: 0,
+            limits: ConnectionLimits::default(),
+            stats: Arc::new(ConnectionStats::default()),
+            post_game_delay: Duration::from_secs(10),
+            config: R::Config::default(),
+            bot: None,
+        }
+    }
+
+    async fn delete(
+        &mut self,
+        uid: usize,
+    ) -> Result<Box<RefCell<dyn Player<R::Event>>>, SessionError> {
+        self.routes.lock().await.remove(&uid);
+        self.receivers.remove(&uid);
+        self.local_uids.remove(&uid);
+        for room in [&mut self.waiting, &mut self.post_game, &mut self.local] {
+            if let Some(idx) = room.iter().position(|seat| seat.uid == uid) {
+                return Ok(room.remove(idx).player);
+            }
+        }
+        for handle in self.open.iter().chain(self.running.iter()) {
+            let removed = handle.lobby.lock().await.get_mut().remove(uid);
+            if removed.is_ok() {
+                return removed;
+            }
+        }
+        Err(SessionError::NoSuchPlayer)
+    }
+
+    fn add<
+        Rx: Receiver<R::Event> + Send + 'static,
+        P: Split<R::Event, BUFFER_SIZE, ReadPart = Rx>,
+        T: New<R::Event, CAPACITY, Output = P> + EqPlayer,
+    >(
+        &mut self,
+        user: T,
+    ) -> Result<(usize, broadcast::Receiver<Message<R::Event>>), SessionError>
+    where
+        P::WritePart: 'static,
+    {
+        for seat in self.waiting.iter().chain(self.post_game.iter()) {
+            if user.identifier() == seat.player.borrow().identifier() {
+                return Err(SessionError::PlayerAlreadyConnected);
+            }
+        }
+        let uid = self.user_counter;
+        self.user_counter += 1;
+
+        let (player, mut receiver) = user.new(uid).split();
+        receiver.limit(self.limits, self.stats.clone());
+        let subscriber = receiver.subscribe().map_err(SessionError::_PlayerError)?;
+        let task = tokio::spawn(
+            async move {
+                let _ = receiver.receive().await;
+            }
+            .instrument(info_span!("player", uid)),
+        );
+        self.receivers.insert(uid, task);
+
+        info!(uid, "Player entered the waiting room");
+        self.waiting.push(Seat {
+            uid,
+            player: Box::new(RefCell::new(player)),
+            since: Instant::now(),
+        });
+        Ok((uid, subscriber))
+    }
+}
+
+impl<
+        R: AsyncRuleEngine + Instantiable + 'static,
+        const BUFFER_SIZE: usize,
+        const CAPACITY: usize,
+    > SessionManager<R, BUFFER_SIZE, CAPACITY>
+{
+    /// Applies the given limits to all future connections.
+    pub fn with_limits(mut self, limits: ConnectionLimits, stats: Arc<ConnectionStats>) -> Self {
+        self.limits = limits;
+        self.stats = stats;
+        self
+    }
+
+    /// Sets the configuration that every new game is created with.
+    pub fn with_config(mut self, config: R::Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets the time that players spend in the post game room before they are re queued.
+    pub fn with_post_game_delay(mut self, delay: Duration) -> Self {
+        self.post_game_delay = delay;
+        self
+    }
+
+    /// Sets how the bots that are added from the [`admin`](crate::engine::admin) socket
+    /// are created.
+    pub fn with_bot(mut self, bot: BotFactory<R::Event>) -> Self {
+        self.bot = Some(bot);
+        self
+    }
+
+    /// Runs a command from the [`admin`](crate::engine::admin) socket and returns the answer.
+    pub fn execute(&mut self, command: AdminCommand) -> String {
+        info!(?command, "Admin command");
+        match command {
+            AdminCommand::Bots(count) => match self.bot.clone() {
+                Some(bot) => {
+                    for _ in 0..count {
+                        self.add_local(bot());
+                    }
+                    format!("Added {} bots", count)
+                }
+                None => "This server has no bots".to_owned(),
+            },
+            AdminCommand::Status => format!(
+                "connections {}, waiting {}, post game {}, bots waiting {}, lobbies {}",
+                self.stats.active(),
+                self.waiting.len(),
+                self.post_game.len(),
+                self.local.len(),
+                self.running.len() + usize::from(self.open.is_some()),
+            ),
+            AdminCommand::Help => HELP.to_owned(),
+        }
+    }
+
+    /// Adds a player that runs inside the server, returning its uid.
+    ///
+    /// Local players are seated once a remote player has taken a seat in the open lobby,
+    /// they are never dropped for idling.
+    pub fn add_local(&mut self, make: LocalPlayer<R::Event>) -> usize {
+        let uid = self.user_counter;
+        self.user_counter += 1;
+        let player = make(uid, self.routes.clone());
+        info!(uid, "Local player entered the waiting room");
+        self.local_uids.insert(uid);
+        self.local.push(Seat {
+            uid,
+            player,
+            since: Instant::now(),
+        });
+        uid
+    }
+
+    /// Returns a handle to the routing table, used to forward player messages to their lobby.
+    pub fn routes(&self) -> Routes<R::Event> {
+        self.routes.clone()
+    }
+
+    /// Moves the players between the rooms and the lobbies.
+    ///
+    /// - Closes finished lobbies moving their players to the post game room
+    /// - Moves players that have been in the post game room long enough to the waiting room
+    /// - Drops players that have waited for a seat for longer than the idle timeout
+    /// - Seats waiting players in the open lobby
+    pub async fn tick(&mut self) {
+        self.close_finished().await;
+
+        let mut post_game = Vec::with_capacity(self.post_game.len());
+        for mut seat in self.post_game.drain(..) {
+            if seat.since.elapsed() >= self.post_game_delay {
+                info!(uid = seat.uid, "Player re entered the waiting room");
+                seat.since = Instant::now();
+                match self.local_uids.contains(&seat.uid) {
+                    true => self.local.push(seat),
+                    false => self.waiting.push(seat),
+                }
+            } else {
+                post_game.push(seat);
+            }
+        }
+        self.post_game = post_game;
+
+        let mut waiting = Vec::with_capacity(self.waiting.len());
+        for seat in std::mem::take(&mut self.waiting) {
+            match seat.since.elapsed() <= self.limits.idle_timeout {
+                true => waiting.push(seat),
+                false => self.kick(seat, DisconnectReason::IdleTimeout).await,
+            }
+        }
+        self.waiting = waiting;
+
+        self.seat_waiting().await;
+    }
+
+    /// Tells the player why it is dropped and closes its connection.
+    ///
+    /// Dropping the player shuts down the write half, once the receiver is stopped the
+    /// connection monitor sees the channel close and releases the connection slot.
+    async fn kick(&mut self, mut seat: Seat<R::Event>, reason: DisconnectReason) {
+        let uid = seat.uid;
+        let player = seat.player.get_mut();
+        self.stats.record(&player.identifier(), reason);
+        // The client might already be gone
+        let _ = player.send(BackendEvent::Kicked(reason).into()).await;
+        drop(seat);
+        if let Some(task) = self.receivers.remove(&uid) {
+            task.abort();
+        }
+    }
+
+    /// Closes all lobbies whose game is over and moves the players to the post game room.
+    async fn close_finished(&mut self) {
+        let mut running = Vec::with_capacity(self.running.len());
+        for handle in self.running.drain(..) {
+            // The lobby task returns once the game is finished
+            if !handle.task.is_finished() {
+                running.push(handle);
+                continue;
+            }
+            let lobby = match Arc::try_unwrap(handle.lobby) {
+                Ok(lobby) => lobby.into_inner().into_inner(),
+                Err(lobby) => {
+                    running.push(LobbyHandle { lobby, ..handle });
+                    continue;
+                }
+            };
+            info!(lobby = lobby.id(), "Closing lobby");
+            let players = lobby.close();
+            let mut routes = self.routes.lock().await;
+            for player in players {
+                let uid = player.borrow().get_id();
+                routes.remove(&uid);
+                self.post_game.push(Seat {
+                    uid,
+                    player,
+                    since: Instant::now(),
+                });
+            }
+        }
+        self.running = running;
+    }
+
+    /// Seats the waiting players in the open lobby, opening a new one if needed.
+    async fn seat_waiting(&mut self) {
+        if let Some(handle) = &self.open {
+            let started =
+                handle.lobby.lock().await.get_mut().started() || handle.task.is_finished();
+            if started {
+                self.running.extend(self.open.take());
+                self.open_has_remote = false;
+            }
+        }
+        if self.waiting.is_empty() && !self.open_has_remote {
+            return;
+        }
+        if self.open.is_none() {
+            self.open = Some(self.open_lobby());
+        }
+        // Just made sure that there is an open lobby
+        let handle = self.open.as_ref().unwrap();
+
+        let mut lobby = handle.lobby.lock().await;
+        let lobby = lobby.get_mut();
+        let mut routes = self.routes.lock().await;
+        while lobby.accepts_players() && !self.waiting.is_empty() {
+            let seat = self.waiting.remove(0);
+            info!(uid = seat.uid, lobby = lobby.id(), "Seating player");
+            routes.insert(seat.uid, handle.channel.clone());
+            // The lobby accepts players, this can not fail
+            lobby.connect(seat.player).unwrap();
+            self.open_has_remote = true;
+        }
+        while self.open_has_remote && lobby.accepts_players() && !self.local.is_empty() {
+            let seat = self.local.remove(0);
+            info!(uid = seat.uid, lobby = lobby.id(), "Seating local player");
+            routes.insert(seat.uid, handle.channel.clone());
+            lobby.connect(seat.player).unwrap();
+        }
+    }
+
+    fn open_lobby(&mut self) -> LobbyHandle<R, CAPACITY> {
+        let (channel, event_rx) = mpsc::channel(32);
+        let lobby = Arc::new(Mutex::new(RefCell::new(Lobby::new(
+            self.lobby_counter,
+            event_rx,
+            self.limits,
+            self.stats.clone(),
+            self.config.clone(),
+        ))));
+        info!(lobby = self.lobby_counter, "Opening lobby");
+        self.lobby_counter += 1;
+        let task = tokio::spawn(Lobby::<R, CAPACITY>::start(lobby.clone()));
+        LobbyHandle {
+            lobby,
+            channel,
+            task,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::io::Read;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use tokio::sync::Mutex;
+
+    use super::SessionManager;
+    use crate::engine::admin::{AdminCommand, HELP};
+    use crate::engine::event::BackendEvent;
+    use crate::engine::limits::{ConnectionLimits, ConnectionStats, DisconnectReason};
+    use crate::engine::rules::{Action, Completed, Error, Instantiable, New, Received, RuleEngine};
+    use crate::engine::player::{Player, PlayerError};
+    use crate::engine::session::Session;
+
+    /// A game that never starts
+    struct Idle;
+
+    impl RuleEngine for Idle {
+        type Event = BackendEvent;
+        fn get_next_action(
+            &mut self,
+            _players: &Vec<usize>,
+        ) -> (Duration, Vec<Action<New, BackendEvent>>) {
+            (Duration::from_millis(100), Vec::new())
+        }
+        fn register_response(
+            &mut self,
+            _players: &Vec<usize>,
+            _response: (BackendEvent, &Action<Received, BackendEvent>),
+        ) -> Result<Action<Completed, BackendEvent>, Error> {
+            Err(Error::UnexpectedResponse)
+        }
+        fn register_message(
+            &mut self,
+            _players: &Vec<usize>,
+            _message: &Action<New, BackendEvent>,
+        ) -> Result<(), Error> {
+            Err(Error::UnexpectedMessage)
+        }
+    }
+
+    impl Instantiable for Idle {
+        type Config = ();
+        fn new(_config: ()) -> Self {
+            Self
+        }
+    }
+
+    /// A local player that never answers
+    #[derive(Debug)]
+    struct Silent(usize);
+
+    #[async_trait]
+    impl Player<BackendEvent> for Silent {
+        fn get_id(&self) -> usize {
+            self.0
+        }
+        async fn send(&mut self, _event: BackendEvent) -> Result<(), PlayerError> {
+            Ok(())
+        }
+        fn identifier(&self) -> String {
+            format!("Silent {}", self.0)
+        }
+    }
+
+    #[test]
+    fn test_admin() {
+        let mut manager = SessionManager::<Idle, 4, 4>::new();
+        assert_eq!(manager.execute(AdminCommand::Bots(2)), "This server has no bots");
+        assert_eq!(manager.execute(AdminCommand::Help), HELP);
+
+        let mut manager = manager.with_bot(Arc::new(|| {
+            Box::new(|uid, _routes| {
+                Box::new(RefCell::new(Silent(uid))) as Box<RefCell<dyn Player<BackendEvent>>>
+            })
+        }));
+        assert_eq!(manager.execute(AdminCommand::Bots(2)), "Added 2 bots");
+        assert_eq!(
+            manager.execute(AdminCommand::Status),
+            "connections 0, waiting 0, post game 0, bots waiting 2, lobbies 0"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_idle_timeout_frees_slot() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+
+        let limits = ConnectionLimits {
+            max_connections: 1,
+            idle_timeout: Duration::from_millis(10),
+            ..Default::default()
+        };
+        let stats = Arc::new(ConnectionStats::default());
+        let guard = stats.open(limits.max_connections).unwrap();
+        assert!(stats.open(limits.max_connections).is_none());
+
+        let mut manager = SessionManager::<Idle, 4, 4>::new().with_limits(limits, stats.clone());
+        let (uid, rx) = manager.add(stream).unwrap();
+        let routes = manager.routes();
+        let manager = Arc::new(Mutex::new(RefCell::new(manager)));
+        let monitor = tokio::spawn(crate::engine::monitor::<BackendEvent, 4, 4, _>(
+            manager.clone(),
+            uid,
+            rx,
+            routes,
+            guard,
+        ));
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        manager.lock().await.get_mut().tick().await;
+        tokio::time::timeout(Duration::from_secs(5), monitor)
+            .await
+            .expect("the monitor should stop once the player is kicked")
+            .unwrap();
+        assert_eq!(stats.active(), 0);
+        assert_eq!(stats.count(DisconnectReason::IdleTimeout), 1);
+        assert!(stats.open(limits.max_connections).is_some());
+
+        // The client is told why before the connection is closed
+        client
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let mut received = Vec::new();
+        client.read_to_end(&mut received).unwrap();
+        let expected: Vec<u8> = BackendEvent::Kicked(DisconnectReason::IdleTimeout).into();
+        assert!(received.starts_with(&expected));
+    }
+}
@@ -1,30 +1,204 @@
-use ::tui::tui::{
-    popup::{info::Info, select::Select},
-    Tui,
+use std::{net::TcpListener, sync::Arc};
+
+use ::tui::{
+    tui::{
+        popup::{info::Info, select::Select},
+        Tui, TuiMonitor,
+    },
+    ui::Ui,
 };
+use server::engine::{self, admin::Admin, limits::ConnectionLimits};
+use tokio::{net::TcpStream, sync::broadcast};
+use tracing::{error, info};
+
+use crate::rule_sets::ServerOptions;
 
 use self::{
-    rules::{cards::AustraliaCard, AustraliaPlayer},
+    bot::{Autopilot, Bot, BotOptions, Strategy},
+    player::{manage_event, manage_event_headless, read_event},
+    protocol::Message,
+    rules::{
+        config::GameConfig,
+        edition::Edition,
+        states::diagram::{self, DiagramFormat},
+        AustraliaEdition, Boomerang, Player,
+    },
+    simulation::{Report, SimulationError, SimulationOptions},
     tui::{
-        map::australia::Map,
         pages::{
-            main_page::MainPage, map_page::DefaultTuiMap, score_popup::Score,
-            show_page::ShowPage,
+            leaderboard::LeaderboardPage, main_page::MainPage, map_page::DefaultTuiMap,
+            score_popup::Score, show_page::ShowPage,
         },
-        ScoreList,
+        EditionUi, ScoreList,
     },
 };
 
+pub mod bot;
 pub mod player;
+pub mod profiles;
 pub mod protocol;
 pub mod rules;
+pub mod score_sheet;
+pub mod simulation;
 pub mod tui;
 
-pub type TuiDefaults = Tui<
-    MainPage<AustraliaCard, AustraliaPlayer>,
-    DefaultTuiMap<Map, ScoreList>,
-    ShowPage<AustraliaCard, AustraliaPlayer>,
+pub type TuiDefaults<E> = Tui<
+    MainPage<E, Player<E>>,
+    DefaultTuiMap<<E as EditionUi>::Map, ScoreList<<E as Edition>::Score>>,
+    ShowPage<<E as Edition>::Card, Player<E>, <E as EditionUi>::Map>,
+    LeaderboardPage,
     Info,
     Select,
-    Score,
+    Score<<E as Edition>::Score>,
 >;
+
+/// The name that this rule set is registered under
+pub const NAME: &str = "australia";
+
+/// The most players that can be seated in one game
+const MAX_PLAYERS: usize = 4;
+
+/// Runs a boomerang australia server
+pub async fn server(listener: TcpListener, limits: ConnectionLimits, options: ServerOptions) {
+    serve::<AustraliaEdition>(listener, limits, options, NAME).await;
+}
+
+/// Runs a boomerang australia client on an already connected stream
+pub async fn client(stream: TcpStream, nickname: Option<String>) {
+    play::<AustraliaEdition>(stream, nickname).await;
+}
+
+/// Runs a headless boomerang australia client on an already connected stream
+pub async fn bot(stream: TcpStream, options: BotOptions, nickname: Option<String>) {
+    play_headless::<AustraliaEdition>(stream, options.strategy(), nickname).await;
+}
+
+/// Simulates boomerang australia games between bots
+pub fn simulate(options: &SimulationOptions) -> Result<Report, SimulationError> {
+    simulation::simulate::<AustraliaEdition>(options)
+}
+
+/// Returns the fingerprint that clients of boomerang australia have to match
+pub fn fingerprint() -> Option<String> {
+    AustraliaEdition::fingerprint()
+}
+
+/// Draws the state machine of boomerang australia
+pub fn diagram(format: DiagramFormat) -> String {
+    diagram::draw::<AustraliaEdition>(NAME, format)
+}
+
+/// Runs a server for any [`Edition`], registered as `name`
+///
+/// The game is configured from the TOML file in the `options` if one is given.
+pub async fn serve<E: Edition>(
+    listener: TcpListener,
+    limits: ConnectionLimits,
+    options: ServerOptions,
+    name: &str,
+) {
+    type Rules<E> = Boomerang<E, MAX_PLAYERS, 2>;
+    if options.bots >= MAX_PLAYERS {
+        error!(bots = options.bots, "At least one seat has to be left for a remote player");
+        return;
+    }
+    let mut config = match options.config {
+        Some(path) => match GameConfig::load(&path) {
+            Ok(config) => config,
+            Err(e) => {
+                error!(error = %e, ?path, "Could not load the game config");
+                return;
+            }
+        },
+        None => GameConfig::default(),
+    };
+    if let Err(e) = config.validate::<E>(MAX_PLAYERS) {
+        error!(error = %e, "Invalid game config");
+        return;
+    }
+    config.check_invariants |= options.check_invariants;
+    config.profiles = options.profiles;
+    config.score_sheets = options.score_sheets;
+    config.score_sheet_format = options.score_sheet_format;
+    info!(?config, bots = options.bots, bot = ?options.bot, "Game config");
+    let admin = match &options.admin {
+        Some(address) => match std::net::TcpListener::bind(address) {
+            Ok(listener) => {
+                let bot = options.bot.clone();
+                Some(Admin {
+                    listener,
+                    bot: Arc::new(move || Bot::<E>::local(bot.clone())),
+                })
+            }
+            Err(e) => {
+                error!(error = ?e, address, "Could not bind the admin socket");
+                return;
+            }
+        },
+        None => None,
+    };
+    let bots = (0..options.bots)
+        .map(|_| Bot::<E>::local(options.bot.clone()))
+        .collect();
+    engine::manager::<Rules<E>, 4, MAX_PLAYERS>(
+        listener,
+        limits,
+        name,
+        E::fingerprint(),
+        config,
+        bots,
+        admin,
+    )
+    .await;
+}
+
+/// Runs a client for any [`Edition`] on an already connected stream
+///
+/// The player is rated under the `nickname` if the server keeps profiles.
+pub async fn play<E: EditionUi>(stream: TcpStream, nickname: Option<String>) {
+    let (writer, reader) = broadcast::channel::<Message<E>>(32);
+    let (feedback_writer, feedback_reader) = broadcast::channel::<Message<E>>(32);
+
+    let join_handle = {
+        let main_page = MainPage::new();
+        let map_page = DefaultTuiMap::new();
+        let leaderboard = LeaderboardPage::new(nickname.clone());
+        let ui = Arc::new(TuiDefaults::<E>::init(main_page, map_page, leaderboard));
+        TuiDefaults::<E>::subscribe(ui.clone(), reader, feedback_writer);
+        let ui_ref_clone = ui.clone();
+        tokio::spawn(async move {
+            TuiDefaults::<E>::start(ui_ref_clone).await;
+        })
+    };
+
+    let (read_part, write_part) = stream.into_split();
+    let (broadcast_writer, broadcast_receiver) = broadcast::channel(32);
+    let _handle = tokio::spawn(async move { read_event::<E>(read_part, broadcast_writer).await });
+    let handle = tokio::spawn(async move {
+        manage_event(writer, feedback_reader, broadcast_receiver, write_part, nickname).await
+    });
+    info!("Started player");
+    join_handle.await.unwrap();
+    handle.await.unwrap();
+}
+
+/// Runs a client for any [`Edition`] that plays by itself with the given [`Strategy`]
+///
+/// No ui is started, the moves are picked by an [`Autopilot`].
+pub async fn play_headless<E: Edition>(
+    stream: TcpStream,
+    strategy: Box<dyn Strategy<E>>,
+    nickname: Option<String>,
+) {
+    let (read_part, write_part) = stream.into_split();
+    let (broadcast_writer, broadcast_receiver) = broadcast::channel(32);
+    let _handle = tokio::spawn(async move { read_event::<E>(read_part, broadcast_writer).await });
+    info!("Started headless player");
+    manage_event_headless(
+        Autopilot::new(0, strategy),
+        broadcast_receiver,
+        write_part,
+        nickname,
+    )
+    .await;
+}
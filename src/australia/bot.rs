@@ -0,0 +1,412 @@
+//! Defines bot players that run inside the server.
+//!
+//! A [`Bot`] implements [`server::engine::player::Player`] so the lobby talks to it like any
+//! other player, instead of writing the events to a socket the bot decides on a response and
+//! sends it back through its route. The moves are chosen by a [`Strategy`].
+
+pub mod external;
+pub mod lookahead;
+
+use std::{cell::RefCell, fmt::Debug, time::Duration};
+
+use async_trait::async_trait;
+use clap::ValueEnum;
+use rand::{seq::SliceRandom, Rng};
+use server::engine::{
+    player::{Player as PlayerTrait, PlayerError},
+    session::{LocalPlayer, Routes},
+};
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use super::{
+    protocol::Event,
+    rules::{
+        config::GameConfig,
+        edition::{Edition, RoundScore},
+        Player,
+    },
+};
+
+use external::{ExternalCommand, ExternalStrategy};
+use lookahead::LookAheadStrategy;
+
+/// Time that a bot waits before it answers, gives the lobby time to register the request
+const THINKING_TIME: Duration = Duration::from_millis(250);
+
+/// How well a bot plays
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum Difficulty {
+    /// Picks a random card and activity
+    Random,
+    /// Picks the card and activity that scores the most right now
+    #[default]
+    Greedy,
+    /// Plays out the rest of the round for every card and picks the best on average
+    LookAhead,
+}
+
+impl Difficulty {
+    /// The strategies play with the default config until the server sends the
+    /// [`Config`](Event::Config) of the game
+    pub fn strategy<E: Edition>(&self) -> Box<dyn Strategy<E>> {
+        match self {
+            Self::Random => Box::new(RandomStrategy),
+            Self::Greedy => Box::new(GreedyStrategy::default()),
+            Self::LookAhead => Box::new(LookAheadStrategy::default()),
+        }
+    }
+}
+
+/// Selects the strategy that bots play with, collected from the command line
+#[derive(Debug, Clone, Default)]
+pub struct BotOptions {
+    pub difficulty: Difficulty,
+    /// An external program that picks the moves instead, see [`external`]
+    pub command: Option<ExternalCommand>,
+}
+
+impl BotOptions {
+    pub fn strategy<E: Edition>(&self) -> Box<dyn Strategy<E>> {
+        match &self.command {
+            Some(command) => Box::new(ExternalStrategy::new(command.clone())),
+            None => self.difficulty.strategy(),
+        }
+    }
+}
+
+/// Decides the moves of a [`Bot`]
+///
+/// The moves are awaited, so a strategy can wait on I/O, such as an [`external`] program. A
+/// [`Bot`] asks for its moves from a task of its own, the lobby does not wait on them.
+#[async_trait]
+pub trait Strategy<E: Edition>: Debug + Send {
+    /// Called with every event that the bot receives, before it responds
+    async fn observe(&mut self, _event: &Event<E>) {}
+    /// Returns the index of the card in the players hand to throw
+    async fn discard(&mut self, player: &Player<E>) -> usize;
+    /// Returns the index of the card in the players hand to show
+    async fn show(&mut self, player: &Player<E>) -> usize;
+    /// Returns the activity to score, if any
+    async fn activity(
+        &mut self,
+        player: &Player<E>,
+        activities: &[E::Activity],
+    ) -> Option<E::Activity>;
+}
+
+#[derive(Debug)]
+pub struct RandomStrategy;
+
+#[async_trait]
+impl<E: Edition> Strategy<E> for RandomStrategy {
+    async fn discard(&mut self, player: &Player<E>) -> usize {
+        rand::thread_rng().gen_range(0..player.hand_size().max(1))
+    }
+    async fn show(&mut self, player: &Player<E>) -> usize {
+        rand::thread_rng().gen_range(0..player.hand_size().max(1))
+    }
+    async fn activity(
+        &mut self,
+        _player: &Player<E>,
+        activities: &[E::Activity],
+    ) -> Option<E::Activity> {
+        activities.choose(&mut rand::thread_rng()).copied()
+    }
+}
+
+/// Returns the total score of the round as if it ended now, the player is not changed
+fn score<E: Edition>(
+    config: &GameConfig,
+    player: &Player<E>,
+    regions: &[E::Region],
+    activity: Option<E::Activity>,
+) -> usize {
+    E::score(config, &mut player.clone(), regions, activity).total_score()
+}
+
+/// Values every move by scoring the cards that the player would keep
+///
+/// The moves are scored with the default config until the server sends the
+/// [`Config`](Event::Config) of the game.
+#[derive(Debug, Default)]
+pub struct GreedyStrategy {
+    config: GameConfig,
+}
+
+impl GreedyStrategy {
+    pub fn new(config: GameConfig) -> Self {
+        Self { config }
+    }
+
+    /// Returns the score of the player if the round ended now, with the best activity
+    pub fn value<E: Edition>(&self, player: &Player<E>) -> usize {
+        let regions = E::regions();
+        let mut activities = vec![None];
+        activities.extend(player.un_scored_activity().into_iter().map(Some));
+        activities
+            .into_iter()
+            .map(|activity| score::<E>(&self.config, player, &regions, activity))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the index of the card in hand that is worth the most to keep
+    fn best_card<E: Edition>(&self, player: &Player<E>) -> usize {
+        let mut best = (0, 0);
+        for (idx, card) in player.get_hand().into_iter().enumerate() {
+            let value = self.value(&player.keeping(card));
+            if value > best.1 {
+                best = (idx, value);
+            }
+        }
+        best.0
+    }
+
+    /// Returns the activity that scores the most this round, if any scores more than none
+    fn best_activity<E: Edition>(
+        &self,
+        player: &Player<E>,
+        activities: &[E::Activity],
+    ) -> Option<E::Activity> {
+        let regions = E::regions();
+        let score = |activity| score::<E>(&self.config, player, &regions, activity);
+        let mut best = (None, score(None));
+        for activity in activities {
+            let value = score(Some(*activity));
+            if value > best.1 {
+                best = (Some(*activity), value);
+            }
+        }
+        best.0
+    }
+}
+
+#[async_trait]
+impl<E: Edition> Strategy<E> for GreedyStrategy {
+    async fn observe(&mut self, event: &Event<E>) {
+        if let Event::Config(config) = event {
+            self.config = config.clone();
+        }
+    }
+    async fn discard(&mut self, player: &Player<E>) -> usize {
+        self.best_card(player)
+    }
+    async fn show(&mut self, player: &Player<E>) -> usize {
+        self.best_card(player)
+    }
+    async fn activity(
+        &mut self,
+        player: &Player<E>,
+        activities: &[E::Activity],
+    ) -> Option<E::Activity> {
+        self.best_activity(player, activities)
+    }
+}
+
+/// Answers the events of the server with the moves of a [`Strategy`]
+///
+/// Shared by the [`Bot`]s that run inside the server and the headless client.
+#[derive(Debug)]
+pub struct Autopilot<E: Edition> {
+    /// The players view of the game, kept up to date by the servers syncs
+    player: Player<E>,
+    strategy: Box<dyn Strategy<E>>,
+}
+
+impl<E: Edition> Autopilot<E> {
+    pub fn new(uid: u8, strategy: Box<dyn Strategy<E>>) -> Self {
+        Self {
+            player: Player::new(uid),
+            strategy,
+        }
+    }
+
+    /// Returns the response to the event, if it requires one
+    pub async fn respond(&mut self, event: Event<E>) -> Option<Event<E>> {
+        self.strategy.observe(&event).await;
+        match event {
+            Event::ReadyCheck | Event::Deal(_) => Some(Event::Accept),
+            Event::Sync(player) => {
+                self.player = player;
+                Some(Event::Accept)
+            }
+            Event::ReassignHand(hand) => {
+                self.player = self.player.clone().set_cards(hand);
+                Some(Event::Accept)
+            }
+            Event::DiscardRequest => Some(Event::Discard(
+                self.strategy.discard(&self.player).await,
+            )),
+            Event::ShowRequest => Some(Event::Show(self.strategy.show(&self.player).await)),
+            Event::ScoreActivityQuery(activities) => Some(Event::ScoreActivity(
+                self.strategy.activity(&self.player, &activities).await,
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// A player that plays by itself
+///
+/// The events are answered by a task that owns the [`Autopilot`], in the order that they
+/// were sent, so a slow strategy holds up the bot but not the lobby.
+#[derive(Debug)]
+pub struct Bot<E: Edition> {
+    uid: usize,
+    events: mpsc::UnboundedSender<Event<E>>,
+}
+
+impl<E: Edition> Bot<E> {
+    pub fn new(uid: usize, routes: Routes<Event<E>>, strategy: Box<dyn Strategy<E>>) -> Self {
+        let (events, received) = mpsc::unbounded_channel();
+        let autopilot = Autopilot::new(uid as u8, strategy);
+        tokio::spawn(Self::play(uid, autopilot, routes, received));
+        Self { uid, events }
+    }
+
+    /// Answers the events until the bot is dropped
+    async fn play(
+        uid: usize,
+        mut autopilot: Autopilot<E>,
+        routes: Routes<Event<E>>,
+        mut events: mpsc::UnboundedReceiver<Event<E>>,
+    ) {
+        while let Some(event) = events.recv().await {
+            let Some(response) = autopilot.respond(event).await else {
+                continue;
+            };
+            debug!(uid, ?response, "Bot responding");
+            tokio::time::sleep(THINKING_TIME).await;
+            let route = routes.lock().await.get(&uid).cloned();
+            match route {
+                Some(tx) => {
+                    let _ = tx.send((uid, response)).await;
+                }
+                None => warn!(uid, "Bot is not seated, dropping its response"),
+            }
+        }
+    }
+
+    /// Returns a [`LocalPlayer`] that creates a bot with the given options
+    pub fn local(options: BotOptions) -> LocalPlayer<Event<E>> {
+        Box::new(move |uid, routes| {
+            Box::new(RefCell::new(Self::new(uid, routes, options.strategy())))
+        })
+    }
+}
+
+#[async_trait]
+impl<E: Edition> PlayerTrait<Event<E>> for Bot<E> {
+    fn get_id(&self) -> usize {
+        self.uid
+    }
+    async fn send(&mut self, event: Event<E>) -> Result<(), PlayerError> {
+        self.events
+            .send(event)
+            .map_err(|_| PlayerError::Disconnected)
+    }
+    fn identifier(&self) -> String {
+        format!("Bot {}", self.uid)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, sync::Arc};
+
+    use server::engine::player::Player;
+    use tokio::sync::{mpsc, Mutex};
+
+    use crate::australia::{
+        protocol,
+        rules::{
+            cards::{AustraliaCard, AustralianActivity},
+            config::GameConfig,
+            AustraliaEdition, AustraliaPlayer,
+        },
+    };
+
+    use super::{Autopilot, Bot, Difficulty, GreedyStrategy, Strategy};
+
+    type Event = protocol::Event<AustraliaEdition>;
+
+    fn bot(difficulty: Difficulty) -> Autopilot<AustraliaEdition> {
+        Autopilot::new(0, difficulty.strategy())
+    }
+
+    #[tokio::test]
+    async fn test_respond() {
+        let hand = vec![AustraliaCard::Uluru, AustraliaCard::BondiBeach];
+        for difficulty in [Difficulty::Random, Difficulty::Greedy, Difficulty::LookAhead] {
+            let mut bot = bot(difficulty);
+            assert_eq!(bot.respond(Event::ReadyCheck).await, Some(Event::Accept));
+            assert_eq!(bot.respond(Event::ReassignHand(hand.clone())).await, Some(Event::Accept));
+            match bot.respond(Event::DiscardRequest).await {
+                Some(Event::Discard(idx)) => assert!(idx < hand.len()),
+                other => panic!("Expected a discard, got {:?}", other),
+            }
+            match bot.respond(Event::ShowRequest).await {
+                Some(Event::Show(idx)) => assert!(idx < hand.len()),
+                other => panic!("Expected a show, got {:?}", other),
+            }
+            assert_eq!(bot.respond(Event::WaitingForPlayers).await, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_greedy() {
+        // Completing a pair of emus is worth more than a lone kangaroo
+        let mut player = AustraliaPlayer::new(0).set_cards(vec![
+            AustraliaCard::Uluru,
+            AustraliaCard::Richmond,
+            AustraliaCard::LakeEyre,
+        ]);
+        player.discard(&0).unwrap();
+        assert_eq!(GreedyStrategy::default().show(&player).await, 1);
+
+        // Two swimming cards are worth scoring, the other activities are not
+        let activities = vec![AustralianActivity::Bushwalking, AustralianActivity::Swimming];
+        assert_eq!(
+            GreedyStrategy::default().activity(&player, &activities).await,
+            Some(AustralianActivity::Swimming)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_config() {
+        // Once activities are worth nothing the bot does not score one
+        let mut bot = bot(Difficulty::Greedy);
+        let hand = vec![
+            AustraliaCard::Uluru,
+            AustraliaCard::Richmond,
+            AustraliaCard::LakeEyre,
+        ];
+        assert_eq!(bot.respond(Event::ReassignHand(hand)).await, Some(Event::Accept));
+        let query = Event::ScoreActivityQuery(vec![AustralianActivity::Swimming]);
+        assert_eq!(
+            bot.respond(query.clone()).await,
+            Some(Event::ScoreActivity(Some(AustralianActivity::Swimming)))
+        );
+        let config = GameConfig {
+            activity_points: vec![0],
+            ..Default::default()
+        };
+        assert_eq!(bot.respond(Event::Config(config)).await, None);
+        assert_eq!(bot.respond(query).await, Some(Event::ScoreActivity(None)));
+    }
+
+    #[tokio::test]
+    async fn test_send() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let routes = Arc::new(Mutex::new(HashMap::from([(0, tx)])));
+        let mut bot = Bot::<AustraliaEdition>::new(0, routes, Difficulty::Greedy.strategy());
+        // The events are queued, the answers arrive in order once the bot has thought
+        let hand = vec![AustraliaCard::Uluru, AustraliaCard::BondiBeach];
+        bot.send(Event::ReassignHand(hand)).await.unwrap();
+        bot.send(Event::WaitingForPlayers).await.unwrap();
+        bot.send(Event::DiscardRequest).await.unwrap();
+        assert_eq!(rx.recv().await, Some((0, Event::Accept)));
+        assert!(matches!(rx.recv().await, Some((0, Event::Discard(idx))) if idx < 2));
+    }
+}
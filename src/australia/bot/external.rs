@@ -0,0 +1,356 @@
+//! Defines a strategy that lets an external program pick the moves.
+//!
+//! The program is started once per bot and talks one JSON [`Event`] per line over its
+//! stdin and stdout, in the same format as the server sends them over tcp. The bot
+//! forwards `Config`, `Seats`, `Deal`, `ReassignHand`, `Sync`, `ShowPile`, `NewRound`,
+//! `FinalResult` and `MatchStandings` as they arrive, none of these are answered. `DiscardRequest`, `ShowRequest` and
+//! `ScoreActivityQuery` must be answered with a single line holding a `Discard`,
+//! `Show` or `ScoreActivity` event, for example:
+//!
+//! ```text
+//! > {"Deal":"Uluru"}
+//! > "DiscardRequest"
+//! < {"Discard":0}
+//! > {"ScoreActivityQuery":["Swimming","Bushwalking"]}
+//! < {"ScoreActivity":"Swimming"}
+//! ```
+//!
+//! Every answer has to arrive within the move time, and the program has to read every
+//! event within the move time as well. A late or invalid answer is replaced by a random
+//! move, and if the program exits or stops reading every remaining move is random.
+//!
+//! The [`Bot`](super::Bot) waits on the program in a task of its own, while it waits the lobby
+//! carries on and the other players are served.
+
+use std::{marker::PhantomData, path::PathBuf, process::Stdio, time::Duration};
+
+use async_trait::async_trait;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    process::{Child, ChildStdin, Command},
+    sync::mpsc,
+    time::timeout,
+};
+use tracing::{debug, warn};
+
+use crate::australia::{
+    protocol::Event,
+    rules::{edition::Edition, Player},
+};
+
+use super::{RandomStrategy, Strategy};
+
+/// Time that an external program has to answer a request, unless configured
+pub const DEFAULT_MOVE_TIME: Duration = Duration::from_secs(1);
+/// Number of lines that are read ahead of the bot
+const LINE_BUFFER: usize = 16;
+
+/// How to start an external bot
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalCommand {
+    pub program: PathBuf,
+    pub args: Vec<String>,
+    /// Time that the program has to answer a request
+    pub move_time: Duration,
+}
+
+impl ExternalCommand {
+    /// Parses a command line such as `python3 bot.py --depth 2`, returns [`None`] if it is empty
+    pub fn parse(command: &str, move_time: Duration) -> Option<Self> {
+        let mut words = command.split_whitespace().map(str::to_owned);
+        Some(Self {
+            program: words.next()?.into(),
+            args: words.collect(),
+            move_time,
+        })
+    }
+}
+
+/// A running external program, it is killed when this is dropped
+#[derive(Debug)]
+struct Process {
+    _child: Child,
+    stdin: ChildStdin,
+    /// Lines read from the programs stdout, the reader task stops when the program exits
+    lines: mpsc::Receiver<String>,
+}
+
+impl Process {
+    /// Starts the program, must be called from within a tokio runtime
+    fn spawn(command: &ExternalCommand) -> std::io::Result<Self> {
+        let mut child = Command::new(&command.program)
+            .args(&command.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()?;
+        // Both are piped above
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (tx, lines) = mpsc::channel(LINE_BUFFER);
+        tokio::spawn(async move {
+            let mut reader = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = reader.next_line().await {
+                if tx.send(line).await.is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self {
+            _child: child,
+            stdin,
+            lines,
+        })
+    }
+
+    async fn send<E: Edition>(&mut self, event: &Event<E>) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(event)?;
+        line.push('\n');
+        self.stdin.write_all(line.as_bytes()).await?;
+        self.stdin.flush().await
+    }
+}
+
+#[derive(Debug)]
+pub struct ExternalStrategy<E: Edition> {
+    command: ExternalCommand,
+    /// The running program, [`None`] once it has failed
+    process: Option<Process>,
+    /// Plays the moves that the program does not
+    fallback: RandomStrategy,
+    edition: PhantomData<E>,
+}
+
+impl<E: Edition> ExternalStrategy<E> {
+    /// Starts the program, every move is random if it can not be started
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn new(command: ExternalCommand) -> Self {
+        let process = match Process::spawn(&command) {
+            Ok(process) => Some(process),
+            Err(e) => {
+                warn!(error = %e, program = ?command.program, "Could not start the external bot");
+                None
+            }
+        };
+        Self {
+            command,
+            process,
+            fallback: RandomStrategy,
+            edition: PhantomData,
+        }
+    }
+
+    /// Writes the event to the program, stops using the program if it does not read it in time
+    async fn forward(&mut self, event: &Event<E>) {
+        let process = match &mut self.process {
+            Some(process) => process,
+            None => return,
+        };
+        let error = match timeout(self.command.move_time, process.send(event)).await {
+            Ok(Ok(())) => return,
+            Ok(Err(e)) => e.to_string(),
+            Err(_) => "the event was not read in time".to_owned(),
+        };
+        warn!(error, program = ?self.command.program, "External bot failed");
+        self.process = None;
+    }
+
+    /// Sends the request and returns the answer, if a valid one arrives in time
+    async fn request<T>(
+        &mut self,
+        event: Event<E>,
+        valid: impl Fn(Event<E>) -> Option<T> + Send,
+    ) -> Option<T> {
+        // Answers that arrived after an earlier move timed out belong to that move
+        while let Some(Ok(stale)) = self
+            .process
+            .as_mut()
+            .map(|process| process.lines.try_recv())
+        {
+            debug!(stale, "Dropping a late answer from the external bot");
+        }
+        self.forward(&event).await;
+        let process = self.process.as_mut()?;
+        let line = match timeout(self.command.move_time, process.lines.recv()).await {
+            Ok(Some(line)) => line,
+            Ok(None) => {
+                warn!(program = ?self.command.program, "External bot exited");
+                self.process = None;
+                return None;
+            }
+            Err(_) => {
+                warn!(?event, "External bot did not answer in time");
+                return None;
+            }
+        };
+        let answer = serde_json::from_str::<Event<E>>(&line).ok().and_then(valid);
+        if answer.is_none() {
+            warn!(line, ?event, "External bot gave an invalid answer");
+        }
+        answer
+    }
+}
+
+#[async_trait]
+impl<E: Edition> Strategy<E> for ExternalStrategy<E> {
+    async fn observe(&mut self, event: &Event<E>) {
+        match event {
+            Event::Config(_)
+            | Event::Seats(_)
+            | Event::Deal(_)
+            | Event::ReassignHand(_)
+            | Event::Sync(_)
+            | Event::ShowPile(..)
+            | Event::NewRound
+            | Event::FinalResult(..)
+            | Event::MatchStandings(_) => self.forward(event).await,
+            _ => {}
+        }
+    }
+    async fn discard(&mut self, player: &Player<E>) -> usize {
+        let size = player.hand_size();
+        let request = Event::<E>::DiscardRequest;
+        match self
+            .request(request, |answer| match answer {
+                Event::Discard(idx) if idx < size => Some(idx),
+                _ => None,
+            })
+            .await
+        {
+            Some(idx) => idx,
+            None => Strategy::<E>::discard(&mut self.fallback, player).await,
+        }
+    }
+    async fn show(&mut self, player: &Player<E>) -> usize {
+        let size = player.hand_size();
+        let request = Event::<E>::ShowRequest;
+        match self
+            .request(request, |answer| match answer {
+                Event::Show(idx) if idx < size => Some(idx),
+                _ => None,
+            })
+            .await
+        {
+            Some(idx) => idx,
+            None => Strategy::<E>::show(&mut self.fallback, player).await,
+        }
+    }
+    async fn activity(
+        &mut self,
+        player: &Player<E>,
+        activities: &[E::Activity],
+    ) -> Option<E::Activity> {
+        let query = Event::<E>::ScoreActivityQuery(activities.to_vec());
+        match self
+            .request(query, |answer| match answer {
+                Event::ScoreActivity(None) => Some(None),
+                Event::ScoreActivity(Some(activity)) if activities.contains(&activity) => {
+                    Some(Some(activity))
+                }
+                _ => None,
+            })
+            .await
+        {
+            Some(activity) => activity,
+            None => {
+                Strategy::<E>::activity(&mut self.fallback, player, activities).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::australia::{
+        bot::Strategy,
+        protocol::Event,
+        rules::{
+            cards::{AustraliaCard, AustralianActivity},
+            AustraliaEdition, AustraliaPlayer,
+        },
+    };
+
+    use super::{ExternalCommand, ExternalStrategy};
+
+    /// A bot that always throws the second card, shows the first and scores swimming
+    const SCRIPT: &str = r#"while read line; do case "$line" in
+        *DiscardRequest*) echo '{"Discard":1}' ;;
+        *ShowRequest*) echo '{"Show":7}' ;;
+        *ScoreActivityQuery*) echo '{"ScoreActivity":"Swimming"}' ;;
+    esac; done"#;
+
+    fn strategy(script: &str, move_time: Duration) -> ExternalStrategy<AustraliaEdition> {
+        ExternalStrategy::new(ExternalCommand {
+            program: "sh".into(),
+            args: vec!["-c".to_owned(), script.to_owned()],
+            move_time,
+        })
+    }
+
+    fn player() -> AustraliaPlayer {
+        AustraliaPlayer::new(0).set_cards(vec![
+            AustraliaCard::Uluru,
+            AustraliaCard::Richmond,
+            AustraliaCard::LakeEyre,
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_answers() {
+        let mut strategy = strategy(SCRIPT, Duration::from_secs(5));
+        let player = player();
+        assert_eq!(strategy.discard(&player).await, 1);
+        // Out of range, a random card is picked
+        assert!(strategy.show(&player).await < player.hand_size());
+        assert!(strategy.process.is_some());
+        let activities = vec![AustralianActivity::Bushwalking, AustralianActivity::Swimming];
+        assert_eq!(
+            strategy.activity(&player, &activities).await,
+            Some(AustralianActivity::Swimming)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fallback() {
+        let player = player();
+
+        let mut silent = strategy("sleep 5", Duration::from_millis(50));
+        assert!(silent.discard(&player).await < player.hand_size());
+
+        let mut crashed = strategy("exit 1", Duration::from_secs(5));
+        assert!(crashed.discard(&player).await < player.hand_size());
+        assert!(crashed.process.is_none());
+
+        let mut missing = ExternalStrategy::<AustraliaEdition>::new(
+            ExternalCommand::parse("./no-such-bot --fast", Duration::from_secs(1)).unwrap(),
+        );
+        assert!(missing.process.is_none());
+        assert!(missing.discard(&player).await < player.hand_size());
+    }
+
+    /// A program that stops reading is dropped once the pipe is full instead of blocking
+    #[tokio::test]
+    async fn test_write_timeout() {
+        let mut stuck = strategy("sleep 5", Duration::from_millis(50));
+        let sync = Event::Sync(player());
+        for _ in 0..10_000 {
+            if stuck.process.is_none() {
+                break;
+            }
+            stuck.observe(&sync).await;
+        }
+        assert!(stuck.process.is_none());
+    }
+
+    #[test]
+    fn test_parse() {
+        let command = ExternalCommand::parse("python3 bot.py  --depth 2", Duration::ZERO).unwrap();
+        assert_eq!(command.program.to_str(), Some("python3"));
+        assert_eq!(command.args, vec!["bot.py", "--depth", "2"]);
+        assert!(ExternalCommand::parse("  ", Duration::ZERO).is_none());
+    }
+}
@@ -0,0 +1,484 @@
+//! Defines a strategy that looks ahead by playing out the rest of the round.
+//!
+//! The other players hands are hidden, so they are sampled from what the bot has seen.
+//! Every card that the bot passed on and that has not been shown since is still in the hand
+//! that it was passed in, and the pass sequence tells which seat holds that hand now. The
+//! rest of the hidden cards are drawn from the cards that the bot has never seen. The hands
+//! are seated like the server seated the players, and a two player round passes a dummy hand
+//! along with theirs. Every possible move is then played out on a [`GameMetaData`] a number
+//! of times, the other players play at random and the bot plays greedily, and the move with
+//! the best average round score is picked.
+//!
+//! The play outs take a while, they run on the blocking pool of the runtime.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use rand::{seq::SliceRandom, Rng};
+use tracing::warn;
+
+use crate::australia::{
+    protocol::Event,
+    rules::{
+        config::GameConfig,
+        edition::Edition,
+        meta::{GameMetaData, TWO_PLAYERS},
+        states::pass::Direction,
+        Player,
+    },
+};
+
+use super::{GreedyStrategy, Strategy};
+
+/// Number of times every move is played out
+const ROLLOUTS: usize = 24;
+
+/// A move that the bot can make
+#[derive(Debug, Clone, Copy)]
+enum Move {
+    Discard(usize),
+    Show(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct LookAheadStrategy<E: Edition> {
+    /// Cards that have passed through the players hand this round
+    seen: Vec<E::Card>,
+    /// The cards that the player keeps in its hand after its last move, they are passed next
+    passing: Vec<E::Card>,
+    /// The hands that the player passed this round and how many seats forward they have
+    /// moved since, a hand that came back to the player is dropped
+    passed: Vec<(Vec<E::Card>, usize)>,
+    /// Number of passes this round
+    passes: usize,
+    /// The show piles of the other players this round
+    shown: HashMap<u8, Vec<E::Card>>,
+    /// Number of cards that were dealt this round
+    dealt: usize,
+    /// The players in the order that the hands are passed in, sent by the server
+    seats: Vec<u8>,
+    /// The config of the game, the default one until the server has sent it
+    config: GameConfig,
+    rollouts: usize,
+}
+
+impl<E: Edition> Default for LookAheadStrategy<E> {
+    fn default() -> Self {
+        Self::new(GameConfig::default())
+    }
+}
+
+impl<E: Edition> LookAheadStrategy<E> {
+    pub fn new(config: GameConfig) -> Self {
+        Self {
+            seen: Vec::new(),
+            passing: Vec::new(),
+            passed: Vec::new(),
+            passes: 0,
+            shown: HashMap::new(),
+            dealt: 0,
+            seats: Vec::new(),
+            config,
+            rollouts: ROLLOUTS,
+        }
+    }
+
+    fn see(&mut self, cards: Vec<E::Card>) {
+        for card in cards {
+            if !self.seen.contains(&card) {
+                self.seen.push(card);
+            }
+        }
+    }
+
+    /// Returns the number of hands that are passed around, a two player round has a dummy
+    fn table(&self) -> usize {
+        match self.seats.len() {
+            TWO_PLAYERS => TWO_PLAYERS + 1,
+            seats => seats,
+        }
+    }
+
+    /// Moves the hands that the player passed along with the pass that was just made
+    fn pass(&mut self) {
+        let table = self.table();
+        let step = match self.config.pass_sequence.get(self.passes) {
+            Some(Direction::Backward) => table.saturating_sub(1),
+            _ => 1,
+        };
+        self.passes += 1;
+        self.passed.push((std::mem::take(&mut self.passing), 0));
+        for (_, offset) in &mut self.passed {
+            *offset = (*offset + step) % table.max(1);
+        }
+        self.passed
+            .retain(|(cards, offset)| *offset != 0 && !cards.is_empty());
+    }
+
+    /// Returns the cards that are known to be in the hand at each position of the table
+    fn known_hands(&self, player: &Player<E>) -> HashMap<usize, Vec<E::Card>> {
+        let mut known = HashMap::new();
+        let Some(me) = self.seats.iter().position(|uid| *uid == player.id()) else {
+            return known;
+        };
+        let shown: Vec<E::Card> = self.shown.values().flatten().copied().collect();
+        for (cards, offset) in &self.passed {
+            let cards = cards.iter().filter(|card| !shown.contains(card)).copied();
+            known
+                .entry((me + offset) % self.table())
+                .or_insert_with(Vec::new)
+                .extend(cards);
+        }
+        known
+    }
+
+    /// Returns a random round that is consistent with what the player has seen
+    fn sample(&self, player: &Player<E>) -> GameMetaData<E> {
+        let mut rng = rand::thread_rng();
+        let mut mine = player.get_hand();
+        mine.extend(player.get_discard());
+        mine.extend(player.get_show());
+        let shown: Vec<E::Card> = self.shown.values().flatten().copied().collect();
+
+        let mut known = self.known_hands(player);
+        let placed: Vec<E::Card> = known.values().flatten().copied().collect();
+        let mut in_hands: Vec<E::Card> = self
+            .seen
+            .iter()
+            .filter(|card| !mine.contains(card) && !shown.contains(card))
+            .filter(|card| !placed.contains(card))
+            .copied()
+            .collect();
+        let mut unknown: Vec<E::Card> = E::deck()
+            .cards()
+            .into_iter()
+            .filter(|card| {
+                !mine.contains(card) && !shown.contains(card) && !self.seen.contains(card)
+            })
+            .collect();
+        in_hands.shuffle(&mut rng);
+        unknown.shuffle(&mut rng);
+
+        let mut seats = self.seats.clone();
+        if !seats.contains(&player.id()) {
+            // The server has not sent the seats, only the players that have shown cards are known
+            seats = std::iter::once(player.id())
+                .chain(self.shown.keys().copied())
+                .collect();
+            seats.sort();
+        }
+
+        let size = player.hand_size();
+        let mut players = Vec::new();
+        for (position, uid) in seats.iter().enumerate() {
+            if *uid == player.id() {
+                players.push(player.clone());
+                continue;
+            }
+            let known = known.remove(&position).unwrap_or_default();
+            let hand = Self::deal(known, size, &mut in_hands, &mut unknown);
+            let discard = match player.get_discard().is_empty() {
+                true => Vec::new(),
+                false => unknown.pop().into_iter().collect(),
+            };
+            let show = self.shown.get(uid).cloned().unwrap_or_default();
+            players.push(Player::new(*uid).set_cards(hand).set_piles(discard, show));
+        }
+        // The dummy of a two player round holds as many cards as the players
+        let dummy: Vec<E::Card> = match seats.len() {
+            TWO_PLAYERS => {
+                let known = known.remove(&TWO_PLAYERS).unwrap_or_default();
+                Self::deal(known, size, &mut in_hands, &mut unknown)
+            }
+            _ => Vec::new(),
+        };
+        GameMetaData::from_round(players, self.config.clone()).with_dummy_hand(dummy)
+    }
+
+    /// Fills up a hand of `size` cards, the cards that are known to be in it come first
+    fn deal(
+        mut hand: Vec<E::Card>,
+        size: usize,
+        in_hands: &mut Vec<E::Card>,
+        unknown: &mut Vec<E::Card>,
+    ) -> Vec<E::Card> {
+        hand.truncate(size);
+        while hand.len() < size {
+            match in_hands.pop().or_else(|| unknown.pop()) {
+                Some(card) => hand.push(card),
+                None => break,
+            }
+        }
+        hand
+    }
+
+    /// Plays out the rest of the round after the player has made the move
+    ///
+    /// Returns the players score for the round.
+    fn rollout(&self, mut round: GameMetaData<E>, uid: u8, first: Move) -> usize {
+        let mut rng = rand::thread_rng();
+        let greedy = GreedyStrategy::new(self.config.clone());
+        let mut next = Some(first);
+        let hand_size = match self.dealt {
+            0 => self.config.hand_size,
+            dealt => dealt,
+        };
+        let me = |round: &mut GameMetaData<E>| -> Player<E> {
+            round
+                .get_players()
+                .iter()
+                .find(|player| player.id() == uid)
+                .cloned()
+                // The player is always part of the sampled round
+                .unwrap()
+        };
+        let mut pass = hand_size.saturating_sub(me(&mut round).hand_size());
+        // Each turn removes a card from the hand, this bounds the number of turns
+        for _ in 0..hand_size {
+            let players: Vec<(u8, usize)> = round
+                .get_players()
+                .iter()
+                .map(|player| (player.id(), player.hand_size()))
+                .collect();
+            let action = next
+                .take()
+                .unwrap_or_else(|| Move::Show(greedy.best_card(&me(&mut round))));
+            for (id, size) in players {
+                if size == 0 {
+                    continue;
+                }
+                let action = match id == uid {
+                    true => action,
+                    false => match action {
+                        Move::Discard(_) => Move::Discard(rng.gen_range(0..size)),
+                        Move::Show(_) => Move::Show(rng.gen_range(0..size)),
+                    },
+                };
+                let _ = match action {
+                    Move::Discard(idx) => round.discard(&(id as usize), &idx),
+                    Move::Show(idx) => round.show(&(id as usize), &idx),
+                };
+            }
+            let direction = self
+                .config
+                .pass_sequence
+                .get(pass)
+                .copied()
+                .unwrap_or(Direction::Forward);
+            pass += 1;
+            round.circulate(direction);
+            if me(&mut round).hand_size() <= 1 {
+                break;
+            }
+        }
+        greedy.value(&me(&mut round))
+    }
+
+    /// Picks the move on the blocking pool and remembers the hand that is passed on
+    ///
+    /// Without a runtime, as in the tests, the move is picked in place.
+    async fn think(&mut self, player: &Player<E>, make: fn(usize) -> Move) -> usize {
+        let idx = match tokio::runtime::Handle::try_current() {
+            Ok(_) => {
+                let (strategy, view) = (self.clone(), player.clone());
+                let thought = tokio::task::spawn_blocking(move || strategy.best_move(&view, make));
+                match thought.await {
+                    Ok(idx) => idx,
+                    Err(e) => {
+                        warn!(error = %e, "Could not look ahead, playing greedily");
+                        GreedyStrategy::new(self.config.clone()).best_card(player)
+                    }
+                }
+            }
+            Err(_) => self.best_move(player, make),
+        };
+        self.passing = player.get_hand();
+        if idx < self.passing.len() {
+            self.passing.remove(idx);
+        }
+        idx
+    }
+
+    /// Returns the move with the best average score
+    fn best_move(&self, player: &Player<E>, make: fn(usize) -> Move) -> usize {
+        let mut best = (0, 0);
+        for idx in 0..player.hand_size() {
+            let total: usize = (0..self.rollouts)
+                .map(|_| self.rollout(self.sample(player), player.id(), make(idx)))
+                .sum();
+            if total > best.1 {
+                best = (idx, total);
+            }
+        }
+        best.0
+    }
+}
+
+#[async_trait]
+impl<E: Edition> Strategy<E> for LookAheadStrategy<E> {
+    async fn observe(&mut self, event: &Event<E>) {
+        match event {
+            Event::NewRound => {
+                self.seen.clear();
+                self.shown.clear();
+                self.passing.clear();
+                self.passed.clear();
+                self.passes = 0;
+                self.dealt = 0;
+            }
+            Event::Deal(card) => {
+                self.dealt += 1;
+                self.see(vec![*card]);
+            }
+            Event::ReassignHand(hand) => {
+                self.pass();
+                self.see(hand.clone());
+            }
+            Event::Sync(player) => self.see(player.get_hand()),
+            Event::ShowPile(uid, cards, _) => {
+                self.shown.insert(*uid, cards.clone());
+            }
+            Event::Config(config) => self.config = config.clone(),
+            Event::Seats(seats) => self.seats = seats.clone(),
+            _ => {}
+        }
+    }
+    async fn discard(&mut self, player: &Player<E>) -> usize {
+        self.think(player, Move::Discard).await
+    }
+    async fn show(&mut self, player: &Player<E>) -> usize {
+        self.think(player, Move::Show).await
+    }
+    /// The round is over when the activity is picked, there is nothing left to look ahead at
+    async fn activity(
+        &mut self,
+        player: &Player<E>,
+        activities: &[E::Activity],
+    ) -> Option<E::Activity> {
+        GreedyStrategy::new(self.config.clone()).best_activity(player, activities)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::australia::{
+        bot::{GreedyStrategy, Strategy},
+        protocol,
+        rules::{
+            cards::{AustraliaCard, AustraliaDeck},
+            config::GameConfig,
+            states::pass::Direction,
+            AustraliaEdition, AustraliaPlayer,
+        },
+    };
+
+    use super::LookAheadStrategy;
+
+    type Event = protocol::Event<AustraliaEdition>;
+
+    #[tokio::test]
+    async fn test_last_show() {
+        // With two cards left the rest of the round is known, it has to agree with greedy
+        let mut player = AustraliaPlayer::new(0).set_cards(vec![
+            AustraliaCard::Uluru,
+            AustraliaCard::Richmond,
+            AustraliaCard::LakeEyre,
+        ]);
+        player.discard(&0).unwrap();
+        let mut strategy = LookAheadStrategy::<AustraliaEdition>::new(GameConfig::default());
+        let shown = Event::ShowPile(1, vec![AustraliaCard::BondiBeach], vec!['S']);
+        strategy.observe(&shown).await;
+        assert_eq!(
+            strategy.show(&player).await,
+            GreedyStrategy::default().show(&player).await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sample() {
+        let mut strategy = LookAheadStrategy::<AustraliaEdition>::default();
+        strategy.observe(&Event::Seats(vec![2, 0, 1])).await;
+        let hand = AustraliaDeck::default().cards()[..7].to_vec();
+        let player = AustraliaPlayer::new(0).set_cards(hand);
+        let round = strategy.sample(&player);
+        let seats: Vec<(u8, usize)> = round
+            .seats()
+            .map(|seat| (seat.id(), seat.hand_size()))
+            .collect();
+        assert_eq!(seats, vec![(2, 7), (0, 7), (1, 7)]);
+        // No card is dealt twice
+        let cards: Vec<AustraliaCard> = round.cards().into_iter().map(|(_, card)| card).collect();
+        for card in &cards {
+            assert_eq!(cards.iter().filter(|el| *el == card).count(), 1);
+        }
+
+        // Two players pass a dummy hand around as well
+        strategy.observe(&Event::Seats(vec![0, 1])).await;
+        let round = strategy.sample(&player);
+        assert_eq!(round.seats().count(), 3);
+        assert!(round.seats().all(|seat| seat.hand_size() == 7));
+    }
+
+    #[tokio::test]
+    async fn test_passed_hand() {
+        let mut strategy = LookAheadStrategy::<AustraliaEdition> {
+            rollouts: 1,
+            ..Default::default()
+        };
+        strategy.observe(&Event::Seats(vec![0, 1, 2])).await;
+        let deck = AustraliaDeck::default().cards();
+        for card in &deck[..7] {
+            strategy.observe(&Event::Deal(*card)).await;
+        }
+        let player = AustraliaPlayer::new(0).set_cards(deck[..7].to_vec());
+        let idx = strategy.discard(&player).await;
+        let mut passed = deck[..7].to_vec();
+        passed.remove(idx);
+
+        // The first pass goes forward, player 1 holds the hand that the bot passed on
+        let mut player = player;
+        player.discard(&idx).unwrap();
+        strategy.observe(&Event::ReassignHand(deck[7..13].to_vec())).await;
+        let player = player.set_cards(deck[7..13].to_vec());
+        for _ in 0..10 {
+            let round = strategy.sample(&player);
+            let hands: Vec<(u8, Vec<AustraliaCard>)> = round
+                .seats()
+                .map(|seat| (seat.id(), seat.get_hand()))
+                .collect();
+            assert_eq!(hands[1], (1, passed.clone()));
+            assert!(hands[2].1.iter().all(|card| !passed.contains(card)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_discard() {
+        // The bot has visited the first three sites of Western Australia in earlier rounds
+        let mut player = AustraliaPlayer::new(0).set_cards(vec![
+            AustraliaCard::TheBungleBungles,
+            AustraliaCard::ThePinnacles,
+            AustraliaCard::MargaretRiver,
+        ]);
+        player.new_round();
+        // Keeping Kalbarri completes the region, the others are passed on for good
+        let hand = vec![
+            AustraliaCard::Uluru,
+            AustraliaCard::KalbarriNationalPark,
+            AustraliaCard::KakaduNationalPark,
+        ];
+        let player = player.set_cards(hand.clone());
+
+        let mut strategy = LookAheadStrategy::<AustraliaEdition>::default();
+        strategy.observe(&Event::Config(GameConfig {
+            hand_size: 3,
+            pass_sequence: vec![Direction::Forward; 2],
+            ..Default::default()
+        }))
+        .await;
+        strategy.observe(&Event::Seats(vec![0, 1])).await;
+        for card in &hand {
+            strategy.observe(&Event::Deal(*card)).await;
+        }
+        assert_eq!(strategy.discard(&player).await, 1);
+    }
+}
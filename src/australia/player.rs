@@ -1,63 +1,81 @@
 use async_recursion::async_recursion;
-use log::{error, info, warn};
+use tracing::{error, info, warn};
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
     net::tcp::{OwnedReadHalf, OwnedWriteHalf},
     sync::broadcast::{self, Receiver},
 };
 
-use super::protocol::{Event, Message};
+use super::{
+    bot::Autopilot,
+    protocol::{Event, Message},
+    rules::edition::Edition,
+};
 
 #[async_recursion]
 /// Manages incoming messages
 ///
 /// This function manages incoming messages and passes them to the event manager for further interpretation
-pub async fn read_event(mut read_part: OwnedReadHalf, channel: broadcast::Sender<Event>) {
+pub async fn read_event<E: Edition>(
+    mut read_part: OwnedReadHalf,
+    channel: broadcast::Sender<Event<E>>,
+) {
+    // A read may hold several frames, or only a part of one
+    let mut pending = Vec::new();
     loop {
         info!("Waiting for events");
         let mut buff = vec![0; 2048];
-        let recv = read_part.read(&mut buff).await;
-        match recv {
-            Ok(_) => {}
-            Err(_) => {
-                continue;
+        let read = match read_part.read(&mut buff).await {
+            Ok(0) => {
+                warn!("Server closed the connection");
+                return;
             }
-        }
-        while let Some(0) = buff.last() {
-            buff.pop();
-        }
+            Ok(read) => read,
+            Err(e) => {
+                error!(error = ?e, "Could not read from the server");
+                return;
+            }
+        };
+        pending.extend_from_slice(&buff[..read]);
 
-        let recv = String::from_utf8_lossy(&buff).to_string();
-        info!("Server sent {:?}", recv);
-        channel
-            .send(match serde_json::from_str::<Event>(recv.as_str()) {
+        while let Some(end) = pending.iter().position(|byte| *byte == 0) {
+            let frame: Vec<u8> = pending.drain(..=end).collect();
+            let recv = String::from_utf8_lossy(&frame[..end]).to_string();
+            info!("Server sent {:?}", recv);
+            match serde_json::from_str::<Event<E>>(recv.as_str()) {
                 Ok(val) => {
                     info!("returning {:?}", val);
-                    val
+                    if channel.send(val).is_err() {
+                        return;
+                    }
                 }
                 _ => continue,
-            })
-            .unwrap();
+            }
+        }
     }
 }
 
 /// Converts tcp [`Event`]s to intra app [`Message`]s.
-pub async fn manage_event(
-    writer: tokio::sync::broadcast::Sender<Message>,
-    mut feedback_reader: tokio::sync::broadcast::Receiver<Message>,
-    mut reader: Receiver<Event>,
+///
+/// The `nickname` is sent to the server after every ready check, see [`Event::Nickname`].
+pub async fn manage_event<E: Edition>(
+    writer: tokio::sync::broadcast::Sender<Message<E>>,
+    mut feedback_reader: tokio::sync::broadcast::Receiver<Message<E>>,
+    mut reader: Receiver<Event<E>>,
     mut write_part: OwnedWriteHalf,
+    nickname: Option<String>,
 ) {
     info!("Monitoring TCP");
 
     loop {
-        let event: Event = match reader.recv().await {
+        let event: Event<E> = match reader.recv().await {
             Ok(event) => event,
             _ => continue,
         };
         info!("Server sent {:?}", event);
+        let ready_check = event == Event::ReadyCheck;
 
-        let to_send: Event = match event {
+        let to_send: Event<E> = match event {
             // =======================================================================
             //                      Requires player intervention
             // =======================================================================
@@ -195,15 +213,34 @@ pub async fn manage_event(
                 Event::Accept
             }
             Event::FinalResult(uid, scores) => {
-                // At this point we should disconnect
+                // Stay connected, the server seats us in the next game
                 writer.send(Message::FinalResult(uid, scores)).unwrap();
-                return;
+                continue;
+            }
+            Event::MatchStandings(standings) => {
+                writer.send(Message::MatchStandings(standings)).unwrap();
+                continue;
+            }
+            Event::Leaderboard(profiles) => {
+                writer.send(Message::Leaderboard(profiles)).unwrap();
+                continue;
+            }
+            Event::History(games) => {
+                writer.send(Message::History(games)).unwrap();
+                continue;
             }
 
             // =======================================================================
             //                          Automated response
             // =======================================================================
             Event::UnexpectedMessage => continue,
+            // Only the bots look at the seats
+            Event::Seats(_) => continue,
+            Event::Kicked(reason) => {
+                error!(?reason, "The server dropped the connection");
+                let _ = writer.send(Message::Exit);
+                return;
+            }
             Event::WaitingForPlayers => {
                 writer.send(Message::WaitingForPlayers).unwrap();
                 continue;
@@ -213,6 +250,10 @@ pub async fn manage_event(
                 writer.send(Message::NewRound).unwrap();
                 continue;
             }
+            Event::Config(config) => {
+                writer.send(Message::Config(config)).unwrap();
+                continue;
+            }
             unexpected => {
                 error!("Got unhandled message: {:?}", unexpected);
                 continue;
@@ -220,11 +261,71 @@ pub async fn manage_event(
         };
 
         send_event(&mut write_part, to_send).await;
+        if ready_check {
+            introduce::<E>(&mut write_part, &nickname).await;
+        }
+    }
+}
+
+/// Answers the [`Event`]s with an [`Autopilot`] instead of asking a frontend.
+///
+/// Runs until the server closes the connection.
+pub async fn manage_event_headless<E: Edition>(
+    mut autopilot: Autopilot<E>,
+    mut reader: Receiver<Event<E>>,
+    mut write_part: OwnedWriteHalf,
+    nickname: Option<String>,
+) {
+    info!("Monitoring TCP headless");
+
+    loop {
+        let event: Event<E> = match reader.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                warn!(missed, "Headless player fell behind the server");
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+        info!("Server sent {:?}", event);
+        if let Event::FinalResult(uid, scores) = &event {
+            info!(uid, ?scores, "Game over");
+        }
+        if let Event::MatchStandings(standings) = &event {
+            info!(?standings, over = standings.over(), "Match standings");
+        }
+        if let Event::Leaderboard(profiles) = &event {
+            info!(?profiles, "Leaderboard");
+        }
+        if let Event::History(games) = &event {
+            info!(?games, "History");
+        }
+        if let Event::Kicked(reason) = &event {
+            error!(?reason, "The server dropped the connection");
+            return;
+        }
+        let ready_check = event == Event::ReadyCheck;
+        if let Some(response) = autopilot.respond(event).await {
+            send_event(&mut write_part, response).await;
+        }
+        if ready_check {
+            introduce::<E>(&mut write_part, &nickname).await;
+        }
+    }
+}
+
+/// Sends the nickname, if any, once the ready check is answered.
+///
+/// The server only reads unrequested events as messages, so the nickname is sent after
+/// the answer.
+async fn introduce<E: Edition>(write_part: &mut OwnedWriteHalf, nickname: &Option<String>) {
+    if let Some(nickname) = nickname {
+        send_event::<E>(write_part, Event::Nickname(nickname.clone())).await;
     }
 }
 
 /// Small little tcp sender.
-async fn send_event(write_part: &mut OwnedWriteHalf, event: Event) {
+async fn send_event<E: Edition>(write_part: &mut OwnedWriteHalf, event: Event<E>) {
     let to_send: Vec<u8> = event.into();
     write_part.write_all(&to_send).await.unwrap();
 }
@@ -0,0 +1,428 @@
+//! Defines a store of player profiles that outlives the server.
+//!
+//! Every finished game is recorded in a SQLite database, with the seated players, the score
+//! of every round, the final standings and the seed that the deck was shuffled with. Players
+//! that sent a nickname are rated, see [`rating`], players without one are recorded but not
+//! rated.
+//!
+//! The server keeps the database in the file given with `--profiles <file>`, it is created
+//! if it does not exist.
+
+use std::{
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use super::rules::edition::RoundScore;
+
+pub mod rating;
+
+/// The longest nickname that is accepted
+pub const MAX_NICKNAME: usize = 24;
+
+/// Seeds are stored as the bits of an `i64`, SQLite has no unsigned integers
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS profiles (
+    nickname TEXT PRIMARY KEY,
+    rating REAL NOT NULL,
+    games INTEGER NOT NULL,
+    wins INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS games (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    seed INTEGER NOT NULL,
+    game INTEGER NOT NULL,
+    played_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS seats (
+    game INTEGER NOT NULL REFERENCES games(id),
+    player INTEGER NOT NULL,
+    nickname TEXT REFERENCES profiles(nickname),
+    place INTEGER NOT NULL,
+    score INTEGER NOT NULL,
+    rating REAL,
+    PRIMARY KEY (game, player)
+);
+CREATE TABLE IF NOT EXISTS rounds (
+    game INTEGER NOT NULL REFERENCES games(id),
+    player INTEGER NOT NULL,
+    round INTEGER NOT NULL,
+    scoring TEXT NOT NULL,
+    PRIMARY KEY (game, player, round)
+);
+";
+
+#[derive(Debug)]
+pub enum ProfileError {
+    /// The database could not be opened, read or written
+    Sql(rusqlite::Error),
+    /// A score could not be stored or read back
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sql(e) => write!(f, "profile database error, {}", e),
+            Self::Json(e) => write!(f, "could not convert a stored score, {}", e),
+        }
+    }
+}
+
+/// The rating and record of a player
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub nickname: String,
+    pub rating: f64,
+    /// The number of rated games played
+    pub games: usize,
+    /// The number of games won or shared the win of
+    pub wins: usize,
+}
+
+impl Profile {
+    /// Returns the profile of a player that has not played yet
+    pub fn new(nickname: &str) -> Self {
+        Self {
+            nickname: nickname.to_owned(),
+            rating: rating::INITIAL_RATING,
+            games: 0,
+            wins: 0,
+        }
+    }
+}
+
+/// A seat in a finished game
+#[derive(Debug, Clone)]
+pub struct Seat<S> {
+    pub player: u8,
+    /// The nickname that the player sent, if any
+    pub nickname: Option<String>,
+    /// The place in the final standings
+    pub place: usize,
+    /// The score of every round
+    pub rounds: Vec<S>,
+}
+
+/// A finished game
+#[derive(Debug, Clone)]
+pub struct GameRecord<S> {
+    /// The seed that the match was shuffled with
+    pub seed: u64,
+    /// The number of the game in its match, starting at 1
+    pub game: usize,
+    pub seats: Vec<Seat<S>>,
+}
+
+/// A game in the history of a player
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PastGame {
+    pub id: i64,
+    pub seed: u64,
+    pub game: usize,
+    pub place: usize,
+    pub score: usize,
+    /// The rating after the game
+    pub rating: f64,
+}
+
+/// Returns true if the nickname can be used for a profile
+pub fn valid_nickname(nickname: &str) -> bool {
+    !nickname.trim().is_empty()
+        && nickname.trim() == nickname
+        && nickname.chars().count() <= MAX_NICKNAME
+}
+
+/// A connection to the profile database
+#[derive(Debug)]
+pub struct Profiles {
+    connection: Connection,
+}
+
+impl Profiles {
+    /// Opens the database, creating the tables if they do not exist
+    pub fn open(path: &Path) -> Result<Self, ProfileError> {
+        let connection = Connection::open(path).map_err(ProfileError::Sql)?;
+        Self::init(connection)
+    }
+
+    /// Opens a database that only lives as long as the connection
+    #[cfg(test)]
+    pub fn in_memory() -> Result<Self, ProfileError> {
+        Self::init(Connection::open_in_memory().map_err(ProfileError::Sql)?)
+    }
+
+    fn init(connection: Connection) -> Result<Self, ProfileError> {
+        // Several lobbies may finish a game at the same time
+        connection
+            .busy_timeout(std::time::Duration::from_secs(5))
+            .map_err(ProfileError::Sql)?;
+        connection
+            .execute_batch(SCHEMA)
+            .map_err(ProfileError::Sql)?;
+        Ok(Self { connection })
+    }
+
+    /// Records a finished game and rates the players that have a nickname
+    ///
+    /// Returns the updated profiles of the rated players.
+    pub fn record<S: RoundScore>(
+        &mut self,
+        record: &GameRecord<S>,
+    ) -> Result<Vec<Profile>, ProfileError> {
+        let tx = self.connection.transaction().map_err(ProfileError::Sql)?;
+        let played_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs() as i64);
+        tx.execute(
+            "INSERT INTO games (seed, game, played_at) VALUES (?1, ?2, ?3)",
+            params![record.seed as i64, record.game as i64, played_at],
+        )
+        .map_err(ProfileError::Sql)?;
+        let id = tx.last_insert_rowid();
+
+        let mut rated = Vec::new();
+        for seat in &record.seats {
+            if let Some(nickname) = &seat.nickname {
+                let profile = Self::find(&tx, nickname)?.unwrap_or_else(|| Profile::new(nickname));
+                rated.push((seat.place, profile));
+            }
+        }
+        let ratings = rating::update(
+            &rated
+                .iter()
+                .map(|(place, profile)| (profile.rating, *place))
+                .collect::<Vec<(f64, usize)>>(),
+        );
+        let mut updated = Vec::with_capacity(rated.len());
+        for ((place, mut profile), rating) in rated.into_iter().zip(ratings) {
+            profile.rating = rating;
+            profile.games += 1;
+            if place == 1 {
+                profile.wins += 1;
+            }
+            tx.execute(
+                "INSERT INTO profiles (nickname, rating, games, wins) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(nickname) DO UPDATE SET
+                 rating = excluded.rating, games = excluded.games, wins = excluded.wins",
+                params![
+                    profile.nickname,
+                    profile.rating,
+                    profile.games as i64,
+                    profile.wins as i64
+                ],
+            )
+            .map_err(ProfileError::Sql)?;
+            updated.push(profile);
+        }
+
+        for seat in &record.seats {
+            let score: usize = seat.rounds.iter().map(RoundScore::total_score).sum();
+            let rating = updated
+                .iter()
+                .find(|profile| Some(&profile.nickname) == seat.nickname.as_ref())
+                .map(|profile| profile.rating);
+            tx.execute(
+                "INSERT INTO seats (game, player, nickname, place, score, rating)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    id,
+                    seat.player,
+                    seat.nickname,
+                    seat.place as i64,
+                    score as i64,
+                    rating
+                ],
+            )
+            .map_err(ProfileError::Sql)?;
+            for (round, scoring) in seat.rounds.iter().enumerate() {
+                let scoring = serde_json::to_string(scoring).map_err(ProfileError::Json)?;
+                tx.execute(
+                    "INSERT INTO rounds (game, player, round, scoring) VALUES (?1, ?2, ?3, ?4)",
+                    params![id, seat.player, round as i64, scoring],
+                )
+                .map_err(ProfileError::Sql)?;
+            }
+        }
+        tx.commit().map_err(ProfileError::Sql)?;
+        Ok(updated)
+    }
+
+    fn find(connection: &Connection, nickname: &str) -> Result<Option<Profile>, ProfileError> {
+        connection
+            .query_row(
+                "SELECT nickname, rating, games, wins FROM profiles WHERE nickname = ?1",
+                params![nickname],
+                |row| {
+                    Ok(Profile {
+                        nickname: row.get(0)?,
+                        rating: row.get(1)?,
+                        games: row.get::<_, i64>(2)? as usize,
+                        wins: row.get::<_, i64>(3)? as usize,
+                    })
+                },
+            )
+            .optional()
+            .map_err(ProfileError::Sql)
+    }
+
+    /// Returns the `limit` highest rated players, the highest first
+    pub fn leaderboard(&self, limit: usize) -> Result<Vec<Profile>, ProfileError> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT nickname, rating, games, wins FROM profiles
+                 ORDER BY rating DESC, nickname LIMIT ?1",
+            )
+            .map_err(ProfileError::Sql)?;
+        let rows = statement
+            .query_map(params![limit as i64], |row| {
+                Ok(Profile {
+                    nickname: row.get(0)?,
+                    rating: row.get(1)?,
+                    games: row.get::<_, i64>(2)? as usize,
+                    wins: row.get::<_, i64>(3)? as usize,
+                })
+            })
+            .map_err(ProfileError::Sql)?;
+        rows.collect::<Result<_, _>>().map_err(ProfileError::Sql)
+    }
+
+    /// Returns the last `limit` games of a player, the latest first
+    pub fn history(&self, nickname: &str, limit: usize) -> Result<Vec<PastGame>, ProfileError> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT games.id, games.seed, games.game, seats.place, seats.score, seats.rating
+                 FROM seats JOIN games ON games.id = seats.game
+                 WHERE seats.nickname = ?1 ORDER BY games.id DESC LIMIT ?2",
+            )
+            .map_err(ProfileError::Sql)?;
+        let rows = statement
+            .query_map(params![nickname, limit as i64], |row| {
+                Ok(PastGame {
+                    id: row.get(0)?,
+                    seed: row.get::<_, i64>(1)? as u64,
+                    game: row.get::<_, i64>(2)? as usize,
+                    place: row.get::<_, i64>(3)? as usize,
+                    score: row.get::<_, i64>(4)? as usize,
+                    rating: row.get(5)?,
+                })
+            })
+            .map_err(ProfileError::Sql)?;
+        rows.collect::<Result<_, _>>().map_err(ProfileError::Sql)
+    }
+
+    /// Returns the score of every round that a player played in a recorded game
+    #[cfg(test)]
+    pub fn rounds<S: RoundScore>(&self, game: i64, player: u8) -> Result<Vec<S>, ProfileError> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT scoring FROM rounds WHERE game = ?1 AND player = ?2 ORDER BY round")
+            .map_err(ProfileError::Sql)?;
+        let rows = statement
+            .query_map(params![game, player], |row| row.get::<_, String>(0))
+            .map_err(ProfileError::Sql)?;
+        let mut rounds = Vec::new();
+        for row in rows {
+            let scoring = row.map_err(ProfileError::Sql)?;
+            rounds.push(serde_json::from_str(&scoring).map_err(ProfileError::Json)?);
+        }
+        Ok(rounds)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::australia::rules::scoring::AustraliaScoring;
+
+    use super::{valid_nickname, GameRecord, Profiles, Seat};
+
+    fn seat(
+        player: u8,
+        nickname: Option<&str>,
+        place: usize,
+        points: usize,
+    ) -> Seat<AustraliaScoring> {
+        Seat {
+            player,
+            nickname: nickname.map(str::to_owned),
+            place,
+            rounds: vec![
+                AustraliaScoring::from_values(points, 0, 0, 0, 0, Vec::new()),
+                AustraliaScoring::from_values(0, points, 0, 0, 0, Vec::new()),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_record() {
+        let mut profiles = Profiles::in_memory().unwrap();
+        let record = GameRecord {
+            seed: u64::MAX,
+            game: 1,
+            seats: vec![
+                seat(0, Some("ada"), 1, 9),
+                seat(1, None, 2, 5),
+                seat(2, Some("bob"), 3, 2),
+            ],
+        };
+        let updated = profiles.record(&record).unwrap();
+        assert_eq!(updated.len(), 2);
+        assert_eq!(updated[0].nickname, "ada");
+        assert_eq!((updated[0].games, updated[0].wins), (1, 1));
+        assert_eq!((updated[1].games, updated[1].wins), (1, 0));
+        assert!(updated[0].rating > updated[1].rating);
+
+        let history = profiles.history("bob", 10).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!((history[0].place, history[0].score), (3, 4));
+        assert_eq!(history[0].seed, u64::MAX);
+        assert_eq!(Some(history[0].rating), updated.get(1).map(|bob| bob.rating));
+
+        // The scores are stored round by round, unrated players as well
+        let rounds: Vec<AustraliaScoring> = profiles.rounds(history[0].id, 1).unwrap();
+        assert_eq!(rounds, record.seats[1].rounds);
+
+        assert!(profiles.history("nobody", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_leaderboard() {
+        let mut profiles = Profiles::in_memory().unwrap();
+        for (game, winner) in ["bob", "bob", "ada"].iter().enumerate() {
+            let loser = match *winner {
+                "bob" => "ada",
+                _ => "bob",
+            };
+            let record = GameRecord {
+                seed: 7,
+                game: game + 1,
+                seats: vec![seat(0, Some(winner), 1, 3), seat(1, Some(loser), 2, 1)],
+            };
+            profiles.record(&record).unwrap();
+        }
+        let leaderboard = profiles.leaderboard(10).unwrap();
+        let names: Vec<&str> = leaderboard
+            .iter()
+            .map(|profile| profile.nickname.as_str())
+            .collect();
+        assert_eq!(names, vec!["bob", "ada"]);
+        assert_eq!((leaderboard[0].games, leaderboard[0].wins), (3, 2));
+        assert_eq!(profiles.leaderboard(1).unwrap().len(), 1);
+        assert_eq!(profiles.history("ada", 2).unwrap()[0].place, 1);
+    }
+
+    #[test]
+    fn test_valid_nickname() {
+        assert!(valid_nickname("ada"));
+        assert!(valid_nickname("Ada Lovelace"));
+        assert!(!valid_nickname(""));
+        assert!(!valid_nickname("  "));
+        assert!(!valid_nickname(" ada"));
+        assert!(!valid_nickname(&"a".repeat(25)));
+    }
+}
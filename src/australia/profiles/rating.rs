@@ -0,0 +1,81 @@
+//! Defines the Elo rating of the players.
+//!
+//! A game of several players is rated as if every player played every other player, a
+//! better place is a win, a shared place is a draw. The rating change is scaled down by
+//! the number of opponents so that a four player game moves the ratings as much as a two
+//! player game.
+
+use std::cmp::Ordering;
+
+/// The rating of a player that has not played any rated game
+pub const INITIAL_RATING: f64 = 1500.0;
+/// The most that a rating can change in one game
+const K: f64 = 32.0;
+
+/// Returns the expected score of a player against an opponent, between 0 and 1
+pub fn expected(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+/// Returns the ratings after a game, given the rating and place of every player
+pub fn update(players: &[(f64, usize)]) -> Vec<f64> {
+    if players.len() < 2 {
+        return players.iter().map(|(rating, _)| *rating).collect();
+    }
+    let k = K / (players.len() - 1) as f64;
+    players
+        .iter()
+        .enumerate()
+        .map(|(idx, (rating, place))| {
+            let change: f64 = players
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != idx)
+                .map(|(_, (opponent, opponent_place))| {
+                    let actual = match place.cmp(opponent_place) {
+                        Ordering::Less => 1.0,
+                        Ordering::Equal => 0.5,
+                        Ordering::Greater => 0.0,
+                    };
+                    actual - expected(*rating, *opponent)
+                })
+                .sum();
+            rating + k * change
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{expected, update, INITIAL_RATING};
+
+    #[test]
+    fn test_expected() {
+        assert_eq!(expected(1500.0, 1500.0), 0.5);
+        assert!(expected(1700.0, 1500.0) > 0.75);
+        assert!((expected(1700.0, 1500.0) + expected(1500.0, 1700.0) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_update() {
+        let ratings = update(&[(INITIAL_RATING, 1), (INITIAL_RATING, 2)]);
+        assert_eq!(ratings, vec![1516.0, 1484.0]);
+
+        // A draw between equals changes nothing
+        let ratings = update(&[(INITIAL_RATING, 1), (INITIAL_RATING, 1)]);
+        assert_eq!(ratings, vec![INITIAL_RATING, INITIAL_RATING]);
+
+        // Beating a stronger player gains more than beating a weaker one
+        let upset = update(&[(1400.0, 1), (1600.0, 2)]);
+        let expected = update(&[(1600.0, 1), (1400.0, 2)]);
+        assert!(upset[0] - 1400.0 > expected[0] - 1600.0);
+
+        // Rating points are only moved in between the players
+        let ratings = update(&[(1450.0, 1), (1600.0, 3), (1500.0, 1), (1520.0, 4)]);
+        let before = 1450.0 + 1600.0 + 1500.0 + 1520.0;
+        assert!((ratings.iter().sum::<f64>() - before).abs() < 1e-9);
+        assert!(ratings[0] > 1450.0 && ratings[3] < 1520.0);
+
+        assert_eq!(update(&[(1234.0, 1)]), vec![1234.0]);
+    }
+}
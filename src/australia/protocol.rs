@@ -5,13 +5,20 @@
 
 
 use serde::{Deserialize, Serialize};
-use server::engine::event::{BackendEvent, GameEvent};
+use server::engine::{
+    event::{BackendEvent, GameEvent},
+    limits::DisconnectReason,
+};
 use tui::ui::UiMessage;
 
-use super::rules::{
-    cards::{AustraliaCard, AustralianActivity},
-    scoring::Scoring,
-    AustraliaPlayer,
+use super::{
+    profiles::{PastGame, Profile},
+    rules::{
+        config::GameConfig,
+        edition::Edition,
+        ranking::{MatchStandings, Placement},
+        Player,
+    },
 };
 
 /// Events sent to and from the [`server`].
@@ -19,7 +26,8 @@ use super::rules::{
 /// Not all of these events require a response from the player.
 /// To check wether an event requires a response use [`GameEvent::requires_response`]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum Event {
+#[serde(bound = "")]
+pub enum Event<E: Edition> {
     /// Server is ready to start
     /// 
     /// This requires a [`Accept`](Event::Accept) or a [`Deny`](Event::Deny) response
@@ -33,7 +41,7 @@ pub enum Event {
     /// 
     /// This requires a [`Accept`](Event::Accept)  response
     /// any other response will be disregarded
-    Deal(AustraliaCard),
+    Deal(E::Card),
     /// Asks the player to show a card
     /// 
     /// This requires a [`Show`](Event::Show(())) response.
@@ -41,7 +49,7 @@ pub enum Event {
     /// Shows the given card to the other players and discards it.
     Show(usize),
     /// Shows the player what another player has in their show pile
-    ShowPile(u8, Vec<AustraliaCard>, Vec<char>),
+    ShowPile(u8, Vec<E::Card>, Vec<char>),
     /// Asks the player to discard a card
     /// 
     /// This requires a [`Show`](Event::Discard(())) response.
@@ -51,33 +59,73 @@ pub enum Event {
     /// Asks the player activity they want to score this turn
     /// 
     /// This requires a [`ScoreActivity`](Event::ScoreActivity(())) response.
-    ScoreActivityQuery(Vec<AustralianActivity>),
+    ScoreActivityQuery(Vec<E::Activity>),
     /// Scores the given activity if any
-    ScoreActivity(Option<AustralianActivity>),
+    ScoreActivity(Option<E::Activity>),
     /// Overwrites the current hand replacing it with a new one.
-    ReassignHand(Vec<AustraliaCard>),
+    ReassignHand(Vec<E::Card>),
     /// Status message
     WaitingForPlayers,
     /// Here fore completeness sake
     /// 
     /// Maps from [`Connected`](BackendEvent::Connected(()))
     Connected(u8),
+    /// Here fore completeness sake, the client reads this before any other event
+    ///
+    /// Maps from [`RuleSet`](BackendEvent::RuleSet(()))
+    RuleSet(String, Option<String>),
 
     /// Server did not expect that response
     UnexpectedMessage,
     /// Unused here fore completeness sake.
     Resend,
+    /// The server drops the connection for the given reason
+    ///
+    /// Maps from [`Kicked`](BackendEvent::Kicked(()))
+    Kicked(DisconnectReason),
     /// Syncs player game data with the servers.
-    /// 
-    /// This requires a [`Accept`](Event::Accept)  response
+    ///
+    /// The player holds the regions that were completed at the table, so that a client can
+    /// project the score of the round. This requires a [`Accept`](Event::Accept)  response
     /// any other response will be disregarded
-    Sync(AustraliaPlayer),
+    Sync(Player<E>),
     /// Status message informs player that the new round has started
     NewRound,
+    /// Status message with the config of the game that is about to start
+    ///
+    /// Sent before the first card is dealt, the seed is left out so the deck can not be
+    /// predicted.
+    Config(GameConfig),
+    /// Status message with the ids of the seated players, hands are passed forward in this order
+    ///
+    /// Sent with the [`Config`](Event::Config) before the first card is dealt.
+    Seats(Vec<u8>),
     /// Status message informs players that the game cannot start yet
     LobbyFull,
     /// Status message informs players of game final result.
-    FinalResult(u8, Vec<(u8, Scoring)>),
+    ///
+    /// Holds the id of the receiving player and the standings, tied players share a place.
+    FinalResult(u8, Vec<Placement<E::Score>>),
+    /// Status message informs players of the standings of the match after each game.
+    ///
+    /// Only sent when the match has more than one game, the next game starts with a
+    /// [`ReadyCheck`](Event::ReadyCheck) unless the match is over.
+    MatchStandings(MatchStandings),
+    /// Names the sending player, used to rate the player in the servers profiles.
+    ///
+    /// Sent by the player after answering a [`ReadyCheck`](Event::ReadyCheck), it does not
+    /// require a response.
+    Nickname(String),
+    /// Status message informs players of the highest rated players.
+    ///
+    /// Only sent by servers that keep profiles, when a player names themselves and after
+    /// every game.
+    Leaderboard(Vec<Profile>),
+    /// Status message informs a named player of their last games, the latest first.
+    ///
+    /// Sent with the [`Leaderboard`](Event::Leaderboard) to the players that sent a
+    /// [`Nickname`](Event::Nickname).
+    History(Vec<PastGame>),
 }
 
 /// Messages passed between [`tui`] and
@@ -87,55 +135,63 @@ pub enum Event {
 /// so for detailed explanations read the docs 
 /// for [`Event`]
 #[derive(Debug, Clone)]
-pub enum Message {
+pub enum Message<E: Edition> {
     WaitingForPlayers,
     ReadyCheck,
     Ready,
     NotReady,
-    Deal(AustraliaCard),
+    Deal(E::Card),
     DiscardQuery,
-    Discard(AustraliaCard, usize),
+    Discard(E::Card, usize),
     ShowQuery,
-    Show(AustraliaCard, usize),
-    ShowOtherHand(usize, Vec<AustraliaCard>, Vec<char>),
-    ReassignHand(Vec<AustraliaCard>),
-    Sync(AustraliaPlayer),
+    Show(E::Card, usize),
+    ShowOtherHand(usize, Vec<E::Card>, Vec<char>),
+    ReassignHand(Vec<E::Card>),
+    Sync(Player<E>),
     Ok,
-    ScoreActivityQuery(Vec<AustralianActivity>),
-    ScoreActivity(Option<AustralianActivity>),
+    ScoreActivityQuery(Vec<E::Activity>),
+    ScoreActivity(Option<E::Activity>),
     NewRound,
+    Config(GameConfig),
     Exit,
-    FinalResult(u8, Vec<(u8, Scoring)>),
+    FinalResult(u8, Vec<Placement<E::Score>>),
+    MatchStandings(MatchStandings),
+    Leaderboard(Vec<Profile>),
+    History(Vec<PastGame>),
 }
 
-impl TryInto<BackendEvent> for Event {
+impl<E: Edition> TryInto<BackendEvent> for Event<E> {
     type Error = ();
     fn try_into(self) -> Result<BackendEvent, Self::Error> {
         match self {
             Self::Connected(uid) => Ok(BackendEvent::Connected(uid)),
+            Self::RuleSet(name, fingerprint) => Ok(BackendEvent::RuleSet(name, fingerprint)),
             Self::UnexpectedMessage => Ok(BackendEvent::UnexpectedMessage),
             Self::Resend => Ok(BackendEvent::Resend),
+            Self::Kicked(reason) => Ok(BackendEvent::Kicked(reason)),
             _ => Err(()),
         }
     }
 }
 
-impl Into<Vec<u8>> for Event {
+impl<E: Edition> Into<Vec<u8>> for Event<E> {
     fn into(self) -> Vec<u8> {
         serde_json::to_string(&self).unwrap().into_bytes()
     }
 }
-impl From<BackendEvent> for Event {
+impl<E: Edition> From<BackendEvent> for Event<E> {
     fn from(value: BackendEvent) -> Self {
         match value {
             BackendEvent::Connected(uid) => Self::Connected(uid),
+            BackendEvent::RuleSet(name, fingerprint) => Self::RuleSet(name, fingerprint),
             BackendEvent::UnexpectedMessage => Self::UnexpectedMessage,
             BackendEvent::Resend => Event::Resend,
+            BackendEvent::Kicked(reason) => Event::Kicked(reason),
         }
     }
 }
 
-impl GameEvent for Event {
+impl<E: Edition> GameEvent for Event<E> {
     fn requires_response(&self) -> bool {
         match self {
             Event::ReadyCheck => true,
@@ -150,4 +206,4 @@ impl GameEvent for Event {
     }
 }
 
-impl UiMessage for Message {}
+impl<E: Edition> UiMessage for Message<E> {}
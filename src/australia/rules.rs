@@ -1,60 +1,133 @@
 pub mod cards;
+pub mod config;
+pub mod edition;
+pub mod invariants;
 pub mod meta;
+pub mod ranking;
+pub mod rule_book;
 pub mod scoring;
 pub mod states;
 
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use server::engine::rules::{Action, Completed, Error, Instantiable, New, Received, RuleEngine};
+use server::engine::rules::{Action, AsyncRuleEngine, Completed, Error, Instantiable, New, Received};
+use tracing::{error, info, info_span, warn, Instrument, Span};
 
 use self::{
-    cards::{AustraliaCard, AustralianActivity, Card},
-    scoring::Scoring,
+    cards::{AustraliaCard, AustraliaDeck, AustralianActivity, AustralianRegion, Card, Collection},
+    config::GameConfig,
+    edition::{Edition, RoundScore, Site},
+    rule_book::RuleBook,
+    scoring::AustraliaScoring,
     states::{DealingCards, GameState, WaitingForPlayers},
 };
 
-use super::{protocol::Event, tui::pages::main_page::CardArea};
+use super::{
+    profiles::{self, GameRecord, Profiles, Seat},
+    protocol::Event,
+    score_sheet::{ScoreSheet, SheetFormat},
+};
+
+/// The number of players that are listed on the leaderboard
+const LEADERBOARD_SIZE: usize = 10;
+/// The number of past games that a player is sent
+const HISTORY_SIZE: usize = 10;
+
+/// The boomerang australia [`Edition`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AustraliaEdition;
+
+impl Edition for AustraliaEdition {
+    type Card = AustraliaCard;
+    type Region = AustralianRegion;
+    type Activity = AustralianActivity;
+    type Score = AustraliaScoring;
+    type RoundScoring = states::Scoring<Self>;
+    const CONFIGURABLE_SCORING: bool = true;
+
+    fn deck() -> AustraliaDeck {
+        AustraliaDeck::default()
+    }
+    fn regions() -> Vec<AustralianRegion> {
+        AustralianRegion::to_vec()
+    }
+    fn activities() -> Vec<AustralianActivity> {
+        AustralianActivity::to_vec()
+    }
+    fn activity(card: &AustraliaCard) -> Option<AustralianActivity> {
+        card.activity()
+    }
+    fn collection(card: &AustraliaCard) -> Option<usize> {
+        card.collection().map(|collection| collection.score())
+    }
+    fn completed(region: &AustralianRegion, visited: &[char]) -> bool {
+        region.completed(visited)
+    }
+    fn score(
+        config: &GameConfig,
+        player: &mut AustraliaPlayer,
+        unclaimed: &[AustralianRegion],
+        activity: Option<AustralianActivity>,
+    ) -> AustraliaScoring {
+        RuleBook::australia(config).score(player, unclaimed, activity)
+    }
+}
 
 /// Player abstraction holds all relevant game data for a specific player.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct AustraliaPlayer {
+#[serde(bound = "")]
+pub struct Player<E: Edition> {
     id: u8,
-    hand: Vec<AustraliaCard>,
-    discard_pile: Vec<AustraliaCard>,
-    show_pile: Vec<AustraliaCard>,
-    un_scored_activity: Vec<AustralianActivity>,
+    hand: Vec<E::Card>,
+    discard_pile: Vec<E::Card>,
+    show_pile: Vec<E::Card>,
+    un_scored_activity: Vec<E::Activity>,
     #[allow(dead_code)]
-    activity_scores: Vec<(AustralianActivity, usize)>,
+    activity_scores: Vec<(E::Activity, usize)>,
     card_ptr: usize,
     visited: Vec<char>,
-    scoring: Vec<Scoring>,
+    scoring: Vec<E::Score>,
+    /// The regions that a player at the table completed in an earlier round, they do not give
+    /// a bonus again
+    claimed: Vec<E::Region>,
 }
-impl AustraliaPlayer {
+pub type AustraliaPlayer = Player<AustraliaEdition>;
+
+impl<E: Edition> Player<E> {
     pub fn new(id: u8) -> Self {
         Self {
             id,
             hand: Vec::new(),
             discard_pile: Vec::new(),
             show_pile: Vec::new(),
-            un_scored_activity: AustralianActivity::to_vec(),
+            un_scored_activity: E::activities(),
             activity_scores: Vec::new(),
             card_ptr: 0,
             visited: Vec::new(),
             scoring: Vec::new(),
+            claimed: Vec::new(),
         }
     }
 }
 // Modifiers
-impl AustraliaPlayer {
+impl<E: Edition> Player<E> {
     /// Moves the card from that index to the discard pile
     ///
     /// Returns error if that index is invalid
-    pub fn discard(&mut self, idx: &usize) -> Result<AustraliaCard, Error> {
+    pub fn discard(&mut self, idx: &usize) -> Result<E::Card, Error> {
         if *idx >= self.hand.len() {
             return Err(Error::NoSuchCard);
         }
         let card = self.hand.remove(*idx);
         self.discard_pile.push(card.clone());
-        self.decrement();
+        if self.card_ptr > 0 {
+            self.card_ptr -= 1;
+        }
         Ok(card)
     }
     /// Moves the card from that index to the show pile
@@ -81,8 +154,8 @@ impl AustraliaPlayer {
         cards.extend(self.get_hand());
 
         for card in cards {
-            if !self.visited.contains(&card.to_char()) {
-                self.visit(card.to_char());
+            if !self.visited.contains(&card.site()) {
+                self.visit(card.site());
             }
         }
 
@@ -92,19 +165,84 @@ impl AustraliaPlayer {
     }
 
     /// Overwrites the cards in the players hand with a new list of cards
-    pub fn set_cards(mut self, cards: Vec<AustraliaCard>) -> Self {
+    pub fn set_cards(mut self, cards: Vec<E::Card>) -> Self {
         self.hand = cards;
         self
     }
+
+    /// Overwrites the discard and show piles, used to recreate a round from what has been seen
+    pub fn set_piles(mut self, discard_pile: Vec<E::Card>, show_pile: Vec<E::Card>) -> Self {
+        self.discard_pile = discard_pile;
+        self.show_pile = show_pile;
+        self
+    }
+
+    /// Returns a copy of the player that holds the cards it keeps this round and `card`
+    ///
+    /// The hand is left empty since it is passed on, the card is thrown if nothing has
+    /// been thrown yet and shown otherwise. Used to value a move before it is made.
+    pub fn keeping(&self, card: E::Card) -> Self {
+        let mut player = self.clone();
+        player.hand.clear();
+        match player.discard_pile.is_empty() {
+            true => player.discard_pile.push(card),
+            false => player.show_pile.push(card),
+        }
+        player
+    }
+
+    /// Returns the score of the round as if it ended now, the player is not changed
+    ///
+    /// The regions that a player at the table completed in an earlier round do not give a
+    /// bonus, the server sends them with every [`Sync`](Event::Sync).
+    pub fn projected_score(&self, config: &GameConfig, activity: Option<E::Activity>) -> E::Score {
+        let claimed: Vec<E::Region> = self
+            .scoring
+            .iter()
+            .flat_map(|score| score.completed_regions())
+            .chain(self.claimed.iter().copied())
+            .collect();
+        let unclaimed: Vec<E::Region> = E::regions()
+            .into_iter()
+            .filter(|region| !claimed.contains(region))
+            .collect();
+        E::score(config, &mut self.clone(), &unclaimed, activity)
+    }
+
+    /// Returns the activity that gives the highest projected score and that score
+    ///
+    /// [`None`] means that not scoring an activity is at least as good.
+    pub fn best_projection(&self, config: &GameConfig) -> (Option<E::Activity>, E::Score) {
+        let mut best = (None, self.projected_score(config, None));
+        for activity in self.un_scored_activity() {
+            let score = self.projected_score(config, Some(activity));
+            if score.total_score() > best.1.total_score() {
+                best = (Some(activity), score);
+            }
+        }
+        best
+    }
+
+    /// Removes the activity from the ones that the player has left to score
+    pub fn take_activity(&mut self, activity: E::Activity) {
+        self.un_scored_activity.retain(|el| *el != activity);
+    }
 }
 // Getters
-impl AustraliaPlayer {
+impl<E: Edition> Player<E> {
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+    /// Returns the activities that the player has not yet scored
+    pub fn un_scored_activity(&self) -> Vec<E::Activity> {
+        self.un_scored_activity.clone()
+    }
     /// Returns true if the players hand is empty
     fn hand_empty(&self) -> bool {
         self.hand.len() == 0
     }
     /// Returns a list of the players round scores
-    pub fn scores(&self) -> Vec<Scoring> {
+    pub fn scores(&self) -> Vec<E::Score> {
         self.scoring.clone()
     }
     /// Returns a list of all the places that the player
@@ -114,8 +252,8 @@ impl AustraliaPlayer {
     pub fn privately_visited(&mut self) -> Vec<char> {
         let mut ret = self.publicly_visited();
         for el in self.get_discard() {
-            if !ret.contains(&el.to_char()) {
-                ret.push(el.to_char());
+            if !ret.contains(&el.site()) {
+                ret.push(el.site());
             }
         }
         ret
@@ -127,18 +265,27 @@ impl AustraliaPlayer {
         let cards = self.get_show();
         let mut ret = self.get_visited();
         for el in cards {
-            if !ret.contains(&el.to_char()) {
-                ret.push(el.to_char());
+            if !ret.contains(&el.site()) {
+                ret.push(el.site());
             }
         }
         ret
     }
 
     /// Pushes a new score to the players list of scores
-    pub fn add_score(&mut self, score: Scoring) {
+    pub fn add_score(&mut self, score: E::Score) {
         self.scoring.push(score);
     }
 
+    /// Marks the regions as completed by a player at the table
+    pub fn claim(&mut self, regions: &[E::Region]) {
+        for region in regions {
+            if !self.claimed.contains(region) {
+                self.claimed.push(*region);
+            }
+        }
+    }
+
     /// pushes a new site to the places that a player has visited
     pub fn visit(&mut self, site: char) {
         self.visited.push(site);
@@ -150,22 +297,22 @@ impl AustraliaPlayer {
     }
 
     /// Returns a clone of the cards on the players hand
-    pub fn get_hand(&self) -> Vec<AustraliaCard> {
+    pub fn get_hand(&self) -> Vec<E::Card> {
         self.hand.clone()
     }
 
     /// Returns a clone of the cards in the players discard pile
-    pub fn get_discard(&self) -> Vec<AustraliaCard> {
+    pub fn get_discard(&self) -> Vec<E::Card> {
         self.discard_pile.clone()
     }
 
     /// Returns a clone of the cards in the players show pile
-    pub fn get_show(&self) -> Vec<AustraliaCard> {
+    pub fn get_show(&self) -> Vec<E::Card> {
         self.show_pile.clone()
     }
 }
 // Strictly TUI helpers
-impl AustraliaPlayer {
+impl<E: Edition> Player<E> {
     pub fn card_ptr(&mut self) -> &mut usize {
         &mut self.card_ptr
     }
@@ -173,7 +320,7 @@ impl AustraliaPlayer {
     pub fn get_cards<const COUNT: usize>(
         &self,
         start: usize,
-    ) -> (&[AustraliaCard], (usize, usize)) {
+    ) -> (&[E::Card], (usize, usize)) {
         if self.hand_empty() {
             return (&[], (0, 0));
         }
@@ -192,7 +339,7 @@ impl AustraliaPlayer {
     }
 }
 
-impl tui::ui::UiElement for AustraliaPlayer {
+impl<E: Edition> tui::ui::UiElement for Player<E> {
     /// This should never be called
     fn new() -> Self {
         Self {
@@ -200,17 +347,18 @@ impl tui::ui::UiElement for AustraliaPlayer {
             hand: Vec::new(),
             discard_pile: Vec::new(),
             show_pile: Vec::new(),
-            un_scored_activity: AustralianActivity::to_vec(),
+            un_scored_activity: E::activities(),
             activity_scores: Vec::new(),
             card_ptr: 0,
             visited: Vec::new(),
             scoring: Vec::new(),
+            claimed: Vec::new(),
         }
     }
 }
 
-impl tui::ui::Hand<AustraliaCard> for AustraliaPlayer {
-    fn get<const COUNT: usize>(&self, start: usize) -> (&[AustraliaCard], (usize, usize)) {
+impl<E: Edition> tui::ui::Hand<E::Card> for Player<E> {
+    fn get<const COUNT: usize>(&self, start: usize) -> (&[E::Card], (usize, usize)) {
         self.get_cards::<COUNT>(start)
     }
 
@@ -218,48 +366,251 @@ impl tui::ui::Hand<AustraliaCard> for AustraliaPlayer {
         self.hand_size()
     }
 
-    fn add_card(&mut self, card: AustraliaCard) {
+    fn add_card(&mut self, card: E::Card) {
         if !self.hand.contains(&card) {
             self.hand.push(card);
         }
     }
-    fn discard_card(&mut self, idx: usize) -> AustraliaCard {
+    fn discard_card(&mut self, idx: usize) -> E::Card {
         self.hand.remove(idx)
     }
 }
 
-pub struct Australia<const CAPACITY: usize, const MIN_PLAYERS: usize> {
-    state: Box<dyn GameState>,
+/// Runs a game of any [`Edition`]
+pub struct Boomerang<E: Edition, const CAPACITY: usize, const MIN_PLAYERS: usize> {
+    state: Box<dyn GameState<E>>,
+    /// Checks the [`invariants`] after every transition
+    checked: bool,
+    /// Set once an invariant is broken, the game is then stopped
+    broken: bool,
+    /// The nicknames that the players sent, by player id
+    nicknames: HashMap<usize, String>,
+    /// The database that finished games are recorded in, if the server keeps profiles
+    profiles: Option<PathBuf>,
+    /// The directory and format that the score sheets of finished games are written in
+    score_sheets: Option<(PathBuf, SheetFormat)>,
+    /// Events that are sent with the next actions of the game
+    outbox: Vec<Action<New, Event<E>>>,
+}
+
+impl<E: Edition, const CAPACITY: usize, const MIN_PLAYERS: usize>
+    Boomerang<E, CAPACITY, MIN_PLAYERS>
+{
+    /// A span tagged with the name of the current state
+    fn span(&self) -> Span {
+        info_span!("state", name = self.state.name())
+    }
+
+    fn transition(&mut self, state: Box<dyn GameState<E>>) {
+        info!(from = self.state.name(), to = state.name(), "State transition");
+        self.state = state;
+        if !self.checked {
+            return;
+        }
+        if let Some(meta) = self.state.metadata() {
+            if let Err(violation) = invariants::check(meta) {
+                error!(%violation, ?meta, "Game invariant broken, stopping the game");
+                self.broken = true;
+            }
+        }
+    }
+
+    /// Stores the nickname of a player and sends them the leaderboard and their history,
+    /// returns false if the nickname can not be used
+    ///
+    /// A nickname has to be unique in the lobby since the profiles are keyed on it.
+    async fn name(&mut self, uid: usize, nickname: String) -> bool {
+        let taken = self
+            .nicknames
+            .iter()
+            .any(|(other, name)| *other != uid && *name == nickname);
+        if taken || !profiles::valid_nickname(&nickname) {
+            warn!(uid, nickname, taken, "Rejected nickname");
+            return false;
+        }
+        info!(uid, nickname, "Player named");
+        self.nicknames.insert(uid, nickname.clone());
+        let Some(path) = self.profiles.clone() else {
+            return true;
+        };
+        let players = vec![(uid, Some(nickname))];
+        let events = Self::blocking(move || match open_profiles(&path) {
+            Some(profiles) => Self::profile_events(&profiles, &players),
+            None => Vec::new(),
+        });
+        self.outbox.extend(events.await);
+        true
+    }
+
+    /// Runs database and file work on the blocking pool so that the lobby task is not stalled
+    async fn blocking<T: Send + 'static>(work: impl FnOnce() -> Vec<T> + Send + 'static) -> Vec<T> {
+        let span = Span::current();
+        match tokio::task::spawn_blocking(move || span.in_scope(work)).await {
+            Ok(done) => done,
+            Err(e) => {
+                error!(error = %e, "Could not store or read the finished games");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Returns the leaderboard for every player and the history of the named players
+    fn profile_events(
+        profiles: &Profiles,
+        players: &[(usize, Option<String>)],
+    ) -> Vec<Action<New, Event<E>>> {
+        let leaderboard = match profiles.leaderboard(LEADERBOARD_SIZE) {
+            Ok(leaderboard) => Some(leaderboard),
+            Err(e) => {
+                error!(error = %e, "Could not read the leaderboard");
+                None
+            }
+        };
+        let mut events = Vec::new();
+        for (uid, nickname) in players {
+            if let Some(leaderboard) = &leaderboard {
+                events.push(Action::new(*uid, Event::Leaderboard(leaderboard.clone())));
+            }
+            let Some(nickname) = nickname else {
+                continue;
+            };
+            match profiles.history(nickname, HISTORY_SIZE) {
+                Ok(history) => events.push(Action::new(*uid, Event::History(history))),
+                Err(e) => error!(error = %e, nickname, "Could not read the history"),
+            }
+        }
+        events
+    }
+
+    /// Returns the record of the game that just ended
+    fn game_record(&mut self) -> Option<GameRecord<E::Score>> {
+        let meta = self.state.metadata()?;
+        let standings = meta.rank();
+        let mut seats = Vec::with_capacity(standings.len());
+        for placement in standings {
+            let rounds = meta
+                .get_players()
+                .iter()
+                .find(|player| player.id == placement.player)
+                .map(Player::scores)
+                .unwrap_or_default();
+            seats.push(Seat {
+                player: placement.player,
+                nickname: self.nicknames.get(&(placement.player as usize)).cloned(),
+                place: placement.place,
+                rounds,
+            });
+        }
+        Some(GameRecord {
+            seed: meta.seed(),
+            game: meta.standings().played,
+            seats,
+        })
+    }
+
+    /// Writes the score sheet and records the game that just ended, then sends the new
+    /// leaderboard to every player and the new history to the named players
+    async fn record(&mut self, players: &[usize]) {
+        if self.profiles.is_none() && self.score_sheets.is_none() {
+            return;
+        }
+        let Some(record) = self.game_record() else {
+            return;
+        };
+        let score_sheets = self.score_sheets.clone();
+        let path = self.profiles.clone();
+        let players: Vec<(usize, Option<String>)> = players
+            .iter()
+            .map(|player| (*player, self.nicknames.get(player).cloned()))
+            .collect();
+        let events = Self::blocking(move || {
+            if let Some((dir, format)) = &score_sheets {
+                match ScoreSheet::new::<E>(&record).save(dir, *format) {
+                    Ok(path) => info!(?path, "Wrote the score sheet"),
+                    Err(e) => error!(error = %e, ?dir, "Could not write the score sheet"),
+                }
+            }
+            let Some(mut profiles) = path.as_deref().and_then(open_profiles) else {
+                return Vec::new();
+            };
+            match profiles.record(&record) {
+                Ok(rated) => info!(?rated, "Recorded the game"),
+                Err(e) => {
+                    error!(error = %e, ?record, "Could not record the game");
+                    return Vec::new();
+                }
+            }
+            Self::profile_events(&profiles, &players)
+        });
+        self.outbox.extend(events.await);
+    }
+}
+
+/// Opens the profile database, it is opened for every use since several lobbies write to it
+fn open_profiles(path: &Path) -> Option<Profiles> {
+    match Profiles::open(path) {
+        Ok(profiles) => Some(profiles),
+        Err(e) => {
+            error!(error = %e, ?path, "Could not open the profiles");
+            None
+        }
+    }
 }
 
-impl<const CAPACITY: usize, const MIN_PLAYERS: usize> RuleEngine
-    for Australia<CAPACITY, MIN_PLAYERS>
+// Mirrors the signatures of `AsyncRuleEngine`
+#[allow(clippy::ptr_arg)]
+#[async_trait]
+impl<E: Edition, const CAPACITY: usize, const MIN_PLAYERS: usize> AsyncRuleEngine
+    for Boomerang<E, CAPACITY, MIN_PLAYERS>
 {
-    type Event = Event;
-    fn get_next_action(
+    type Event = Event<E>;
+    /// Finished games are recorded on the blocking pool, this awaits the database
+    async fn get_next_action(
         &mut self,
         players: &Vec<usize>,
     ) -> (tokio::time::Duration, Vec<Action<New, Self::Event>>) {
-        let (delay, actions, state) = self.state.get_next_action(players);
+        let (delay, mut actions, state) = {
+            let _span = self.span().entered();
+            self.state.get_next_action(players)
+        };
+        let ended = actions
+            .iter()
+            .any(|action| matches!(action.action(), Event::FinalResult(..)));
+        if ended {
+            let span = self.span();
+            self.record(players).instrument(span).await;
+        }
+        let _span = self.span().entered();
         if let Some(state) = state {
-            self.state = state;
+            self.transition(state);
+        }
+        if self.broken {
+            return (delay, Vec::new());
         }
+        actions.append(&mut self.outbox);
 
         (delay, actions)
     }
 
-    fn register_response(
+    async fn register_response(
         &mut self,
         _: &Vec<usize>,
         response: (Self::Event, &Action<Received, Self::Event>),
     ) -> Result<Action<Completed, Self::Event>, Error> {
+        if let Event::Nickname(nickname) = &response.0 {
+            // The nickname crossed paths with a request, which is still waiting for its answer
+            let span = self.span();
+            self.name(response.1.player(), nickname.clone()).instrument(span).await;
+            return Err(Error::NotAResponse);
+        }
+        let _span = self.span().entered();
         let completed_action =
             Action::<Completed, Self::Event>::new(response.1.player(), response.1.action().clone());
         let res = self.state.register_response(response);
         match res {
             Ok(val) => {
                 match val {
-                    Some(state) => self.state = state,
+                    Some(state) => self.transition(state),
                     None => {}
                 }
                 Ok(completed_action)
@@ -268,20 +619,46 @@ impl<const CAPACITY: usize, const MIN_PLAYERS: usize> RuleEngine
         }
     }
 
-    fn register_message(
+    async fn register_message(
         &mut self,
         _players: &Vec<usize>,
-        _message: &Action<New, Self::Event>,
+        message: &Action<New, Self::Event>,
     ) -> Result<(), Error> {
-        return Err(Error::UnexpectedMessage);
+        let span = self.span();
+        let named = match message.action() {
+            Event::Nickname(nickname) => self.name(message.player(), nickname).instrument(span),
+            _ => return Err(Error::UnexpectedMessage),
+        };
+        match named.await {
+            true => Ok(()),
+            false => Err(Error::UnexpectedMessage),
+        }
+    }
+
+    fn accepts_players(&self) -> bool {
+        self.state.accepts_players()
+    }
+
+    fn finished(&self) -> bool {
+        self.broken || self.state.finished()
     }
 }
-impl<const CAPACITY: usize, const MIN_PLAYERS: usize> Instantiable
-    for Australia<CAPACITY, MIN_PLAYERS>
+impl<E: Edition, const CAPACITY: usize, const MIN_PLAYERS: usize> Instantiable
+    for Boomerang<E, CAPACITY, MIN_PLAYERS>
 {
-    fn new() -> Self {
-        Australia {
-            state: Box::new(WaitingForPlayers::<DealingCards>::new(None)),
+    type Config = GameConfig;
+    fn new(config: GameConfig) -> Self {
+        Boomerang {
+            checked: invariants::ALWAYS_CHECKED || config.check_invariants,
+            profiles: config.profiles.clone(),
+            score_sheets: config
+                .score_sheets
+                .clone()
+                .map(|dir| (dir, config.score_sheet_format)),
+            state: Box::new(WaitingForPlayers::<E, DealingCards<E>>::new(None, config)),
+            broken: false,
+            nicknames: HashMap::new(),
+            outbox: Vec::new(),
         }
     }
 }
@@ -290,22 +667,30 @@ impl<const CAPACITY: usize, const MIN_PLAYERS: usize> Instantiable
 mod tests {
     use tui::ui::UiElement;
 
-    use crate::australia::rules::{cards::AustralianRegion, meta::GameMetaData};
+    use crate::australia::rules::{
+        cards::{AustralianAnimal, AustralianRegion},
+        config::GameConfig,
+        meta,
+        rule_book::{Collections, ThrowCatch, TouristSites},
+        scoring::LedgerEntry,
+    };
 
     use super::*;
 
+    type GameMetaData = meta::GameMetaData<AustraliaEdition>;
+
     #[test]
     /// Req 10. a
     fn test_10_a() {
         let mut player = AustraliaPlayer::new(0);
         player.hand.push(AustraliaCard::BarossaValley);
         player.discard_pile.push(AustraliaCard::BarossaValley);
-        let scoring = Scoring::new().score_throw_catch(&player);
+        let scoring = RuleBook::new().add(ThrowCatch).score(&mut player, &[], None);
         assert_eq!(scoring.throw_catch(), 0);
         let mut player = AustraliaPlayer::new(0);
         player.hand.push(AustraliaCard::TheBungleBungles);
         player.discard_pile.push(AustraliaCard::Uluru);
-        let scoring = Scoring::new().score_throw_catch(&player);
+        let scoring = RuleBook::new().add(ThrowCatch).score(&mut player, &[], None);
         assert_eq!(scoring.throw_catch(), 3);
     }
 
@@ -319,7 +704,9 @@ mod tests {
         player.hand.push(AustraliaCard::KalbarriNationalPark);
 
         let unclaimed_regions = vec![AustralianRegion::WesternAustralia] /* Initialize unclaimed regions */;
-        let scoring = Scoring::new().score_regions(&mut player, &unclaimed_regions);
+        let scoring = RuleBook::new()
+            .add(TouristSites)
+            .score(&mut player, &unclaimed_regions, None);
         assert_eq!(scoring.tourist_sites(), 7);
         assert_eq!(scoring.completed_regions().len(), 1);
 
@@ -330,7 +717,9 @@ mod tests {
         player.hand.push(AustraliaCard::KalbarriNationalPark);
 
         let unclaimed_regions = vec![];
-        let scoring = Scoring::new().score_regions(&mut player, &unclaimed_regions);
+        let scoring = RuleBook::new()
+            .add(TouristSites)
+            .score(&mut player, &unclaimed_regions, None);
         assert_eq!(scoring.tourist_sites(), 4);
         assert_eq!(scoring.completed_regions().len(), 0);
 
@@ -341,11 +730,13 @@ mod tests {
         player.hand.push(AustraliaCard::KalbarriNationalPark);
 
         let unclaimed_regions = vec![AustralianRegion::WesternAustralia];
-        let scoring = Scoring::new().score_regions(&mut player, &unclaimed_regions);
+        let scoring = RuleBook::new()
+            .add(TouristSites)
+            .score(&mut player, &unclaimed_regions, None);
         assert_eq!(scoring.tourist_sites(), 7);
         assert_eq!(scoring.completed_regions().len(), 1);
 
-        let mut meta = GameMetaData::new(&[0, 1]);
+        let mut meta = GameMetaData::new(&[0, 1], GameConfig::default());
         for player in meta.get_players().iter_mut() {
             player.hand.push(AustraliaCard::TheBungleBungles);
             player.hand.push(AustraliaCard::ThePinnacles);
@@ -388,7 +779,7 @@ mod tests {
             assert_eq!(player.scoring[1].tourist_sites(), 0);
         }
 
-        let mut meta = GameMetaData::new(&[0, 1]);
+        let mut meta = GameMetaData::new(&[0, 1], GameConfig::default());
         for player in meta.get_players().iter_mut() {
             player.hand.push(AustraliaCard::TheBungleBungles);
             player.hand.push(AustraliaCard::ThePinnacles);
@@ -441,7 +832,7 @@ mod tests {
         player.hand.push(AustraliaCard::MargaretRiver); // 3 pt
         player.hand.push(AustraliaCard::DaintreeRainforest); // 5 pt
 
-        let scoring = Scoring::new().score_collections(&player);
+        let scoring = RuleBook::new().add(Collections).score(&mut player, &[], None);
 
         assert_eq!(scoring.collections(), 1 + 2 + 3 + 5);
 
@@ -451,13 +842,13 @@ mod tests {
         player.hand.push(AustraliaCard::KalbarriNationalPark); // 2 pt
         player.hand.push(AustraliaCard::MargaretRiver); // 3 pt
 
-        let scoring = Scoring::new().score_collections(&player);
+        let scoring = RuleBook::new().add(Collections).score(&mut player, &[], None);
 
         assert_eq!(scoring.collections(), (1 + 2 + 3) * 2);
     }
     #[test]
     fn test_10_d() {
-        let mut meta = GameMetaData::new(&[0, 1]);
+        let mut meta = GameMetaData::new(&[0, 1], GameConfig::default());
 
         for player in meta.get_players().iter_mut() {
             player.hand.push(AustraliaCard::ThePinnacles);
@@ -478,7 +869,7 @@ mod tests {
     }
     #[test]
     fn test_10_e() {
-        let mut meta = GameMetaData::new(&[0, 1]);
+        let mut meta = GameMetaData::new(&[0, 1], GameConfig::default());
 
         for player in meta.get_players().iter_mut() {
             player.hand.push(AustraliaCard::TheBungleBungles);
@@ -507,12 +898,12 @@ mod tests {
     }
     #[test]
     fn test_12() {
-        let mut meta = GameMetaData::new(&[0, 1, 2, 3]);
+        let mut meta = GameMetaData::new(&[0, 1, 2, 3], GameConfig::default());
         let scores = vec![
-            Scoring::from_values(10, 21, 11, 1, 2, Vec::new()),
-            Scoring::from_values(11, 20, 11, 1, 2, Vec::new()),
-            Scoring::from_values(8, 20, 11, 1, 2, Vec::new()),
-            Scoring::from_values(9, 20, 11, 1, 2, Vec::new()),
+            AustraliaScoring::from_values(10, 21, 11, 1, 2, Vec::new()),
+            AustraliaScoring::from_values(11, 20, 11, 1, 2, Vec::new()),
+            AustraliaScoring::from_values(8, 20, 11, 1, 2, Vec::new()),
+            AustraliaScoring::from_values(9, 20, 11, 1, 2, Vec::new()),
         ];
         // Simulate four rounds of scoring
         for _ in 0..4 {
@@ -525,8 +916,138 @@ mod tests {
         }
 
         let ranking = meta.rank();
-        for ((idx, _), target) in ranking.iter().zip(vec![1, 0, 3, 2]) {
-            assert_eq!(*idx, target);
+        for (placement, target) in ranking.iter().zip(vec![1, 0, 3, 2]) {
+            assert_eq!(placement.player, target);
+        }
+    }
+
+    #[test]
+    fn test_ledger() {
+        let mut player = AustraliaPlayer::new(0);
+        player.discard_pile.push(AustraliaCard::TheBungleBungles);
+        player.show_pile.push(AustraliaCard::ThePinnacles);
+        player.show_pile.push(AustraliaCard::MargaretRiver);
+        player.hand.push(AustraliaCard::KalbarriNationalPark);
+        let scoring = AustraliaEdition::score(
+            &GameConfig::default(),
+            &mut player,
+            &AustralianRegion::to_vec(),
+            Some(AustralianActivity::IndigenousCulture),
+        );
+        let ledger = scoring.ledger();
+
+        assert!(ledger.contains(&LedgerEntry::ThrowCatch {
+            throw: AustraliaCard::TheBungleBungles,
+            catch: AustraliaCard::KalbarriNationalPark,
+            points: scoring.throw_catch(),
+        }));
+        let sites = ledger
+            .iter()
+            .filter(|entry| matches!(entry, LedgerEntry::Site { .. }))
+            .count();
+        assert_eq!(sites, 4);
+        assert!(ledger.contains(&LedgerEntry::Region {
+            region: AustralianRegion::WesternAustralia,
+            points: 3,
+        }));
+        assert_eq!(scoring.tourist_sites(), sites + 3);
+        assert!(ledger.iter().any(|entry| matches!(
+            entry,
+            LedgerEntry::Collections { doubled: true, points, .. } if *points == scoring.collections()
+        )));
+        assert!(ledger.contains(&LedgerEntry::AnimalPair {
+            animal: AustralianAnimal::Kangaroos.into(),
+            cards: (AustraliaCard::ThePinnacles, AustraliaCard::MargaretRiver),
+            points: scoring.animals(),
+        }));
+        assert!(ledger.contains(&LedgerEntry::Activity {
+            activity: AustralianActivity::IndigenousCulture,
+            cards: vec![AustraliaCard::TheBungleBungles],
+            points: 0,
+        }));
+
+        // The ledger of every round is kept in the total
+        let total: AustraliaScoring = vec![scoring.clone(), scoring.clone()].into_iter().sum();
+        assert_eq!(total.ledger().len(), 2 * ledger.len());
+        assert_eq!(RoundScore::ledger(&scoring).len(), ledger.len());
+    }
+
+    #[test]
+    fn test_projected_score() {
+        let mut player = AustraliaPlayer::new(0);
+        player.discard_pile.push(AustraliaCard::TheBungleBungles);
+        player.show_pile.push(AustraliaCard::ThePinnacles);
+        player.show_pile.push(AustraliaCard::MargaretRiver);
+        player.hand.push(AustraliaCard::KalbarriNationalPark);
+
+        // The projection is scored with the config of the game, not the default one
+        let config = GameConfig {
+            activity_points: vec![0, 5, 10, 20, 30, 40, 50],
+            ..Default::default()
+        };
+        let expected = AustraliaEdition::score(
+            &config,
+            &mut player.clone(),
+            &AustralianRegion::to_vec(),
+            Some(AustralianActivity::Bushwalking),
+        );
+        let projected = player.projected_score(&config, Some(AustralianActivity::Bushwalking));
+        assert_eq!(projected, expected);
+        assert_ne!(
+            projected,
+            player.projected_score(&GameConfig::default(), Some(AustralianActivity::Bushwalking))
+        );
+        // Projecting does not use up the activity
+        assert!(player
+            .un_scored_activity()
+            .contains(&AustralianActivity::Bushwalking));
+
+        let (activity, best) = player.best_projection(&config);
+        for activity in player.un_scored_activity() {
+            let score = player.projected_score(&config, Some(activity));
+            assert!(score.total_score() <= best.total_score());
         }
+        assert_eq!(best, player.projected_score(&config, activity));
+
+        // A region that another player at the table completed does not give a bonus
+        assert!(!expected.completed_regions().is_empty());
+        let mut late = player.clone();
+        late.claim(&expected.completed_regions());
+        assert!(late.projected_score(&config, None).completed_regions().is_empty());
+
+        // A region completed in an earlier round does not give a bonus again
+        player.scoring.push(expected);
+        let again = player.projected_score(&config, None);
+        assert!(again.completed_regions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_nickname() {
+        let path = std::env::temp_dir().join(format!("boomerang-{}.db", std::process::id()));
+        let config = GameConfig {
+            profiles: Some(path.clone()),
+            ..Default::default()
+        };
+        let mut rules = Boomerang::<AustraliaEdition, 4, 2>::new(config);
+        assert!(rules.name(1, "ada".to_owned()).await);
+        assert!(!rules.name(2, "ada".to_owned()).await);
+        // The named player is sent the leaderboard and their history, both empty for now
+        let events: Vec<Event<AustraliaEdition>> =
+            rules.outbox.iter().map(|action| action.action()).collect();
+        assert_eq!(
+            events,
+            vec![Event::Leaderboard(Vec::new()), Event::History(Vec::new())]
+        );
+        assert!(rules.outbox.iter().all(|action| action.player() == 1));
+
+        // A nickname that crosses paths with a request is not an answer to it
+        let request = Action::<Received, Event<AustraliaEdition>>::new(2, Event::ReadyCheck);
+        let response = (Event::Nickname("bob".to_owned()), &request);
+        assert!(matches!(
+            rules.register_response(&vec![1, 2], response).await,
+            Err(Error::NotAResponse)
+        ));
+        assert_eq!(rules.nicknames.get(&2).map(String::as_str), Some("bob"));
+        std::fs::remove_file(path).unwrap();
     }
 }
@@ -3,10 +3,12 @@
 //! Also defines all of the data
 //! that relates to cards a enums
 
-use log::error;
-use rand::seq::SliceRandom;
+use tracing::error;
+use rand::{seq::SliceRandom, Rng};
 use serde::{Deserialize, Serialize};
 
+use super::edition::Site;
+
 pub trait Collection {
     fn score(&self) -> usize;
 }
@@ -25,18 +27,22 @@ pub trait Card<C: Collection, A: Animal>: std::fmt::Debug {
 }
 
 #[derive(Debug, Serialize, PartialEq, Deserialize, Clone)]
-pub struct AustraliaDeck {
-    deck: Vec<AustraliaCard>,
+pub struct Deck<C> {
+    deck: Vec<C>,
 }
+pub type AustraliaDeck = Deck<AustraliaCard>;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AustraliaHand {
     deck: Vec<AustraliaCard>,
 }
 
-impl AustraliaDeck {
+impl<C: Clone> Deck<C> {
+    pub fn new(cards: Vec<C>) -> Self {
+        Self { deck: cards }
+    }
     /// Pops the last card from the deck, given that the deck is shuffled this is random.
-    pub fn draft(&mut self) -> AustraliaCard {
+    pub fn draft(&mut self) -> C {
         match self.deck.pop() {
             Some(card) => card,
             _ => {
@@ -45,12 +51,16 @@ impl AustraliaDeck {
             }
         }
     }
-    #[cfg(test)]
-    pub fn cards(&mut self) -> Vec<AustraliaCard> {
+    /// Returns the number of cards left in the deck
+    pub fn len(&self) -> usize {
+        self.deck.len()
+    }
+    /// Returns the cards left in the deck
+    pub fn cards(&self) -> Vec<C> {
         self.deck.clone()
     }
-    pub fn shuffle(&mut self) {
-        self.deck.shuffle(&mut rand::thread_rng());
+    pub fn shuffle(&mut self, rng: &mut impl Rng) {
+        self.deck.shuffle(rng);
     }
 }
 
@@ -61,6 +71,12 @@ impl tui::ui::UiElement for AustraliaCard {
     }
 }
 
+impl Site for AustraliaCard {
+    fn site(&self) -> char {
+        self.to_char()
+    }
+}
+
 impl tui::ui::Card for AustraliaCard {
     fn get_name(&self) -> &str {
         self.name()
@@ -76,6 +92,15 @@ macro_rules! regions {
         pub enum AustralianRegion {
             $($variant,)*
         }
+        impl From<AustralianRegion> for String{
+            fn from(value: AustralianRegion) -> String{
+                match value{
+                    $(
+                        AustralianRegion::$variant => $name.to_owned(),
+                    )+
+                }
+            }
+        }
         #[allow(dead_code)]
         impl AustralianRegion {
             pub fn to_vec() -> Vec<AustralianRegion> {
@@ -101,7 +126,7 @@ macro_rules! regions {
                     )+
                 }
             }
-            pub fn completed(self,identifiers:&Vec<char>) -> bool{
+            pub fn completed(self,identifiers:&[char]) -> bool{
                 match self{
                     $(
                         AustralianRegion::$variant => {
@@ -135,6 +160,7 @@ macro_rules! activities {
                 }
             }
         }
+        #[allow(dead_code)]
         impl AustralianActivity {
             pub fn to_vec() -> Vec<AustralianActivity> {
                 vec![$(AustralianActivity::$variant,)*]
@@ -278,7 +304,7 @@ macro_rules! card {
             $(activity : $activity:ident)?
         }
     )+) => {
-        #[derive(Debug,Serialize,Deserialize,Clone,Copy,PartialEq)]
+        #[derive(Debug,Serialize,Deserialize,Clone,Copy,PartialEq,Hash,Eq)]
         pub enum AustraliaCard{
             $(
                 $name,
@@ -595,7 +621,7 @@ mod test {
     fn test_3() {
         let deck1 = AustraliaDeck::default();
         let mut deck2 = AustraliaDeck::default();
-        deck2.shuffle();
+        deck2.shuffle(&mut rand::thread_rng());
         assert_ne!(deck1, deck2);
     }
     #[test]
@@ -0,0 +1,341 @@
+//! Defines the game parameters that can be changed without touching the rules.
+//!
+//! The parameters are loaded from a TOML file, any field that is left out falls
+//! back to the boomerang australia default, for example a quick two round game:
+//!
+//! ```toml
+//! rounds = 2
+//! hand_size = 5
+//! pass_sequence = ["Forward", "Forward", "Forward", "Backward"]
+//! ```
+//!
+//! Setting `seed` shuffles the deck the same way in every game, which makes games reproducible.
+//!
+//! A lobby can play a match of several games, the seats rotate in between games and the
+//! players are ranked on their wins, then on their points over the whole match:
+//!
+//! ```toml
+//! games = 3
+//! best_of = true
+//! ```
+//!
+//! With `best_of` the match ends as soon as no other player can catch up with the leader.
+//!
+//! The points for an activity are looked up by the number of matching cards, a player with
+//! more cards than the table lists gets the last entry:
+//!
+//! ```toml
+//! activity_points = [0, 1, 3, 6, 10, 15, 21]
+//! ```
+//!
+//! House rules are added to the [`rule book`](super::rule_book) by name:
+//!
+//! ```toml
+//! house_rules = ["UndoubledCollections", "GrandTour"]
+//! ```
+//!
+//! Only boomerang australia scores with `activity_points` and `house_rules`, the other
+//! editions reject a config that changes them.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::australia::score_sheet::SheetFormat;
+
+use super::{edition::Edition, meta::TWO_PLAYERS, rule_book::HouseRule, states::pass::Direction};
+
+/// Number of cards that a player needs for one discard, one show and the final pass
+const MIN_HAND_SIZE: usize = 3;
+/// Points for an activity with 0 to 6 matching cards in boomerang australia
+pub const ACTIVITY_POINTS: [usize; 7] = [0, 0, 2, 4, 7, 10, 15];
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file could not be read
+    Io(std::io::Error),
+    /// The file is not a valid configuration
+    Parse(toml::de::Error),
+    /// A game needs at least one round
+    NoRounds,
+    /// A match needs at least one game
+    NoGames,
+    /// The hand is too small to play a round
+    HandTooSmall { hand_size: usize, min: usize },
+    /// Every turn but the first is preceded by a pass, so there is one pass less than cards in the hand
+    PassSequenceLength { expected: usize, found: usize },
+    /// There are not enough cards in the deck to deal every player a hand
+    DeckTooSmall { needed: usize, available: usize },
+    /// The edition scores with fixed rules, the field can not be changed
+    FixedScoring { field: &'static str },
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "could not read the config, {}", e),
+            Self::Parse(e) => write!(f, "could not parse the config, {}", e),
+            Self::NoRounds => write!(f, "a game needs at least one round"),
+            Self::NoGames => write!(f, "a match needs at least one game"),
+            Self::HandTooSmall { hand_size, min } => {
+                write!(f, "hand size {} is less than the minimum {}", hand_size, min)
+            }
+            Self::PassSequenceLength { expected, found } => {
+                write!(f, "expected {} passes per round, found {}", expected, found)
+            }
+            Self::DeckTooSmall { needed, available } => write!(
+                f,
+                "dealing every player needs {} cards but the deck has {}",
+                needed, available
+            ),
+            Self::FixedScoring { field } => {
+                write!(f, "{} is not supported, this edition scores with fixed rules", field)
+            }
+        }
+    }
+}
+
+/// Game parameters, see the [module](self) docs for the file format
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameConfig {
+    /// Number of rounds in a game
+    pub rounds: usize,
+    /// Number of cards dealt to each player at the start of a round
+    pub hand_size: usize,
+    /// Direction of every pass in a round, in order
+    pub pass_sequence: Vec<Direction>,
+    /// Seed for shuffling the deck, a random seed is used if omitted
+    pub seed: Option<u64>,
+    /// Number of games in a match
+    pub games: usize,
+    /// Ends the match once the leader has more wins than anyone can still reach
+    pub best_of: bool,
+    /// Points for an activity by the number of matching cards, see [`ACTIVITY_POINTS`]
+    pub activity_points: Vec<usize>,
+    /// House rules on top of the boomerang australia scoring, none by default
+    pub house_rules: Vec<HouseRule>,
+    /// Checks the [`invariants`](super::invariants) after every state transition in release
+    /// builds, set from the command line and not read from the file
+    #[serde(skip)]
+    pub check_invariants: bool,
+    /// The [`profiles`](crate::australia::profiles) database that finished games are recorded
+    /// in, set from the command line and not read from the file
+    #[serde(skip)]
+    pub profiles: Option<PathBuf>,
+    /// The directory that the [`score sheets`](crate::australia::score_sheet) of finished
+    /// games are written to, set from the command line and not read from the file
+    #[serde(skip)]
+    pub score_sheets: Option<PathBuf>,
+    #[serde(skip)]
+    pub score_sheet_format: SheetFormat,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            rounds: 4,
+            hand_size: 7,
+            pass_sequence: vec![
+                Direction::Forward,
+                Direction::Forward,
+                Direction::Forward,
+                Direction::Forward,
+                Direction::Forward,
+                Direction::Backward,
+            ],
+            seed: None,
+            games: 1,
+            best_of: false,
+            activity_points: ACTIVITY_POINTS.to_vec(),
+            house_rules: Vec::new(),
+            check_invariants: false,
+            profiles: None,
+            score_sheets: None,
+            score_sheet_format: SheetFormat::default(),
+        }
+    }
+}
+
+impl GameConfig {
+    /// Loads a configuration from a TOML file, the configuration is not validated.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let content = std::fs::read_to_string(path).map_err(ConfigError::Io)?;
+        Self::parse(&content)
+    }
+
+    /// Parses a configuration from a TOML string, the configuration is not validated.
+    pub fn parse(content: &str) -> Result<Self, ConfigError> {
+        toml::from_str(content).map_err(ConfigError::Parse)
+    }
+
+    /// Checks that a game with up to `max_players` players can be played with the deck of `E`.
+    ///
+    /// A two player game deals a dummy hand as well, so the deck always needs to hold at least
+    /// three hands. The scoring can only be changed for editions with
+    /// [`CONFIGURABLE_SCORING`](Edition::CONFIGURABLE_SCORING).
+    pub fn validate<E: Edition>(&self, max_players: usize) -> Result<(), ConfigError> {
+        if self.rounds == 0 {
+            return Err(ConfigError::NoRounds);
+        }
+        if self.games == 0 {
+            return Err(ConfigError::NoGames);
+        }
+        if self.hand_size < MIN_HAND_SIZE {
+            return Err(ConfigError::HandTooSmall {
+                hand_size: self.hand_size,
+                min: MIN_HAND_SIZE,
+            });
+        }
+        if self.pass_sequence.len() != self.hand_size - 1 {
+            return Err(ConfigError::PassSequenceLength {
+                expected: self.hand_size - 1,
+                found: self.pass_sequence.len(),
+            });
+        }
+        let needed = self.hand_size * max_players.max(TWO_PLAYERS + 1);
+        let available = E::deck().len();
+        if needed > available {
+            return Err(ConfigError::DeckTooSmall { needed, available });
+        }
+        if !E::CONFIGURABLE_SCORING {
+            if self.activity_points != ACTIVITY_POINTS {
+                return Err(ConfigError::FixedScoring {
+                    field: "activity_points",
+                });
+            }
+            if !self.house_rules.is_empty() {
+                return Err(ConfigError::FixedScoring {
+                    field: "house_rules",
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        australia::rules::{rule_book::HouseRule, states::pass::Direction, AustraliaEdition},
+        europe::rules::EuropeEdition,
+    };
+
+    use super::{ConfigError, GameConfig};
+
+    #[test]
+    fn test_default_is_valid() {
+        assert!(GameConfig::default().validate::<AustraliaEdition>(4).is_ok());
+    }
+
+    #[test]
+    fn test_parse() {
+        let config = GameConfig::parse(
+            r#"
+            rounds = 2
+            hand_size = 4
+            pass_sequence = ["Forward", "Forward", "Backward"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.rounds, 2);
+        assert_eq!(config.hand_size, 4);
+        assert_eq!(
+            config.pass_sequence,
+            vec![Direction::Forward, Direction::Forward, Direction::Backward]
+        );
+        assert!(config.validate::<AustraliaEdition>(4).is_ok());
+
+        // Missing fields fall back to the defaults
+        let config = GameConfig::parse("rounds = 1").unwrap();
+        assert_eq!(config.hand_size, GameConfig::default().hand_size);
+        assert_eq!(config.seed, None);
+        assert_eq!(GameConfig::parse("seed = 42").unwrap().seed, Some(42));
+        assert_eq!(config.games, 1);
+        assert!(!config.best_of);
+
+        assert!(matches!(
+            GameConfig::parse("round = 1"),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate() {
+        let config = GameConfig {
+            rounds: 0,
+            ..Default::default()
+        };
+        assert!(matches!(config.validate::<AustraliaEdition>(4), Err(ConfigError::NoRounds)));
+
+        let config = GameConfig {
+            games: 0,
+            ..Default::default()
+        };
+        assert!(matches!(config.validate::<AustraliaEdition>(4), Err(ConfigError::NoGames)));
+
+        let config = GameConfig {
+            hand_size: 2,
+            pass_sequence: vec![Direction::Backward],
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate::<AustraliaEdition>(4),
+            Err(ConfigError::HandTooSmall { .. })
+        ));
+
+        let config = GameConfig {
+            hand_size: 5,
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate::<AustraliaEdition>(4),
+            Err(ConfigError::PassSequenceLength {
+                expected: 4,
+                found: 6
+            })
+        ));
+
+        let config = GameConfig {
+            hand_size: 8,
+            pass_sequence: vec![Direction::Forward; 7],
+            ..Default::default()
+        };
+        assert!(config.validate::<AustraliaEdition>(3).is_ok());
+        assert!(matches!(
+            config.validate::<AustraliaEdition>(4),
+            Err(ConfigError::DeckTooSmall {
+                needed: 32,
+                available: 28
+            })
+        ));
+    }
+
+    #[test]
+    fn test_fixed_scoring() {
+        let config = GameConfig {
+            house_rules: vec![HouseRule::GrandTour],
+            ..Default::default()
+        };
+        assert!(config.validate::<AustraliaEdition>(4).is_ok());
+        assert!(matches!(
+            config.validate::<EuropeEdition>(4),
+            Err(ConfigError::FixedScoring {
+                field: "house_rules"
+            })
+        ));
+
+        let config = GameConfig {
+            activity_points: vec![0, 1, 2],
+            ..Default::default()
+        };
+        assert!(config.validate::<AustraliaEdition>(4).is_ok());
+        assert!(matches!(
+            config.validate::<EuropeEdition>(4),
+            Err(ConfigError::FixedScoring {
+                field: "activity_points"
+            })
+        ));
+        assert!(GameConfig::default().validate::<EuropeEdition>(4).is_ok());
+    }
+}
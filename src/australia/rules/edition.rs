@@ -0,0 +1,116 @@
+//! Defines what sets the boomerang editions apart.
+//!
+//! Every edition is played the same way, a hand is dealt, one card is discarded and the
+//! rest are shown one by one while the hands are passed around the table. This means that
+//! the [`states`](super::states) can be shared, only the cards and how a round is scored
+//! differ, these are described by an [`Edition`].
+
+use std::{fmt::Debug, hash::Hash, iter::Sum, ops::AddAssign};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{
+    cards::Deck,
+    config::GameConfig,
+    meta::GameMetaData,
+    states::{AsMetaData, Transitions},
+    Player,
+};
+
+/// A card that can be dealt, passed around and visited
+pub trait Site:
+    tui::ui::Card
+    + Copy
+    + Eq
+    + Hash
+    + Debug
+    + Serialize
+    + DeserializeOwned
+    + Send
+    + Sync
+    + 'static
+{
+    /// Returns the identifier of the tourist site on the card
+    fn site(&self) -> char;
+}
+
+/// The score that a player was given for a single round
+pub trait RoundScore:
+    Clone + Debug + PartialEq + Serialize + DeserializeOwned + AddAssign + Sum + Send + Sync + 'static
+{
+    type Region;
+    /// Used to break ties, the player with the highest throw and catch wins
+    fn throw_catch(&self) -> usize;
+    /// Returns the named parts of the score in the order that they are displayed
+    fn parts(&self) -> Vec<(&str, usize)>;
+    /// Returns the regions that were completed this round
+    fn completed_regions(&self) -> Vec<Self::Region>;
+    /// Explains where the points came from, one line per card or rule that gave points
+    fn ledger(&self) -> Vec<String> {
+        Vec::new()
+    }
+    fn total_score(&self) -> usize {
+        self.parts().iter().map(|(_, score)| score).sum()
+    }
+}
+
+pub trait Edition: Debug + Clone + PartialEq + Send + Sync + 'static {
+    type Card: Site;
+    /// The regions that the tourist sites are grouped in
+    type Region: Copy
+        + PartialEq
+        + Debug
+        + Into<String>
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static;
+    /// Something that the players score once per game, at the end of a round
+    type Activity: Copy
+        + Eq
+        + Hash
+        + Debug
+        + Serialize
+        + DeserializeOwned
+        + Into<String>
+        + Send
+        + Sync
+        + 'static;
+    type Score: RoundScore<Region = Self::Region>;
+    /// The state that the hands are passed to once the last card has been shown
+    type RoundScoring: AsMetaData<Self> + Transitions + From<GameMetaData<Self>> + Sync + 'static;
+    /// True if [`score`](Self::score) reads the `activity_points` and `house_rules` of the
+    /// config, [`GameConfig::validate`] rejects them for editions that do not
+    const CONFIGURABLE_SCORING: bool = false;
+
+    /// Fingerprint of the content that is loaded at runtime, the server sends it in the
+    /// handshake and clients that loaded something else are turned away
+    fn fingerprint() -> Option<String> {
+        None
+    }
+
+    /// Returns the full deck, unshuffled
+    fn deck() -> Deck<Self::Card>;
+    /// Returns all of the regions on the map
+    fn regions() -> Vec<Self::Region>;
+    /// Returns all of the activities that a player can score
+    fn activities() -> Vec<Self::Activity>;
+    /// Returns the activity on the card if any
+    fn activity(card: &Self::Card) -> Option<Self::Activity>;
+    /// Returns the points of the collection on the card if any
+    fn collection(card: &Self::Card) -> Option<usize>;
+    /// Returns true if every site of the region has been visited
+    fn completed(region: &Self::Region, visited: &[char]) -> bool;
+    /// Scores a players round
+    ///
+    /// Only the regions in `unclaimed` give a bonus when completed, the scored activity
+    /// is removed from the ones that the player has left to score. The bots and the clients
+    /// project their scores with the config that the server sent.
+    fn score(
+        config: &GameConfig,
+        player: &mut Player<Self>,
+        unclaimed: &[Self::Region],
+        activity: Option<Self::Activity>,
+    ) -> Self::Score;
+}
@@ -0,0 +1,194 @@
+//! Defines the invariants that hold in between every state transition.
+//!
+//! Every card of the [`Edition`]s deck is in exactly one place at all times, either in
+//! the deck or in a hand, discard pile or show pile. No player, including the dummy hand
+//! of a two player game, holds more cards than the configured hand size, and the game
+//! never goes past the configured number of rounds.
+//!
+//! The invariants are always checked in debug builds, the server checks them in release
+//! builds when started with `--check-invariants`.
+
+use std::{collections::HashMap, fmt::Debug};
+
+use super::{edition::Edition, meta::GameMetaData};
+
+/// Returns true if the invariants are checked even if it was not asked for
+pub const ALWAYS_CHECKED: bool = cfg!(debug_assertions);
+
+/// Where a card is held
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Location {
+    Deck,
+    Hand(u8),
+    DiscardPile(u8),
+    ShowPile(u8),
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Deck => write!(f, "the deck"),
+            Self::Hand(uid) => write!(f, "the hand of player {}", uid),
+            Self::DiscardPile(uid) => write!(f, "the discard pile of player {}", uid),
+            Self::ShowPile(uid) => write!(f, "the show pile of player {}", uid),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation<C> {
+    /// A card of the deck is nowhere to be found
+    Missing { card: C },
+    /// A card is held in more places than it occurs in the deck
+    Duplicated { card: C, locations: Vec<Location> },
+    /// A player holds more cards than a hand is dealt
+    Overdealt {
+        player: u8,
+        cards: usize,
+        hand_size: usize,
+    },
+    /// The game has gone past the last round
+    TooManyRounds { round: usize, rounds: usize },
+}
+
+impl<C: Debug> std::fmt::Display for Violation<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing { card } => write!(f, "{:?} is missing", card),
+            Self::Duplicated { card, locations } => {
+                let locations: Vec<String> = locations.iter().map(ToString::to_string).collect();
+                write!(f, "{:?} is duplicated in {}", card, locations.join(", "))
+            }
+            Self::Overdealt {
+                player,
+                cards,
+                hand_size,
+            } => write!(
+                f,
+                "player {} holds {} cards but a hand is {} cards",
+                player, cards, hand_size
+            ),
+            Self::TooManyRounds { round, rounds } => {
+                write!(
+                    f,
+                    "round {} was started but the game has {} rounds",
+                    round + 1,
+                    rounds
+                )
+            }
+        }
+    }
+}
+
+/// Checks every invariant, returning the first one that does not hold
+pub fn check<E: Edition>(meta: &GameMetaData<E>) -> Result<(), Violation<E::Card>> {
+    let deck = E::deck().cards();
+    let mut copies: HashMap<E::Card, usize> = HashMap::new();
+    for card in &deck {
+        *copies.entry(*card).or_default() += 1;
+    }
+    let mut held: HashMap<E::Card, Vec<Location>> = HashMap::new();
+    for (location, card) in meta.cards() {
+        held.entry(card).or_default().push(location);
+    }
+    for card in deck {
+        // A card with several copies is checked once
+        let Some(expected) = copies.remove(&card) else {
+            continue;
+        };
+        let locations = held.remove(&card).unwrap_or_default();
+        if locations.len() < expected {
+            return Err(Violation::Missing { card });
+        }
+        if locations.len() > expected {
+            return Err(Violation::Duplicated { card, locations });
+        }
+    }
+    let hand_size = meta.config().hand_size;
+    for player in meta.seats() {
+        let held = player.hand_size() + player.get_discard().len() + player.get_show().len();
+        if held > hand_size {
+            return Err(Violation::Overdealt {
+                player: player.id,
+                cards: held,
+                hand_size,
+            });
+        }
+    }
+    let (round, rounds) = (meta.round(), meta.config().rounds);
+    if round >= rounds {
+        return Err(Violation::TooManyRounds { round, rounds });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::australia::rules::{config::GameConfig, meta::GameMetaData, AustraliaEdition};
+
+    use super::{check, Location, Violation};
+
+    fn dealt(players: &[usize]) -> GameMetaData<AustraliaEdition> {
+        let config = GameConfig {
+            seed: Some(7),
+            ..Default::default()
+        };
+        let mut meta = GameMetaData::new(players, config);
+        assert_eq!(check(&meta), Ok(()));
+        while !meta.draft().0 {}
+        meta
+    }
+
+    #[test]
+    fn test_holds() {
+        for players in [vec![0, 1], vec![0, 1, 2, 3]] {
+            let mut meta = dealt(&players);
+            assert_eq!(check(&meta), Ok(()));
+            for uid in &players {
+                meta.discard(uid, &0).unwrap();
+            }
+            assert_eq!(check(&meta), Ok(()));
+            for uid in &players {
+                meta.show(uid, &2).unwrap();
+            }
+            let direction = meta.next_pass();
+            meta.circulate(direction);
+            assert_eq!(check(&meta), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_violations() {
+        let mut meta = dealt(&[0, 1, 2]);
+        let card = meta.get_players()[0].hand.pop().unwrap();
+        assert_eq!(check(&meta), Err(Violation::Missing { card }));
+
+        meta.get_players()[1].hand.push(card);
+        assert!(matches!(
+            check(&meta),
+            Err(Violation::Overdealt { player: 1, .. })
+        ));
+
+        let mut meta = dealt(&[0, 1, 2]);
+        let card = meta.get_players()[2].hand[0];
+        meta.get_players()[0].show_pile.push(card);
+        let expected = Violation::Duplicated {
+            card,
+            locations: vec![Location::ShowPile(0), Location::Hand(2)],
+        };
+        assert_eq!(check(&meta), Err(expected.clone()));
+        assert!(expected.to_string().contains("the show pile of player 0"));
+
+        let mut meta = dealt(&[0, 1]);
+        for _ in 0..GameConfig::default().rounds {
+            meta.new_round();
+        }
+        assert_eq!(
+            check(&meta),
+            Err(Violation::TooManyRounds {
+                round: 4,
+                rounds: 4
+            })
+        );
+    }
+}
@@ -1,63 +1,117 @@
 //! Defines the game meta data type
 //! 
 //! This type hold all the data about the game that could be relevant at runtime.
+//!
+//! ## Two players
+//!
+//! Passing a single hand back and forth would let both players see every card, so a
+//! two player game deals a third, dummy, hand. The dummy sits after the last player
+//! when the hands are passed. Before its hand is passed on it discards the first card
+//! in its hand on the first turn and shows it on the later turns, it is never scored
+//! and never sent any messages.
+//!
+//! ## Matches
+//!
+//! A match of several games keeps its [`MatchStandings`] in between games. Every new
+//! game moves the seats one place, the first player keeps their seat and the others
+//! move up one seat, so the passes reach the players in a different order.
 
+use rand::{rngs::StdRng, SeedableRng};
 use server::engine::rules::{Action, Error, New};
-use tui::ui::UiElement;
+use tracing::debug;
 
 use crate::australia::protocol::Event;
 
 use super::{
-    cards::{AustraliaDeck, AustralianActivity, AustralianRegion},
-    scoring::Scoring,
+    cards::Deck,
+    config::GameConfig,
+    edition::{Edition, RoundScore},
+    invariants::Location,
+    ranking::{self, MatchStandings, Placement},
     states::pass::Direction,
-    AustraliaPlayer,
+    Player,
 };
 
 #[derive(Debug, Clone)]
-pub struct GameMetaData {
-    deck: AustraliaDeck,
-    players: Vec<AustraliaPlayer>,
-    non_completed_regions: Vec<AustralianRegion>,
+pub struct GameMetaData<E: Edition> {
+    deck: Deck<E::Card>,
+    players: Vec<Player<E>>,
+    non_completed_regions: Vec<E::Region>,
     round_counter: usize,
+    /// Number of passes made this round
+    pass_counter: usize,
+    config: GameConfig,
+    /// The dummy hand that is passed around in a two player game
+    dummy: Option<Player<E>>,
+    /// Shuffles the deck, seeded from the [`GameConfig`] if it has a seed
+    rng: StdRng,
+    /// The seed of the match, drawn at random if the [`GameConfig`] has none
+    seed: u64,
+    /// The standings of the match over the games played so far
+    standings: MatchStandings,
 }
+
+/// The number of players that triggers the two player variant
+pub const TWO_PLAYERS: usize = 2;
+/// The id of the dummy player, never used by a connected player
+const DUMMY_ID: u8 = u8::MAX;
 // Getters
-impl GameMetaData {
-    pub fn get_players(&mut self) -> &mut Vec<AustraliaPlayer> {
+impl<E: Edition> GameMetaData<E> {
+    pub fn get_players(&mut self) -> &mut Vec<Player<E>> {
         &mut self.players
     }
-    pub fn rank(&mut self) -> Vec<(u8, Scoring)> {
-        let mut totals = Vec::new();
-        for player in &self.players {
-            let mut sum = Scoring::new();
-            for score in player.scores() {
-                sum += score;
-            }
-            totals.push((player.id, sum));
-        }
-        totals.sort_by(|a, b| {
-            let (a_tot, b_tot) = (a.1.total_score(), b.1.total_score());
-            if a_tot > b_tot {
-                std::cmp::Ordering::Less
-            } else if a_tot == b_tot {
-                match a.1.throw_catch() > b.1.throw_catch() {
-                    true => std::cmp::Ordering::Less,
-                    false => std::cmp::Ordering::Greater,
-                }
-            } else {
-                std::cmp::Ordering::Greater
+    /// Returns the final standings, see [`ranking`](super::ranking) for how ties are broken
+    pub fn rank(&self) -> Vec<Placement<E::Score>> {
+        let standings = ranking::rank(
+            self.players
+                .iter()
+                .map(|player| (player.id, player.scores()))
+                .collect(),
+        );
+        debug!(?standings, "Final standings");
+        standings
+    }
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+    pub fn standings(&self) -> &MatchStandings {
+        &self.standings
+    }
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+    /// Returns the index of the current round, starting at zero
+    pub fn round(&self) -> usize {
+        self.round_counter
+    }
+    /// Returns every seat at the table, including the dummy in a two player game
+    pub fn seats(&self) -> impl Iterator<Item = &Player<E>> {
+        self.players.iter().chain(self.dummy.as_ref())
+    }
+    /// Returns every card in the game and where it is held
+    pub fn cards(&self) -> Vec<(Location, E::Card)> {
+        let mut cards: Vec<(Location, E::Card)> = self
+            .deck
+            .cards()
+            .into_iter()
+            .map(|card| (Location::Deck, card))
+            .collect();
+        for player in self.seats() {
+            let piles = [
+                (Location::Hand(player.id), &player.hand),
+                (Location::DiscardPile(player.id), &player.discard_pile),
+                (Location::ShowPile(player.id), &player.show_pile),
+            ];
+            for (location, pile) in piles {
+                cards.extend(pile.iter().map(|card| (location, *card)));
             }
-        });
-        println!("{:?}", totals);
-        totals
+        }
+        cards
     }
 }
-impl GameMetaData {
+impl<E: Edition> GameMetaData<E> {
     /// Returns true if the game should end if not it returns false
-    pub fn score_round(
-        &mut self,
-        score_activities: &Vec<(u8, Option<AustralianActivity>)>,
-    ) -> bool {
+    pub fn score_round(&mut self, score_activities: &Vec<(u8, Option<E::Activity>)>) -> bool {
         let mut completed = Vec::new();
         for player in &mut self.players {
             let mut activity = None;
@@ -67,12 +121,7 @@ impl GameMetaData {
                     break;
                 }
             }
-            let scoring = Scoring::new()
-                .score_throw_catch(player)
-                .score_collections(player)
-                .score_regions(player, &self.non_completed_regions)
-                .score_activity(player, activity)
-                .score_animals(player);
+            let scoring = E::score(&self.config, player, &self.non_completed_regions, activity);
 
             for el in scoring.completed_regions() {
                 if !completed.contains(&el) {
@@ -81,6 +130,9 @@ impl GameMetaData {
             }
             player.add_score(scoring);
         }
+        for player in &mut self.players {
+            player.claim(&completed);
+        }
         for el in completed {
             let mut found = None;
             for (idx, region) in self.non_completed_regions.iter().enumerate() {
@@ -93,47 +145,118 @@ impl GameMetaData {
                 self.non_completed_regions.remove(idx);
             }
         }
-        self.round_counter == 3
+        self.round_counter + 1 >= self.config.rounds
     }
     pub fn new_round(&mut self) {
-        self.deck = AustraliaDeck::default();
-        self.deck.shuffle();
+        self.deck = E::deck();
+        self.deck.shuffle(&mut self.rng);
         self.round_counter += 1;
+        self.pass_counter = 0;
         for player in self.players.iter_mut() {
             player.new_round();
         }
+        self.dummy = Self::dummy(self.players.len());
+    }
+    /// Adds the final standings of the game to the match standings
+    pub fn record_game(&mut self, result: &[Placement<E::Score>]) {
+        self.standings.record(result);
+    }
+    /// Returns the meta data of the next game of the match, with the seats rotated
+    pub fn next_game(&self) -> Self {
+        let mut ids: Vec<u8> = self.players.iter().map(|player| player.id).collect();
+        if let Some(others) = ids.get_mut(1..) {
+            others.rotate_left(1);
+        }
+        let mut rng = self.rng.clone();
+        let mut deck = E::deck();
+        deck.shuffle(&mut rng);
+        Self {
+            deck,
+            players: ids.into_iter().map(Player::new).collect(),
+            non_completed_regions: E::regions(),
+            round_counter: 0,
+            pass_counter: 0,
+            config: self.config.clone(),
+            dummy: Self::dummy(self.players.len()),
+            rng,
+            seed: self.seed,
+            standings: self.standings.clone(),
+        }
     }
 }
 
-impl GameMetaData {
-    const MAX_CARDS: usize = 7;
-    pub fn new(players: &[usize]) -> Self {
+impl<E: Edition> GameMetaData<E> {
+    pub fn new(players: &[usize], config: GameConfig) -> Self {
         let mut players_vec = Vec::with_capacity(players.len());
         for player in players {
-            players_vec.push(AustraliaPlayer::new(*player as u8));
+            players_vec.push(Player::new(*player as u8));
         }
-        let mut deck = AustraliaDeck::default();
-        deck.shuffle();
+        let seed = config.seed.unwrap_or_else(rand::random);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut deck = E::deck();
+        deck.shuffle(&mut rng);
         Self {
             deck,
             players: players_vec,
-            non_completed_regions: AustralianRegion::to_vec(),
+            non_completed_regions: E::regions(),
+            round_counter: 0,
+            pass_counter: 0,
+            dummy: Self::dummy(players.len()),
+            rng,
+            seed,
+            standings: MatchStandings::new(config.games, config.best_of),
+            config,
+        }
+    }
+    /// Creates the meta data of a round in progress, used to simulate the rest of the round
+    ///
+    /// Every card has been dealt so the deck is empty, the hand of the dummy in a two player
+    /// round is set with [`with_dummy_hand`](Self::with_dummy_hand).
+    pub fn from_round(players: Vec<Player<E>>, config: GameConfig) -> Self {
+        Self {
+            deck: Deck::new(Vec::new()),
+            non_completed_regions: E::regions(),
             round_counter: 0,
+            pass_counter: 0,
+            dummy: Self::dummy(players.len()),
+            players,
+            rng: StdRng::from_entropy(),
+            seed: 0,
+            standings: MatchStandings::new(config.games, config.best_of),
+            config,
+        }
+    }
+    /// Gives the dummy the hand, does nothing if the round has no dummy
+    pub fn with_dummy_hand(mut self, hand: Vec<E::Card>) -> Self {
+        if let Some(dummy) = &mut self.dummy {
+            dummy.hand = hand;
+        }
+        self
+    }
+    fn dummy(players: usize) -> Option<Player<E>> {
+        match players {
+            TWO_PLAYERS => Some(Player::new(DUMMY_ID)),
+            _ => None,
         }
     }
-    pub fn draft(&mut self) -> (bool, Vec<Action<New, Event>>) {
+    pub fn draft(&mut self) -> (bool, Vec<Action<New, Event<E>>>) {
         let mut done = true;
         let mut actions = Vec::new();
         for player in self.players.iter_mut() {
-            if player.hand.len() == Self::MAX_CARDS {
+            if player.hand.len() == self.config.hand_size {
                 continue;
             }
             done = false;
             let card = self.deck.draft();
-            let action = Action::<New, Event>::new(player.id as usize, Event::Deal(card));
+            let action = Action::<New, Event<E>>::new(player.id as usize, Event::Deal(card));
             player.hand.push(card);
             actions.push(action);
         }
+        if let Some(dummy) = &mut self.dummy {
+            if dummy.hand.len() < self.config.hand_size {
+                dummy.hand.push(self.deck.draft());
+            }
+        }
         (done, actions)
     }
     pub fn discard(&mut self, uid: &usize, idx: &usize) -> Result<(), Error> {
@@ -165,30 +288,46 @@ impl GameMetaData {
             _ => return Err(Error::NoSuchCard),
         }
     }
+    /// Returns the direction of the next pass this round
+    ///
+    /// The [`GameConfig`] is validated to hold exactly one direction per pass.
+    pub fn next_pass(&mut self) -> Direction {
+        let direction = self.config.pass_sequence[self.pass_counter];
+        self.pass_counter += 1;
+        direction
+    }
     /// Circulates the players hands in between them
+    ///
+    /// In a two player game the dummy first plays cards until its hand is as small as
+    /// the players hands, and then takes a seat after the last player.
     pub fn circulate(&mut self, direction: Direction) {
-        let _players = match direction {
-            Direction::Forward => {
-                let mut prev_hand = self.players.last().unwrap().hand.clone();
-                for player in self.players.iter_mut() {
-                    let intermediate = player.hand.clone();
-                    player.hand = prev_hand;
-                    prev_hand = intermediate;
-                }
+        if let Some(dummy) = &mut self.dummy {
+            let hand_size = self.players.first().map_or(0, |player| player.hand.len());
+            while dummy.hand.len() > hand_size {
+                // Like a player the dummy discards its first card and shows the rest
+                let _ = match dummy.discard_pile.is_empty() {
+                    true => dummy.discard(&0).map(|_| ()),
+                    false => dummy.show(&0),
+                };
             }
-            Direction::Backward => {
-                let mut prev_hand = self.players.first().unwrap().hand.clone();
-                for player in self.players.iter_mut().rev() {
-                    let intermediate = player.hand.clone();
-                    player.hand = prev_hand;
-                    prev_hand = intermediate;
-                }
-            }
-        };
+        }
+        let mut hands: Vec<Vec<E::Card>> = self
+            .players
+            .iter_mut()
+            .chain(self.dummy.as_mut())
+            .map(|player| std::mem::take(&mut player.hand))
+            .collect();
+        match direction {
+            Direction::Forward => hands.rotate_right(1),
+            Direction::Backward => hands.rotate_left(1),
+        }
+        for (player, hand) in self.players.iter_mut().chain(self.dummy.as_mut()).zip(hands) {
+            player.hand = hand;
+        }
     }
 
     #[cfg(test)]
-    pub fn hands(&mut self) -> Vec<AustraliaPlayer> {
+    pub fn hands(&mut self) -> Vec<Player<E>> {
         self.players.clone()
     }
     pub fn hands_singleton(&self) -> bool {
@@ -0,0 +1,319 @@
+//! Defines the final standings of a game and how ties are broken.
+//!
+//! The players are ordered by
+//!
+//! 1. their total score,
+//! 2. their throw and catch score in the final round, the tie break of the rule book,
+//! 3. their throw and catch score over every round.
+//!
+//! Players that are tied on all of these share the place, and the places that they take
+//! up are skipped, so two shared winners are followed by the third place.
+//!
+//! A match of several games ranks the players on the number of games that they won, a
+//! shared win counts for every player that shares it, and then on their points summed
+//! over every game. Ties in the match standings are shared in the same way.
+
+use std::cmp::Reverse;
+
+use serde::{Deserialize, Serialize};
+
+use super::edition::RoundScore;
+
+/// A players place in the final standings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Placement<S> {
+    /// The place, starting at 1, tied players have the same place
+    pub place: usize,
+    pub player: u8,
+    /// The score summed over every round
+    pub score: S,
+}
+
+impl<S> Placement<S> {
+    /// Returns true if another player in the standings has the same place
+    pub fn shared(&self, standings: &[Placement<S>]) -> bool {
+        standings
+            .iter()
+            .filter(|other| other.place == self.place)
+            .count()
+            > 1
+    }
+}
+
+/// A players place in the standings of a match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchPlacement {
+    /// The place, starting at 1, tied players have the same place
+    pub place: usize,
+    pub player: u8,
+    /// The number of games that the player won or shared the win of
+    pub wins: usize,
+    /// The total score summed over every game
+    pub points: usize,
+}
+
+/// The cumulative standings of a match of several games
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchStandings {
+    /// The number of games played so far
+    pub played: usize,
+    /// The number of games in the match
+    pub games: usize,
+    /// The match ends once the leader can not be caught up with
+    pub best_of: bool,
+    /// The players, the leaders first
+    pub standings: Vec<MatchPlacement>,
+}
+
+impl MatchStandings {
+    pub fn new(games: usize, best_of: bool) -> Self {
+        Self {
+            played: 0,
+            games,
+            best_of,
+            standings: Vec::new(),
+        }
+    }
+
+    /// Adds the final standings of a game to the match
+    pub fn record<S: RoundScore>(&mut self, result: &[Placement<S>]) {
+        self.played += 1;
+        for placement in result {
+            let idx = match self
+                .standings
+                .iter()
+                .position(|el| el.player == placement.player)
+            {
+                Some(idx) => idx,
+                None => {
+                    self.standings.push(MatchPlacement {
+                        place: 0,
+                        player: placement.player,
+                        wins: 0,
+                        points: 0,
+                    });
+                    self.standings.len() - 1
+                }
+            };
+            let entry = &mut self.standings[idx];
+            if placement.place == 1 {
+                entry.wins += 1;
+            }
+            entry.points += placement.score.total_score();
+        }
+        self.standings
+            .sort_by_key(|el| Reverse((el.wins, el.points)));
+        let mut previous: Option<(usize, usize, usize)> = None;
+        for (idx, entry) in self.standings.iter_mut().enumerate() {
+            entry.place = match previous {
+                Some((wins, points, place)) if (wins, points) == (entry.wins, entry.points) => {
+                    place
+                }
+                _ => idx + 1,
+            };
+            previous = Some((entry.wins, entry.points, entry.place));
+        }
+    }
+
+    /// Returns true if no more games are played
+    ///
+    /// Every game is played unless the match is a best of, which ends as soon as the
+    /// leader has more wins than any other player can reach in the remaining games.
+    pub fn over(&self) -> bool {
+        if self.played >= self.games {
+            return true;
+        }
+        let remaining = self.games - self.played;
+        match (self.best_of, self.standings.first()) {
+            (true, Some(leader)) => self
+                .standings
+                .iter()
+                .skip(1)
+                .all(|other| leader.wins > other.wins + remaining),
+            _ => false,
+        }
+    }
+
+    /// Returns the players that lead the match, the winners once the match is over
+    pub fn winners(&self) -> Vec<u8> {
+        self.standings
+            .iter()
+            .filter(|el| el.place == 1)
+            .map(|el| el.player)
+            .collect()
+    }
+}
+
+/// The values that the players are compared on, in order of precedence
+fn tie_breaks<S: RoundScore>(rounds: &[S], total: &S) -> [usize; 3] {
+    [
+        total.total_score(),
+        rounds.last().map_or(0, RoundScore::throw_catch),
+        total.throw_catch(),
+    ]
+}
+
+/// Ranks the players from their scores of every round, the winners first
+///
+/// Tied players are listed in the order that they are given.
+pub fn rank<S: RoundScore>(players: Vec<(u8, Vec<S>)>) -> Vec<Placement<S>> {
+    let mut standings: Vec<([usize; 3], u8, S)> = players
+        .into_iter()
+        .map(|(player, rounds)| {
+            let total: S = rounds.iter().cloned().sum();
+            (tie_breaks(&rounds, &total), player, total)
+        })
+        .collect();
+    standings.sort_by_key(|(key, ..)| Reverse(*key));
+
+    let mut placements: Vec<Placement<S>> = Vec::with_capacity(standings.len());
+    let mut previous = None;
+    for (idx, (key, player, score)) in standings.into_iter().enumerate() {
+        let place = match (previous, placements.last()) {
+            (Some(previous), Some(above)) if previous == key => above.place,
+            _ => idx + 1,
+        };
+        previous = Some(key);
+        placements.push(Placement {
+            place,
+            player,
+            score,
+        });
+    }
+    placements
+}
+
+#[cfg(test)]
+mod test {
+    use crate::australia::rules::scoring::AustraliaScoring;
+
+    use super::{rank, MatchStandings, Placement};
+
+    /// A round with only throw and catch and tourist site points
+    fn round(throw_catch: usize, tourist_sites: usize) -> AustraliaScoring {
+        AustraliaScoring::from_values(throw_catch, tourist_sites, 0, 0, 0, Vec::new())
+    }
+
+    fn places(standings: &[Placement<AustraliaScoring>]) -> Vec<(u8, usize)> {
+        standings
+            .iter()
+            .map(|placement| (placement.player, placement.place))
+            .collect()
+    }
+
+    #[test]
+    fn test_total_score() {
+        let standings = rank(vec![
+            (0, vec![round(1, 5), round(1, 5)]),
+            (1, vec![round(0, 9), round(0, 9)]),
+            (2, vec![round(9, 0), round(0, 0)]),
+        ]);
+        assert_eq!(places(&standings), vec![(1, 1), (0, 2), (2, 3)]);
+        assert_eq!(standings[0].score.total_score(), 18);
+        assert!(standings
+            .iter()
+            .all(|placement| !placement.shared(&standings)));
+    }
+
+    #[test]
+    fn test_final_throw_catch() {
+        // Same total and same throw and catch over the game, the final round decides
+        let standings = rank(vec![
+            (0, vec![round(5, 2), round(3, 2)]),
+            (1, vec![round(3, 2), round(5, 2)]),
+        ]);
+        assert_eq!(places(&standings), vec![(1, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn test_game_throw_catch() {
+        // Same total and same final round, the throw and catch of the whole game decides
+        let standings = rank(vec![
+            (0, vec![round(4, 2), round(2, 0)]),
+            (1, vec![round(6, 0), round(2, 0)]),
+        ]);
+        assert_eq!(places(&standings), vec![(1, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn test_shared() {
+        let standings = rank(vec![
+            (0, vec![round(1, 1)]),
+            (1, vec![round(3, 3)]),
+            (2, vec![round(3, 3)]),
+            (3, vec![round(1, 1)]),
+        ]);
+        assert_eq!(places(&standings), vec![(1, 1), (2, 1), (0, 3), (3, 3)]);
+        assert!(standings
+            .iter()
+            .all(|placement| placement.shared(&standings)));
+
+        let standings = rank(vec![
+            (0, vec![round(2, 0)]),
+            (1, vec![round(2, 0)]),
+            (2, vec![round(1, 0)]),
+        ]);
+        assert_eq!(places(&standings), vec![(0, 1), (1, 1), (2, 3)]);
+        assert!(!standings[2].shared(&standings));
+    }
+
+    #[test]
+    fn test_match_standings() {
+        let mut standings = MatchStandings::new(3, false);
+        standings.record(&rank(vec![
+            (0, vec![round(9, 0)]),
+            (1, vec![round(5, 0)]),
+            (2, vec![round(1, 0)]),
+        ]));
+        standings.record(&rank(vec![
+            (0, vec![round(1, 0)]),
+            (1, vec![round(6, 0)]),
+            (2, vec![round(3, 0)]),
+        ]));
+        let table: Vec<(u8, usize, usize, usize)> = standings
+            .standings
+            .iter()
+            .map(|el| (el.player, el.place, el.wins, el.points))
+            .collect();
+        // Both have a win, player 1 has more points
+        assert_eq!(table, vec![(1, 1, 1, 11), (0, 2, 1, 10), (2, 3, 0, 4)]);
+        assert_eq!(standings.winners(), vec![1]);
+        assert!(!standings.over());
+
+        // A shared win counts for players 0 and 2, player 0 leads with 2 wins and wins the match
+        standings.record(&rank(vec![
+            (0, vec![round(2, 0)]),
+            (1, vec![round(1, 0)]),
+            (2, vec![round(2, 0)]),
+        ]));
+        assert!(standings.over());
+        assert_eq!(standings.standings[0].wins, 2);
+        assert_eq!(standings.standings[0].player, 0);
+        assert_eq!(standings.winners(), vec![0]);
+    }
+
+    #[test]
+    fn test_best_of() {
+        let game = |winner: u8| {
+            rank(vec![
+                (0, vec![round(if winner == 0 { 2 } else { 1 }, 0)]),
+                (1, vec![round(if winner == 1 { 2 } else { 1 }, 0)]),
+            ])
+        };
+        let mut standings = MatchStandings::new(3, true);
+        standings.record(&game(0));
+        assert!(!standings.over());
+        standings.record(&game(0));
+        // Player 1 can at most win the last game
+        assert!(standings.over());
+        assert_eq!(standings.winners(), vec![0]);
+
+        let mut standings = MatchStandings::new(3, true);
+        standings.record(&game(0));
+        standings.record(&game(1));
+        assert!(!standings.over());
+        standings.record(&game(1));
+        assert!(standings.over());
+        assert_eq!(standings.winners(), vec![1]);
+    }
+}
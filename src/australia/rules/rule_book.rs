@@ -0,0 +1,576 @@
+//! Defines the categories that a round is scored in.
+//!
+//! Every category is a [`ScoringRule`], a [`RuleBook`] lists the rules that a game is scored
+//! with in the order that they are scored and displayed. The book of boomerang australia
+//! is [`RuleBook::australia`], house rules add, remove or replace categories on top of it
+//!
+//! ```ignore
+//! let house_rules = RuleBook::new()
+//!     .add(ThrowCatch)
+//!     .add(GrandTour { points: 5 });
+//! ```
+//!
+//! The [`HouseRule`]s in the `house_rules` of the config are applied by [`RuleBook::australia`].
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    cards::{
+        Animal, AustraliaCard, AustraliaDeck, AustralianActivity, AustralianAnimal,
+        AustralianRegion, Card,
+    },
+    config::{GameConfig, ACTIVITY_POINTS},
+    scoring::{
+        round_cards, AustraliaScoring, LedgerEntry, ACTIVITY, ANIMALS, COLLECTIONS, THROW_CATCH,
+        TOURIST_SITES,
+    },
+    AustraliaPlayer,
+};
+
+/// The name of the [`GrandTour`] bonus
+pub const GRAND_TOUR: &str = "Grand tour";
+/// Points of the [`GrandTour`] when it is picked as a [`HouseRule`]
+pub const GRAND_TOUR_POINTS: usize = 5;
+
+/// The house rules that a [`GameConfig`] can pick
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HouseRule {
+    /// Replaces the collections with [`UndoubledCollections`]
+    UndoubledCollections,
+    /// Adds the [`GrandTour`] bonus
+    GrandTour,
+}
+
+/// The round that is being scored for a single player
+pub struct Round<'a> {
+    pub player: &'a mut AustraliaPlayer,
+    /// The regions that no player has completed in an earlier round
+    pub unclaimed: &'a [AustralianRegion],
+    /// The activity that the player chose to score this round
+    pub activity: Option<AustralianActivity>,
+    /// The sites that the player had visited before this round
+    pub visited: Vec<char>,
+    /// Every card that the player drafted this round, the thrown card first
+    pub cards: Vec<AustraliaCard>,
+}
+
+/// A category of the score
+pub trait ScoringRule: Send + Sync {
+    /// The name that the points are listed under
+    fn category(&self) -> &'static str;
+    /// Returns the points of the round
+    ///
+    /// The rule explains the points in the ledger of `scoring`, and lists the regions that
+    /// it completed there.
+    fn score(&self, round: &mut Round<'_>, scoring: &mut AustraliaScoring) -> usize;
+}
+
+/// The rules that a round is scored with, in the order that they are scored
+#[derive(Default)]
+pub struct RuleBook {
+    rules: Vec<Box<dyn ScoringRule>>,
+}
+
+impl RuleBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The rules of boomerang australia, with the activity points and house rules of the config
+    pub fn australia(config: &GameConfig) -> Self {
+        let book = Self::new()
+            .add(ThrowCatch)
+            .add(TouristSites)
+            .add(Collections)
+            .add(Animals)
+            .add(Activity::new(config.activity_points.clone()));
+        config
+            .house_rules
+            .iter()
+            .fold(book, |book, house_rule| match house_rule {
+                HouseRule::UndoubledCollections => book.replace(UndoubledCollections),
+                HouseRule::GrandTour => book.replace(GrandTour {
+                    points: GRAND_TOUR_POINTS,
+                }),
+            })
+    }
+    /// Adds a rule after the rules that are already in the book
+    pub fn add(mut self, rule: impl ScoringRule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+    /// Removes the rule of a category
+    #[cfg(test)]
+    pub fn remove(mut self, category: &str) -> Self {
+        self.rules.retain(|rule| rule.category() != category);
+        self
+    }
+    /// Replaces the rule of the same category in place, or adds it if there is none
+    pub fn replace(mut self, rule: impl ScoringRule + 'static) -> Self {
+        match self
+            .rules
+            .iter()
+            .position(|old| old.category() == rule.category())
+        {
+            Some(idx) => self.rules[idx] = Box::new(rule),
+            None => self.rules.push(Box::new(rule)),
+        }
+        self
+    }
+    /// Returns the categories in the order that they are scored
+    #[cfg(test)]
+    pub fn categories(&self) -> Vec<&'static str> {
+        self.rules.iter().map(|rule| rule.category()).collect()
+    }
+    /// Scores the round of a player, every category is listed even if it gave no points
+    pub fn score(
+        &self,
+        player: &mut AustraliaPlayer,
+        unclaimed: &[AustralianRegion],
+        activity: Option<AustralianActivity>,
+    ) -> AustraliaScoring {
+        let cards = round_cards(player);
+        let mut round = Round {
+            visited: player.get_visited(),
+            player,
+            unclaimed,
+            activity,
+            cards,
+        };
+        let mut scoring = AustraliaScoring::new();
+        for rule in &self.rules {
+            let points = rule.score(&mut round, &mut scoring);
+            scoring.add(rule.category(), points);
+        }
+        scoring
+    }
+}
+
+/// The difference between the numbers of the thrown and the caught card
+pub struct ThrowCatch;
+
+impl ScoringRule for ThrowCatch {
+    fn category(&self) -> &'static str {
+        THROW_CATCH
+    }
+    fn score(&self, round: &mut Round<'_>, scoring: &mut AustraliaScoring) -> usize {
+        scoring.throw_catch_points(self.category(), round.player)
+    }
+}
+
+/// A point for every new site and 3 for every region that no one had completed before
+///
+/// The new sites are marked as visited by the player.
+pub struct TouristSites;
+
+impl ScoringRule for TouristSites {
+    fn category(&self) -> &'static str {
+        TOURIST_SITES
+    }
+    fn score(&self, round: &mut Round<'_>, scoring: &mut AustraliaScoring) -> usize {
+        scoring.tourist_site_points(self.category(), round.player, round.unclaimed)
+    }
+}
+
+/// The sum of the collections, doubled unless it is more than 7
+pub struct Collections;
+
+impl ScoringRule for Collections {
+    fn category(&self) -> &'static str {
+        COLLECTIONS
+    }
+    fn score(&self, round: &mut Round<'_>, scoring: &mut AustraliaScoring) -> usize {
+        scoring.collection_points(self.category(), &round.cards)
+    }
+}
+
+/// House rule, the sum of the collections is never doubled
+pub struct UndoubledCollections;
+
+impl ScoringRule for UndoubledCollections {
+    fn category(&self) -> &'static str {
+        COLLECTIONS
+    }
+    fn score(&self, round: &mut Round<'_>, scoring: &mut AustraliaScoring) -> usize {
+        scoring.collected_points(self.category(), &round.cards)
+    }
+}
+
+/// The points of the animal for every two cards with the same animal
+pub struct Animals;
+
+impl ScoringRule for Animals {
+    fn category(&self) -> &'static str {
+        ANIMALS
+    }
+    fn score(&self, round: &mut Round<'_>, scoring: &mut AustraliaScoring) -> usize {
+        // The card that is waiting for a second card with the same animal
+        let mut map = HashMap::<AustralianAnimal, AustraliaCard>::new();
+        let mut sum = 0;
+        for card in round.cards.iter() {
+            if let Some(animal) = card.animal() {
+                match map.remove(&animal) {
+                    Some(first) => {
+                        sum += animal.score();
+                        scoring.explain(
+                            self.category(),
+                            LedgerEntry::AnimalPair {
+                                animal: animal.into(),
+                                cards: (first, *card),
+                                points: animal.score(),
+                            },
+                        );
+                    }
+                    None => {
+                        let _ = map.insert(animal, *card);
+                    }
+                }
+            }
+        }
+        sum
+    }
+}
+
+/// The cards that match the activity that the player chose, if any
+///
+/// The points are looked up by the number of matching cards, more cards than the table
+/// lists get the last entry. The activity can not be chosen again by the player.
+pub struct Activity {
+    points: Vec<usize>,
+}
+
+impl Activity {
+    pub fn new(points: Vec<usize>) -> Self {
+        Self { points }
+    }
+    /// Returns the points for a number of matching cards
+    pub fn points(&self, cards: usize) -> usize {
+        self.points
+            .get(cards)
+            .or(self.points.last())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+impl Default for Activity {
+    fn default() -> Self {
+        Self::new(ACTIVITY_POINTS.to_vec())
+    }
+}
+
+impl ScoringRule for Activity {
+    fn category(&self) -> &'static str {
+        ACTIVITY
+    }
+    fn score(&self, round: &mut Round<'_>, scoring: &mut AustraliaScoring) -> usize {
+        scoring.activity_points(self.category(), round.player, round.activity, |cards| {
+            self.points(cards)
+        })
+    }
+}
+
+/// House rule, a bonus for the round where the player visits a site in every region
+pub struct GrandTour {
+    pub points: usize,
+}
+
+impl ScoringRule for GrandTour {
+    fn category(&self) -> &'static str {
+        GRAND_TOUR
+    }
+    fn score(&self, round: &mut Round<'_>, scoring: &mut AustraliaScoring) -> usize {
+        let toured = |visited: &[char]| {
+            let regions: Vec<AustralianRegion> = AustraliaDeck::default()
+                .cards()
+                .iter()
+                .filter(|card| visited.contains(&card.to_char()))
+                .map(|card| card.region())
+                .collect();
+            AustralianRegion::to_vec()
+                .iter()
+                .all(|region| regions.contains(region))
+        };
+        let mut visited = round.visited.clone();
+        visited.extend(round.cards.iter().map(|card| card.to_char()));
+        if toured(&round.visited) || !toured(&visited) {
+            return 0;
+        }
+        scoring.explain(
+            self.category(),
+            LedgerEntry::GrandTour {
+                points: self.points,
+            },
+        );
+        self.points
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::australia::rules::{
+        cards::{AustraliaCard, AustraliaDeck, AustralianActivity, AustralianRegion, Card},
+        config::GameConfig,
+        edition::RoundScore,
+        scoring::{AustraliaScoring, ACTIVITY, ANIMALS, COLLECTIONS, THROW_CATCH, TOURIST_SITES},
+        AustraliaPlayer,
+    };
+
+    use super::{
+        GrandTour, HouseRule, RuleBook, ThrowCatch, UndoubledCollections, GRAND_TOUR,
+        GRAND_TOUR_POINTS,
+    };
+
+    /// Deals the player a round, the first card is thrown and the last one is caught
+    fn deal(player: &mut AustraliaPlayer, cards: &[AustraliaCard]) {
+        player.new_round();
+        player.discard_pile.push(cards[0]);
+        player.show_pile.extend(&cards[1..cards.len() - 1]);
+        player.hand.push(cards[cards.len() - 1]);
+    }
+
+    /// Scores two rounds and checks them against a scorecard that was filled in by hand
+    #[test]
+    fn test_scorecard() {
+        let book = RuleBook::australia(&GameConfig::default());
+        let mut player = AustraliaPlayer::new(0);
+        let mut unclaimed = AustralianRegion::to_vec();
+
+        deal(
+            &mut player,
+            &[
+                AustraliaCard::TheBungleBungles,
+                AustraliaCard::ThePinnacles,
+                AustraliaCard::MargaretRiver,
+                AustraliaCard::KalbarriNationalPark,
+                AustraliaCard::Uluru,
+                AustraliaCard::TheWhitsundays,
+                AustraliaCard::BlueMountains,
+            ],
+        );
+        let first = book.score(
+            &mut player,
+            &unclaimed,
+            Some(AustralianActivity::IndigenousCulture),
+        );
+        // Western Australia (1) is thrown and New South Whales (5) is caught
+        assert_eq!(first.throw_catch(), 4);
+        // Seven new sites and Western Australia
+        assert_eq!(first.tourist_sites(), 7 + 3);
+        // Leaves, Shells and Wildflowers sum to 6 which is doubled
+        assert_eq!(first.collections(), 12);
+        // Three kangaroos make one pair
+        assert_eq!(first.animals(), 3);
+        // Four cards with indigenous culture
+        assert_eq!(first.activity(), 7);
+        assert_eq!(first.total_score(), 36);
+        assert_eq!(
+            first.completed_regions(),
+            vec![AustralianRegion::WesternAustralia]
+        );
+        unclaimed.retain(|region| *region != AustralianRegion::WesternAustralia);
+
+        deal(
+            &mut player,
+            &[
+                AustraliaCard::Richmond,
+                AustraliaCard::Melbourne,
+                AustraliaCard::TheMCG,
+                AustraliaCard::TwelveApostles,
+                AustraliaCard::RoyalExhibitionBuilding,
+                AustraliaCard::KakaduNationalPark,
+                AustraliaCard::BondiBeach,
+            ],
+        );
+        let second = book.score(&mut player, &unclaimed, Some(AustralianActivity::Swimming));
+        // Tasmania (7) is thrown and New South Whales (5) is caught
+        assert_eq!(second.throw_catch(), 2);
+        // Seven new sites and Victoria
+        assert_eq!(second.tourist_sites(), 7 + 3);
+        // Two leaves and shells sum to 5 which is doubled
+        assert_eq!(second.collections(), 10);
+        // Two wombats make a pair
+        assert_eq!(second.animals(), 5);
+        // Three cards with swimming
+        assert_eq!(second.activity(), 4);
+        assert_eq!(second.total_score(), 31);
+
+        let game: AustraliaScoring = vec![first, second].into_iter().sum();
+        assert_eq!(
+            game.parts(),
+            vec![
+                ("Throw Catch", 6),
+                ("Tourist sites", 20),
+                ("Collections", 22),
+                ("Animals", 8),
+                ("Activity", 11),
+            ]
+        );
+        assert_eq!(game.total_score(), 67);
+        assert_eq!(
+            game.completed_regions(),
+            vec![
+                AustralianRegion::WesternAustralia,
+                AustralianRegion::Victoria
+            ]
+        );
+        // Both activities were used up
+        assert!(!player
+            .un_scored_activity()
+            .contains(&AustralianActivity::IndigenousCulture));
+        assert!(!player
+            .un_scored_activity()
+            .contains(&AustralianActivity::Swimming));
+    }
+
+    #[test]
+    fn test_activity_points() {
+        // Seven cards with indigenous culture, more than the printed table lists
+        let mut player = AustraliaPlayer::new(0);
+        deal(
+            &mut player,
+            &[
+                AustraliaCard::TheBungleBungles,
+                AustraliaCard::Uluru,
+                AustraliaCard::TheWhitsundays,
+                AustraliaCard::BlueMountains,
+                AustraliaCard::TheMCG,
+                AustraliaCard::PortArthur,
+                AustraliaCard::Uluru,
+            ],
+        );
+        let activity = Some(AustralianActivity::IndigenousCulture);
+        let config = GameConfig::default();
+        let score = RuleBook::australia(&config).score(&mut player.clone(), &[], activity);
+        assert_eq!(score.points(ACTIVITY), 15);
+
+        let config = GameConfig {
+            activity_points: vec![0, 1, 3, 6, 10, 15, 21, 28],
+            ..GameConfig::default()
+        };
+        let score = RuleBook::australia(&config).score(&mut player.clone(), &[], activity);
+        assert_eq!(score.points(ACTIVITY), 28);
+
+        // Without a table activities give no points
+        let config = GameConfig {
+            activity_points: Vec::new(),
+            ..GameConfig::default()
+        };
+        let score = RuleBook::australia(&config).score(&mut player, &[], activity);
+        assert_eq!(score.points(ACTIVITY), 0);
+        assert_eq!(score.ledger_of(ACTIVITY).len(), 1);
+    }
+
+    #[test]
+    fn test_rule_book() {
+        let book = RuleBook::australia(&GameConfig::default());
+        assert_eq!(
+            book.categories(),
+            vec![
+                "Throw Catch",
+                "Tourist sites",
+                "Collections",
+                "Animals",
+                "Activity"
+            ]
+        );
+        let book = book
+            .remove(THROW_CATCH)
+            .replace(UndoubledCollections)
+            .add(GrandTour { points: 5 })
+            .replace(ThrowCatch);
+        assert_eq!(
+            book.categories(),
+            vec![
+                "Tourist sites",
+                "Collections",
+                "Animals",
+                "Activity",
+                "Grand tour",
+                "Throw Catch"
+            ]
+        );
+
+        // Every category is listed, in the order of the book
+        let mut player = AustraliaPlayer::new(0);
+        let score = book.score(&mut player, &[], None);
+        let categories: Vec<&str> = score.parts().iter().map(|(name, _)| *name).collect();
+        assert_eq!(categories, book.categories());
+        assert_eq!(score.total_score(), 0);
+    }
+
+    #[test]
+    fn test_house_rules() {
+        let mut player = AustraliaPlayer::new(0);
+        player.hand.push(AustraliaCard::TheBungleBungles); // 1 pt
+        player.hand.push(AustraliaCard::KalbarriNationalPark); // 2 pt
+        player.hand.push(AustraliaCard::MargaretRiver); // 3 pt
+
+        let score =
+            RuleBook::australia(&GameConfig::default()).score(&mut player.clone(), &[], None);
+        assert_eq!(score.points(COLLECTIONS), 12);
+        let book = RuleBook::australia(&GameConfig::default()).replace(UndoubledCollections);
+        let score = book.score(&mut player.clone(), &[], None);
+        assert_eq!(score.points(COLLECTIONS), 6);
+
+        // Picked by the config, a house rule that is listed twice is applied once
+        let config = GameConfig {
+            house_rules: vec![
+                HouseRule::GrandTour,
+                HouseRule::UndoubledCollections,
+                HouseRule::GrandTour,
+            ],
+            ..Default::default()
+        };
+        let book = RuleBook::australia(&config);
+        assert_eq!(
+            book.categories(),
+            vec![
+                THROW_CATCH,
+                TOURIST_SITES,
+                COLLECTIONS,
+                ANIMALS,
+                ACTIVITY,
+                GRAND_TOUR
+            ]
+        );
+        assert_eq!(book.score(&mut player, &[], None).points(COLLECTIONS), 6);
+        assert_eq!(
+            GameConfig::parse(r#"house_rules = ["GrandTour"]"#)
+                .unwrap()
+                .house_rules,
+            vec![HouseRule::GrandTour]
+        );
+    }
+
+    #[test]
+    fn test_grand_tour() {
+        let book = RuleBook::new().add(GrandTour {
+            points: GRAND_TOUR_POINTS,
+        });
+        // One card of every region but Tasmania
+        let mut player = AustraliaPlayer::new(0);
+        for region in AustralianRegion::to_vec() {
+            if region == AustralianRegion::Tasmania {
+                continue;
+            }
+            let card = AustraliaDeck::default()
+                .cards()
+                .into_iter()
+                .find(|card| card.region() == region)
+                .unwrap();
+            player.visit(card.to_char());
+        }
+        assert_eq!(book.score(&mut player.clone(), &[], None).total_score(), 0);
+
+        player.hand.push(AustraliaCard::MountWellington);
+        let score = book.score(&mut player, &[], None);
+        assert_eq!(score.points(GRAND_TOUR), 5);
+
+        // The bonus is only given once
+        player.new_round();
+        player.hand.push(AustraliaCard::Uluru);
+        assert_eq!(book.score(&mut player, &[], None).total_score(), 0);
+    }
+}
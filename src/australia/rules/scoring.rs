@@ -1,37 +1,178 @@
 //! Defines methods and types to help with scoring a game.
+//!
+//! Every edition is scored into a [`Scoring`], the rules that the editions share are scored
+//! here, the edition specific ones live with the edition.
 
 use serde::{Deserialize, Serialize};
+use tracing::warn;
+use tui::ui::Card;
 
 use super::{
-    cards::{Animal, AustralianActivity, AustralianAnimal, AustralianRegion, Card, Collection},
-    AustraliaPlayer,
+    edition::{Edition, RoundScore, Site},
+    AustraliaEdition, Player,
 };
 
+/// A line in the [`Scoring`] ledger, explains where a part of the score came from
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct Scoring {
-    throw_catch: usize,
-    tourist_sites: usize,
-    collections: usize,
-    animals: usize,
-    activity: usize,
-    completed_regions: Vec<AustralianRegion>,
+#[serde(bound = "")]
+pub enum LedgerEntry<E: Edition> {
+    /// The first card that was thrown and the last card that was caught
+    ThrowCatch {
+        throw: E::Card,
+        catch: E::Card,
+        points: usize,
+    },
+    /// A card with a site that had not been visited before
+    Site { card: E::Card },
+    /// A region that was completed before any other player completed it
+    Region { region: E::Region, points: usize },
+    /// A card that belongs to a collection
+    Collection { card: E::Card, points: usize },
+    /// The sum of the collections, it is doubled unless it is more than 7
+    Collections {
+        sum: usize,
+        doubled: bool,
+        points: usize,
+    },
+    /// Two cards with the same animal
+    AnimalPair {
+        animal: String,
+        cards: (E::Card, E::Card),
+        points: usize,
+    },
+    /// The distinct means of transport that were used
+    Transport { transports: Vec<String>, points: usize },
+    /// The cards that match the activity that was scored
+    Activity {
+        activity: E::Activity,
+        cards: Vec<E::Card>,
+        points: usize,
+    },
+    /// A site in every region has been visited
+    GrandTour { points: usize },
 }
-impl std::ops::AddAssign<Scoring> for Scoring {
-    fn add_assign(&mut self, rhs: Scoring) {
-        self.throw_catch += rhs.throw_catch;
-        self.tourist_sites += rhs.tourist_sites;
-        self.collections += rhs.collections;
-        self.activity += rhs.activity;
-        self.activity += rhs.activity;
+
+impl<E: Edition> std::fmt::Display for LedgerEntry<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ThrowCatch {
+                throw,
+                catch,
+                points,
+            } => write!(
+                f,
+                "Threw {} ({}), caught {} ({}) : {}",
+                throw.get_name(),
+                throw.number(),
+                catch.get_name(),
+                catch.number(),
+                points
+            ),
+            Self::Site { card } => {
+                write!(f, "New site {} ({}) : 1", card.get_name(), card.site())
+            }
+            Self::Region { region, points } => write!(
+                f,
+                "Completed {} : {}",
+                Into::<String>::into(*region),
+                points
+            ),
+            Self::Collection { card, points } => {
+                write!(f, "Collected {} : {}", card.get_name(), points)
+            }
+            Self::Collections {
+                sum,
+                doubled,
+                points,
+            } => match doubled {
+                true => write!(f, "Collections {} doubled : {}", sum, points),
+                false => write!(f, "Collections {} over 7, not doubled : {}", sum, points),
+            },
+            Self::AnimalPair {
+                animal,
+                cards,
+                points,
+            } => write!(
+                f,
+                "Pair of {} from {} and {} : {}",
+                animal,
+                cards.0.get_name(),
+                cards.1.get_name(),
+                points
+            ),
+            Self::Transport { transports, points } => {
+                write!(f, "Travelled by {} : {}", transports.join(", "), points)
+            }
+            Self::Activity {
+                activity,
+                cards,
+                points,
+            } => write!(
+                f,
+                "{} with {} cards : {}",
+                Into::<String>::into(*activity),
+                cards.len(),
+                points
+            ),
+            Self::GrandTour { points } => write!(f, "Visited every region : {}", points),
+        }
+    }
+}
+
+/// The name of the points for the difference between the first and the last card
+pub const THROW_CATCH: &str = "Throw Catch";
+/// The name of the points for visited sites and completed regions
+pub const TOURIST_SITES: &str = "Tourist sites";
+pub const COLLECTIONS: &str = "Collections";
+pub const ANIMALS: &str = "Animals";
+pub const ACTIVITY: &str = "Activity";
+
+/// The points of one category of a [`Scoring`] and the entries that explain them
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct Category<E: Edition> {
+    name: String,
+    points: usize,
+    #[serde(default)]
+    ledger: Vec<LedgerEntry<E>>,
+}
+
+/// The score of a round, the points of every category that the round was scored in
+///
+/// Points are added with checked arithmetic, a sum that does not fit stays at
+/// [`usize::MAX`] instead of wrapping around.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct Scoring<E: Edition> {
+    /// The categories in the order that they were scored
+    categories: Vec<Category<E>>,
+    completed_regions: Vec<E::Region>,
+}
+pub type AustraliaScoring = Scoring<AustraliaEdition>;
+
+impl<E: Edition> Default for Scoring<E> {
+    fn default() -> Self {
+        Self {
+            categories: Vec::new(),
+            completed_regions: Vec::new(),
+        }
+    }
+}
+impl<E: Edition> std::ops::AddAssign<Scoring<E>> for Scoring<E> {
+    fn add_assign(&mut self, rhs: Scoring<E>) {
+        for category in rhs.categories {
+            self.add(&category.name, category.points);
+            self.category(&category.name).ledger.extend(category.ledger);
+        }
         for region in rhs.completed_regions {
-            if !self.completed_regions().contains(&region) {
+            if !self.completed_regions.contains(&region) {
                 self.completed_regions.push(region);
             }
         }
     }
 }
-impl std::iter::Sum for Scoring {
-    fn sum<I: Iterator<Item = Scoring>>(iter: I) -> Self {
+impl<E: Edition> std::iter::Sum for Scoring<E> {
+    fn sum<I: Iterator<Item = Scoring<E>>>(iter: I) -> Self {
         let mut total = Scoring::new();
         for el in iter {
             total += el;
@@ -39,187 +180,363 @@ impl std::iter::Sum for Scoring {
         total
     }
 }
-impl Scoring {
+impl<E: Edition> Scoring<E> {
     pub fn new() -> Self {
-        Self {
-            throw_catch: 0,
-            tourist_sites: 0,
-            collections: 0,
-            animals: 0,
-            activity: 0,
-            completed_regions: Vec::new(),
-        }
+        Self::default()
     }
+    /// Creates the score of a round that was scored with the rules of boomerang australia
+    #[cfg(test)]
     pub fn from_values(
         throw_catch: usize,
         tourist_sites: usize,
         collections: usize,
         animals: usize,
         activity: usize,
-        completed_regions: Vec<AustralianRegion>,
+        completed_regions: Vec<E::Region>,
     ) -> Self {
-        Self {
-            throw_catch,
-            tourist_sites,
-            collections,
-            animals,
-            activity,
-            completed_regions,
-        }
+        let mut scoring = Self::new();
+        scoring.add(THROW_CATCH, throw_catch);
+        scoring.add(TOURIST_SITES, tourist_sites);
+        scoring.add(COLLECTIONS, collections);
+        scoring.add(ANIMALS, animals);
+        scoring.add(ACTIVITY, activity);
+        scoring.completed_regions = completed_regions;
+        scoring
+    }
+    /// Returns the category, a category that was not scored yet is added last
+    fn category(&mut self, name: &str) -> &mut Category<E> {
+        let idx = match self
+            .categories
+            .iter()
+            .position(|category| category.name == name)
+        {
+            Some(idx) => idx,
+            None => {
+                self.categories.push(Category {
+                    name: name.to_owned(),
+                    points: 0,
+                    ledger: Vec::new(),
+                });
+                self.categories.len() - 1
+            }
+        };
+        &mut self.categories[idx]
+    }
+    /// Adds points to a category
+    pub fn add(&mut self, category: &str, points: usize) {
+        let category = self.category(category);
+        category.points = match category.points.checked_add(points) {
+            Some(sum) => sum,
+            None => {
+                warn!(category = category.name, points, "Score overflowed");
+                usize::MAX
+            }
+        };
+    }
+    pub fn complete(&mut self, region: E::Region) {
+        self.completed_regions.push(region);
+    }
+    /// Explains a part of the points of a category
+    pub fn explain(&mut self, category: &str, entry: LedgerEntry<E>) {
+        self.category(category).ledger.push(entry);
     }
 }
 // Getters
-impl Scoring {
+impl<E: Edition> Scoring<E> {
+    /// Returns the points of a category, a category that was not scored has no points
+    pub fn points(&self, category: &str) -> usize {
+        self.categories
+            .iter()
+            .find(|el| el.name == category)
+            .map(|el| el.points)
+            .unwrap_or_default()
+    }
     pub fn throw_catch(&self) -> usize {
-        self.throw_catch
+        self.points(THROW_CATCH)
     }
+    #[cfg(test)]
     pub fn tourist_sites(&self) -> usize {
-        self.tourist_sites
+        self.points(TOURIST_SITES)
     }
+    #[cfg(test)]
     pub fn collections(&self) -> usize {
-        self.collections
+        self.points(COLLECTIONS)
     }
+    #[cfg(test)]
     pub fn animals(&self) -> usize {
-        self.animals
+        self.points(ANIMALS)
     }
+    #[cfg(test)]
     pub fn activity(&self) -> usize {
-        self.activity
+        self.points(ACTIVITY)
     }
+    /// Returns the sum of every category, it stays at [`usize::MAX`] if it does not fit
     pub fn total_score(&self) -> usize {
-        self.activity + self.animals + self.collections + self.throw_catch + self.tourist_sites
+        self.categories
+            .iter()
+            .try_fold(0usize, |total, category| total.checked_add(category.points))
+            .unwrap_or(usize::MAX)
     }
-    pub fn completed_regions(&self) -> Vec<AustralianRegion> {
+    pub fn completed_regions(&self) -> Vec<E::Region> {
         self.completed_regions.clone()
     }
-}
-
-// Builder pattern for scoring
-impl Scoring {
-    pub fn score_throw_catch(mut self, player: &AustraliaPlayer) -> Self {
-        let throw = player.get_discard()[0].number();
-        let catch = player.get_hand()[0].number();
-        self.throw_catch = {
-            if throw > catch {
-                throw - catch
-            } else {
-                catch - throw
-            }
-        };
-        self
+    /// Returns the entries that explain the points of a category
+    #[cfg(test)]
+    pub fn ledger_of(&self, category: &str) -> Vec<LedgerEntry<E>> {
+        self.categories
+            .iter()
+            .find(|el| el.name == category)
+            .map(|el| el.ledger.clone())
+            .unwrap_or_default()
     }
-    pub fn score_collections(mut self, player: &AustraliaPlayer) -> Self {
-        let mut cards = player.get_discard();
-        cards.extend(player.get_show());
-        cards.extend(player.get_hand());
-        let mut sum = 0;
-        for card in cards {
-            sum += match card.collection() {
-                Some(collection) => collection.score(),
-                _ => 0,
-            };
-        }
-        self.collections = match sum > 7 {
-            false => sum * 2,
-            _ => sum,
-        };
-        self
+    /// Returns the entries of every category, in the order of the categories
+    pub fn ledger(&self) -> Vec<LedgerEntry<E>> {
+        self.categories
+            .iter()
+            .flat_map(|category| category.ledger.iter().cloned())
+            .collect()
     }
+}
 
-    pub fn score_animals(mut self, player: &AustraliaPlayer) -> Self {
-        let mut map = std::collections::HashMap::<AustralianAnimal, bool>::new();
-        let mut cards = player.get_discard();
-        cards.extend(player.get_show());
-        cards.extend(player.get_hand());
-        let mut sum = 0;
+/// Returns all of the cards that the player has drafted this round, the thrown card first
+pub fn round_cards<E: Edition>(player: &Player<E>) -> Vec<E::Card> {
+    let mut cards = player.get_discard();
+    cards.extend(player.get_show());
+    cards.extend(player.get_hand());
+    cards
+}
 
-        for card in cards {
-            let animal = card.animal();
-            if let Some(animal) = animal {
-                match map.get(&animal) {
-                    Some(value) => {
-                        if *value {
-                            sum += animal.score();
-                        }
-
-                        map.insert(animal, !value);
-                    }
-                    None => {
-                        let _ = map.insert(animal, true);
-                    }
-                }
-            }
-        }
-        self.animals = sum;
-        self
+// The rules that every edition scores the same way, the points are explained in the ledger
+// of `category` but not added to it
+impl<E: Edition> Scoring<E> {
+    /// Returns the difference between the numbers of the thrown and the caught card
+    pub fn throw_catch_points(&mut self, category: &str, player: &Player<E>) -> usize {
+        let (throw, catch) = match (player.get_discard().first(), player.get_hand().first()) {
+            (Some(throw), Some(catch)) => (*throw, *catch),
+            // The round is not over, there is nothing to score yet
+            _ => return 0,
+        };
+        let points = throw.number().abs_diff(catch.number());
+        self.explain(
+            category,
+            LedgerEntry::ThrowCatch {
+                throw,
+                catch,
+                points,
+            },
+        );
+        points
     }
-    pub fn score_regions(
-        mut self,
-        player: &mut AustraliaPlayer,
-        unclaimed_region: &Vec<AustralianRegion>,
-    ) -> Self {
-        let mut cards = player.get_discard();
-        cards.extend(player.get_show());
-        cards.extend(player.get_hand());
+    /// Returns a point for every new site and 3 for every region in `unclaimed` that is
+    /// completed, the completed regions are listed and the new sites are marked as visited
+    /// by the player
+    pub fn tourist_site_points(
+        &mut self,
+        category: &str,
+        player: &mut Player<E>,
+        unclaimed: &[E::Region],
+    ) -> usize {
         let mut visited = player.get_visited();
         let mut total = 0;
-        for card in cards {
-            // Insert if not exists
-            if !visited.contains(&card.to_char()) {
-                visited.push(card.to_char());
-                player.visit(card.to_char());
+        for card in round_cards(player) {
+            if !visited.contains(&card.site()) {
+                visited.push(card.site());
+                player.visit(card.site());
                 //  A new site has been visited
                 total += 1;
+                self.explain(category, LedgerEntry::Site { card });
             }
         }
-        let mut completed = Vec::new();
-        for region in unclaimed_region {
-            if region.completed(&visited) {
-                completed.push(region.clone());
+        for region in unclaimed {
+            if E::completed(region, &visited) {
+                total += 3;
+                self.complete(*region);
+                self.explain(
+                    category,
+                    LedgerEntry::Region {
+                        region: *region,
+                        points: 3,
+                    },
+                );
             }
         }
-        total += completed.len() * 3;
-        self.completed_regions.extend(completed);
-        self.tourist_sites += total;
-        self
+        total
     }
-    pub fn score_activity(
-        mut self,
-        player: &mut AustraliaPlayer,
-        activity: Option<AustralianActivity>,
-    ) -> Self {
-        if let None = activity {
-            return self;
-        }
-        let mut cards = player.get_discard();
-        cards.extend(player.get_show());
-        cards.extend(player.get_hand());
-        let mut total = 0;
-        if let Some(activity) = activity {
-            let mut target_idx = None;
-            for (idx, el) in player.un_scored_activity.iter().enumerate() {
-                if *el == activity {
-                    target_idx = Some(idx);
-                    break;
-                }
-            }
-            if let Some(idx) = target_idx {
-                player.un_scored_activity.remove(idx);
-            }
-        }
+    /// Returns the sum of the collections on the cards, every card is explained
+    pub fn collected_points(&mut self, category: &str, cards: &[E::Card]) -> usize {
+        let mut sum = 0;
         for card in cards {
-            if card.activity() == activity {
-                total += 1;
+            if let Some(points) = E::collection(card) {
+                sum += points;
+                self.explain(
+                    category,
+                    LedgerEntry::Collection {
+                        card: *card,
+                        points,
+                    },
+                );
             }
         }
-        self.activity += match total {
-            0 | 1 => 0,
-            2 => 2,
-            3 => 4,
-            4 => 7,
-            5 => 10,
-            6 => 15,
-            _ => unreachable!(),
+        sum
+    }
+    /// Returns the sum of the collections on the cards, doubled unless it is more than 7
+    pub fn collection_points(&mut self, category: &str, cards: &[E::Card]) -> usize {
+        let sum = self.collected_points(category, cards);
+        let doubled = sum <= 7;
+        let points = match doubled {
+            true => sum * 2,
+            false => sum,
+        };
+        if sum > 0 {
+            self.explain(
+                category,
+                LedgerEntry::Collections {
+                    sum,
+                    doubled,
+                    points,
+                },
+            );
+        }
+        points
+    }
+    /// Returns the points for the cards that match the activity that the player chose, if
+    /// any, looked up by the number of cards. The activity can not be chosen again.
+    pub fn activity_points(
+        &mut self,
+        category: &str,
+        player: &mut Player<E>,
+        activity: Option<E::Activity>,
+        points: impl Fn(usize) -> usize,
+    ) -> usize {
+        let activity = match activity {
+            Some(activity) => activity,
+            None => return 0,
         };
+        player.take_activity(activity);
+        let matching: Vec<E::Card> = round_cards(player)
+            .into_iter()
+            .filter(|card| E::activity(card) == Some(activity))
+            .collect();
+        let points = points(matching.len());
+        self.explain(
+            category,
+            LedgerEntry::Activity {
+                activity,
+                cards: matching,
+                points,
+            },
+        );
+        points
+    }
+}
+
+// Builder pattern for scoring, every step lists its category even if it gave no points
+impl<E: Edition> Scoring<E> {
+    pub fn score_throw_catch(mut self, player: &Player<E>) -> Self {
+        let points = self.throw_catch_points(THROW_CATCH, player);
+        self.add(THROW_CATCH, points);
+        self
+    }
+    pub fn score_regions(mut self, player: &mut Player<E>, unclaimed: &[E::Region]) -> Self {
+        let points = self.tourist_site_points(TOURIST_SITES, player, unclaimed);
+        self.add(TOURIST_SITES, points);
         self
     }
+    pub fn score_collections(mut self, player: &Player<E>) -> Self {
+        let points = self.collection_points(COLLECTIONS, &round_cards(player));
+        self.add(COLLECTIONS, points);
+        self
+    }
+}
+
+impl<E: Edition> RoundScore for Scoring<E> {
+    type Region = E::Region;
+    fn throw_catch(&self) -> usize {
+        Scoring::throw_catch(self)
+    }
+    fn parts(&self) -> Vec<(&str, usize)> {
+        self.categories
+            .iter()
+            .map(|category| (category.name.as_str(), category.points))
+            .collect()
+    }
+    fn completed_regions(&self) -> Vec<E::Region> {
+        Scoring::completed_regions(self)
+    }
+    fn ledger(&self) -> Vec<String> {
+        Scoring::ledger(self)
+            .iter()
+            .map(LedgerEntry::to_string)
+            .collect()
+    }
+    fn total_score(&self) -> usize {
+        Scoring::total_score(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::australia::rules::{cards::AustralianRegion, edition::RoundScore};
+
+    use super::{AustraliaScoring, LedgerEntry, ACTIVITY, ANIMALS, THROW_CATCH};
+
+    #[test]
+    fn test_sum() {
+        let first = AustraliaScoring::from_values(1, 2, 3, 4, 5, vec![AustralianRegion::Victoria]);
+        let second = AustraliaScoring::from_values(
+            10,
+            20,
+            30,
+            40,
+            50,
+            vec![AustralianRegion::Victoria, AustralianRegion::Tasmania],
+        );
+        let total: AustraliaScoring = vec![first, second].into_iter().sum();
+        assert_eq!(total.throw_catch(), 11);
+        assert_eq!(total.tourist_sites(), 22);
+        assert_eq!(total.collections(), 33);
+        assert_eq!(total.animals(), 44);
+        assert_eq!(total.activity(), 55);
+        assert_eq!(total.total_score(), 165);
+        assert_eq!(
+            total.completed_regions(),
+            vec![AustralianRegion::Victoria, AustralianRegion::Tasmania]
+        );
+    }
+
+    #[test]
+    fn test_categories() {
+        let mut first = AustraliaScoring::new();
+        first.add(ANIMALS, 3);
+        first.explain(ACTIVITY, LedgerEntry::GrandTour { points: 0 });
+        let mut second = AustraliaScoring::new();
+        second.add("Bonus", 2);
+        second.add(ANIMALS, 4);
+
+        first += second;
+        // The categories keep the order that they were first scored in
+        assert_eq!(
+            first.parts(),
+            vec![("Animals", 7), ("Activity", 0), ("Bonus", 2)]
+        );
+        assert_eq!(first.ledger_of(ACTIVITY).len(), 1);
+        assert!(first.ledger_of(ANIMALS).is_empty());
+        assert_eq!(first.points("Unknown"), 0);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut scoring = AustraliaScoring::new();
+        scoring.add(THROW_CATCH, usize::MAX - 1);
+        scoring.add(THROW_CATCH, 2);
+        assert_eq!(scoring.throw_catch(), usize::MAX);
+
+        let mut scoring = AustraliaScoring::new();
+        scoring.add(THROW_CATCH, usize::MAX);
+        scoring.add(ANIMALS, 1);
+        assert_eq!(scoring.total_score(), usize::MAX);
+        let total: AustraliaScoring = vec![scoring.clone(), scoring].into_iter().sum();
+        assert_eq!(total.animals(), 2);
+        assert_eq!(total.total_score(), usize::MAX);
+    }
 }
@@ -6,10 +6,11 @@
 extern crate server;
 use std::marker::PhantomData;
 
-use super::{cards::AustralianActivity, Event, meta::GameMetaData};
+use super::{config::GameConfig, edition::Edition, Event, meta::GameMetaData};
 use server::engine::rules::{Action, Error, New, Received};
 
 pub mod dealing;
+pub mod diagram;
 pub mod discard;
 pub mod game_end;
 pub mod pass;
@@ -18,52 +19,104 @@ pub mod show;
 pub mod syncing;
 pub mod waiting;
 
-pub trait GameState: Send + std::fmt::Debug {
+#[cfg(test)]
+mod properties;
+
+pub trait GameState<E: Edition>: Send + std::fmt::Debug {
+    /// The name of the state, used to tag the logs
+    fn name(&self) -> &'static str;
     fn get_next_action(
         &mut self,
         players: &Vec<usize>,
     ) -> (
         tokio::time::Duration,
-        Vec<Action<New, Event>>,
-        Option<Box<dyn GameState>>,
+        Vec<Action<New, Event<E>>>,
+        Option<Box<dyn GameState<E>>>,
     );
     fn register_message(
         &mut self,
-        action: &Action<New, Event>,
-    ) -> Result<Option<Box<dyn GameState>>, Error>;
+        action: &Action<New, Event<E>>,
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error>;
     fn register_response(
         &mut self,
-        action: (Event, &Action<Received, Event>),
-    ) -> Result<Option<Box<dyn GameState>>, Error>;
-    fn metadata(&mut self) -> Option<&mut GameMetaData>;
+        action: (Event<E>, &Action<Received, Event<E>>),
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error>;
+    fn metadata(&mut self) -> Option<&mut GameMetaData<E>>;
+    /// Returns true while new players can join the game
+    fn accepts_players(&self) -> bool {
+        false
+    }
+    /// Returns true once the game is over
+    fn finished(&self) -> bool {
+        false
+    }
 }
 
-pub trait AsMetaData: GameState {
-    fn metadata(&mut self) -> &mut GameMetaData;
+pub trait AsMetaData<E: Edition>: GameState<E> {
+    fn metadata(&mut self) -> &mut GameMetaData<E>;
+}
+
+/// A state as it is drawn in the [`diagram`] of the state machine
+#[derive(Debug, Clone, Copy)]
+pub struct StateNode {
+    pub name: &'static str,
+    type_name: fn() -> &'static str,
+    next: fn() -> Vec<StateNode>,
+}
+
+impl StateNode {
+    pub fn of<S: Transitions>() -> Self {
+        Self {
+            name: S::NAME,
+            type_name: std::any::type_name::<S>,
+            next: S::transitions,
+        }
+    }
+    /// Returns the full type of the state, it tells apart states that share a name
+    pub fn type_name(&self) -> &'static str {
+        (self.type_name)()
+    }
+    /// Returns the states that can follow this one
+    pub fn next(&self) -> Vec<StateNode> {
+        (self.next)()
+    }
+}
+
+/// Declares the states that a state can be followed by
+///
+/// A state that is generic over the state that follows it, such as [`Syncing`], is drawn
+/// as one state with an edge to every state that it is followed by.
+pub trait Transitions {
+    /// The name of the state, [`GameState::name`] returns the same name
+    const NAME: &'static str;
+    fn transitions() -> Vec<StateNode>;
 }
 #[derive(Debug)]
-pub struct DealingCards {
-    state: GameMetaData,
+pub struct DealingCards<E: Edition> {
+    state: GameMetaData<E>,
     pending_actions: Vec<u8>,
     #[allow(dead_code)]
     validated: Vec<usize>,
 }
 #[derive(Debug)]
-pub struct WaitingForPlayers<Next: AsMetaData + Send> {
+pub struct WaitingForPlayers<E: Edition, Next: AsMetaData<E> + Send> {
     ready: Vec<u8>,
     pending_ready: Vec<u8>,
     next_state: Option<Box<Next>>,
+    /// Configuration for the game that starts once everyone is ready
+    config: GameConfig,
+    edition: PhantomData<E>,
 }
 
 #[derive(Debug)]
-pub struct DiscardCard {
-    state: GameMetaData,
+pub struct DiscardCard<E: Edition> {
+    state: GameMetaData<E>,
     pending: Vec<u8>,
     requested: bool,
 }
 #[derive(Debug)]
-pub struct PassHand<Next: AsMetaData + Send + Sync + From<GameMetaData>> {
-    state: GameMetaData,
+pub struct PassHand<E: Edition, Next: AsMetaData<E> + Send + Sync + From<GameMetaData<E>>> {
+    state: GameMetaData<E>,
     pending: Vec<u8>,
     requested: bool,
     direction: pass::Direction,
@@ -71,39 +124,40 @@ pub struct PassHand<Next: AsMetaData + Send + Sync + From<GameMetaData>> {
 }
 
 #[derive(Debug)]
-pub struct ShowCard {
-    state: GameMetaData,
+pub struct ShowCard<E: Edition> {
+    state: GameMetaData<E>,
     pending: Vec<u8>,
     requested: bool,
 }
 
+/// Asks every player which activity they want to score before scoring the round
 #[derive(Debug)]
-pub struct Scoring {
-    state: GameMetaData,
+pub struct Scoring<E: Edition> {
+    state: GameMetaData<E>,
     pending: Vec<u8>,
     requested: bool,
-    actions: Vec<(u8, Option<AustralianActivity>)>,
+    actions: Vec<(u8, Option<E::Activity>)>,
 }
 
 #[derive(Debug)]
-pub struct Syncing<Next: AsMetaData + Send + Sync> {
-    state: GameMetaData,
+pub struct Syncing<E: Edition, Next: AsMetaData<E> + Send + Sync> {
+    state: GameMetaData<E>,
     pending: Vec<u8>,
     requested: bool,
     next_state: Option<Box<Next>>,
 }
 
 #[derive(Debug)]
-pub struct Final {
-    state: GameMetaData,
+pub struct Final<E: Edition> {
+    state: GameMetaData<E>,
     delivered: bool,
 }
 
 macro_rules! represent {
     ($($state:ident$(<$generic:ident>)?)+) => {
         $(
-            impl$(<$generic: AsMetaData +Send +Sync +From<GameMetaData>+'static>)? AsMetaData for $state$(<$generic>)?{
-                fn metadata(&mut self) -> &mut GameMetaData{
+            impl<E: Edition $(, $generic: AsMetaData<E> +Transitions +Send +Sync +From<GameMetaData<E>>+'static)?> AsMetaData<E> for $state<E $(, $generic)?>{
+                fn metadata(&mut self) -> &mut GameMetaData<E>{
                     &mut self.state
                 }
             }
@@ -128,9 +182,12 @@ mod test {
     use server::engine::rules::{Action, New};
 
 
-    use crate::australia::{protocol::Event, rules::{cards::{AustraliaDeck, AustraliaCard}, AustraliaPlayer}};
+    use crate::australia::{protocol, rules::{cards::{AustraliaDeck, AustraliaCard}, config::GameConfig, meta::GameMetaData, AustraliaEdition, AustraliaPlayer}};
+
+    use super::{pass::Direction, GameState, WaitingForPlayers};
 
-    use super::{pass::Direction, DealingCards, GameState, WaitingForPlayers};
+    type Event = protocol::Event<AustraliaEdition>;
+    type DealingCards = super::DealingCards<AustraliaEdition>;
 
     #[test]
     fn test_1_2() {
@@ -190,6 +247,37 @@ mod test {
         test_waiting_state_with_players(players, expected_result);
     }
 
+    /// Every player gets the config without the seed and the seats once they are all ready
+    #[test]
+    fn test_config_sent() {
+        let players = vec![1, 2];
+        let config = GameConfig {
+            seed: Some(42),
+            activity_points: vec![0, 1, 2],
+            ..Default::default()
+        };
+        let mut state =
+            WaitingForPlayers::<AustraliaEdition, DealingCards>::new(None, config.clone());
+        let (_duration, actions, _next) = state.get_next_action(&players);
+        for action in actions {
+            state
+                .register_response((Event::Accept, &action.transition().transition()))
+                .unwrap();
+        }
+        let (_duration, actions, next) = state.get_next_action(&players);
+        assert!(next.is_some());
+        let sent = GameConfig { seed: None, ..config };
+        assert_eq!(
+            actions,
+            vec![
+                Action::<New, Event>::new(1, Event::Config(sent.clone())),
+                Action::<New, Event>::new(1, Event::Seats(vec![1, 2])),
+                Action::<New, Event>::new(2, Event::Config(sent)),
+                Action::<New, Event>::new(2, Event::Seats(vec![1, 2])),
+            ]
+        );
+    }
+
     #[test]
     fn deal() {
         assert_eq!(AustraliaDeck::default().cards().len(), 28)
@@ -200,92 +288,238 @@ mod test {
     /// This test will only pass if the number of cards dealt to each players is 7
     #[test]
     fn test_4() {
-        deal_all();
+        deal_all(&vec![0, 1, 2, 3]);
     }
     #[test]
     fn test_4_and_5() {
-        let state = deal_all();
-        let state = sync(state);
-        discard_card(state);
+        let players = vec![0, 1, 2, 3];
+        let state = deal_all(&players);
+        let state = sync(state, &players);
+        discard_card(state, &players);
     }
 
     #[test]
     fn test_4_thru_7() {
-        let state = deal_all();
-        let state = sync(state);
-        let state = discard_card(state);
-        let state = pass_hand(state, Direction::Forward);
-        let state = sync(state);
-        let _state = show_card(state);
+        let players = vec![0, 1, 2, 3];
+        let state = deal_all(&players);
+        let state = sync(state, &players);
+        let state = discard_card(state, &players);
+        let state = pass_hand(state, Direction::Forward, &players);
+        let state = sync(state, &players);
+        let _state = show_card(state, &players);
     }
 
     #[test]
     fn test_4_thru_8() {
-        let state = deal_all();
-        let state = sync(state);
-        let mut state = discard_card(state);
+        let players = vec![0, 1, 2, 3];
+        let state = deal_all(&players);
+        let state = sync(state, &players);
+        let mut state = discard_card(state, &players);
         for _ in 0..5 {
-            state = pass_hand(state, Direction::Forward);
-            state = sync(state);
-            state = show_card(state);
+            state = pass_hand(state, Direction::Forward, &players);
+            state = sync(state, &players);
+            state = show_card(state, &players);
         }
     }
 
     #[test]
     fn test_4_thru_12() {
-        let mut state = deal_all();
+        play_game(&vec![0, 1, 2, 3]);
+    }
+
+    /// Plays a full two player game, a dummy hand is passed around with the players hands
+    #[test]
+    fn test_2_players_4_thru_12() {
+        play_game(&vec![0, 1]);
+    }
+
+    /// Ends a game of a two game match, the next game starts after a ready check
+    #[test]
+    fn test_match() {
+        let players = vec![0, 1, 2];
+        let config = GameConfig {
+            games: 2,
+            ..Default::default()
+        };
+        let mut state: Box<dyn GameState<AustraliaEdition>> =
+            Box::new(super::Final::new(GameMetaData::new(&players, config)));
+        let (_duration, actions, next) = state.get_next_action(&players);
+        assert!(next.is_none());
+        // Every player gets the result of the game and the match standings
+        assert_eq!(actions.len(), 2 * players.len());
+        assert!(actions.iter().any(|action| matches!(
+            action.action(),
+            Event::MatchStandings(standings) if standings.played == 1
+        )));
+        assert!(!state.finished());
+
+        let (_duration, _actions, next) = state.get_next_action(&players);
+        let mut state = next.unwrap();
+        assert_eq!(state.name(), "WaitingForPlayers");
+        assert!(!state.accepts_players());
+        let (_duration, actions, _next) = state.get_next_action(&players);
+        for action in actions {
+            assert_eq!(action.action(), Event::ReadyCheck);
+            state
+                .register_response((Event::Accept, &action.transition().transition()))
+                .unwrap();
+        }
+        let (_duration, actions, next) = state.get_next_action(&players);
+        let mut state = next.unwrap();
+        let meta = state.metadata().unwrap();
+        // The seats have rotated and the standings are kept
+        let seats: Vec<u8> = meta.get_players().iter().map(|player| player.id).collect();
+        assert_eq!(seats, vec![0, 2, 1]);
+        assert!(actions
+            .iter()
+            .all(|action| action.action() != Event::Seats(vec![0, 1, 2])));
+        assert!(actions
+            .iter()
+            .any(|action| action.action() == Event::Seats(seats.clone())));
+        assert_eq!(meta.standings().played, 1);
+
+        let mut state = super::Final::new(meta.clone());
+        let (_duration, actions, _next) = state.get_next_action(&players);
+        assert_eq!(actions.len(), 2 * players.len());
+        assert!(state.finished());
+    }
+
+    #[test]
+    fn test_2_players_dummy_hand() {
+        let players = vec![0, 1];
+        let state = deal_all(&players);
+        let state = sync(state, &players);
+        let mut state = discard_card(state, &players);
+        let hand_size = |state: &mut Box<dyn GameState<AustraliaEdition>>| {
+            hands(&state.metadata().unwrap().hands())
+                .iter()
+                .map(|hand| hand.len())
+                .collect::<Vec<usize>>()
+        };
+        assert_eq!(hand_size(&mut state), vec![6, 6]);
+
+        // The dummy hand plays a card before it is passed on
+        let initial_hands = hands(&state.metadata().unwrap().hands());
+        state.metadata().unwrap().circulate(Direction::Forward);
+        let final_hands = hands(&state.metadata().unwrap().hands());
+        assert_eq!(final_hands[1], initial_hands[0]);
+        assert_eq!(final_hands[0].len(), 6);
+        assert!(final_hands[0] != initial_hands[1]);
+
+        // After two more passes every hand has been around the table once
+        state.metadata().unwrap().circulate(Direction::Forward);
+        state.metadata().unwrap().circulate(Direction::Forward);
+        assert_eq!(hands(&state.metadata().unwrap().hands()), initial_hands);
+    }
+
+    /// Plays a two player round, the dummy hand is passed with the players hands and plays
+    /// its first card before every pass
+    #[test]
+    fn test_2_players_dummy_round() {
+        let players = vec![0, 1];
+        let state = deal_all(&players);
+        let state = sync(state, &players);
+        let mut state = discard_card(state, &players);
+        let dummy = |state: &mut Box<dyn GameState<AustraliaEdition>>| {
+            state.metadata().unwrap().seats().last().unwrap().clone()
+        };
+        assert_eq!(dummy(&mut state).hand_size(), 7);
+
+        let mut played = Vec::new();
+        for pass in 0..6 {
+            let direction = match pass {
+                5 => Direction::Backward,
+                _ => Direction::Forward,
+            };
+            let before = dummy(&mut state);
+            played.push(before.get_hand()[0]);
+            state = pass_hand(state, direction, &players);
+            let after = dummy(&mut state);
+            // The dummy played one card and holds as many cards as the players
+            assert_eq!(
+                after.get_discard().len() + after.get_show().len(),
+                pass + 1
+            );
+            let players_hand = state.metadata().unwrap().hands()[0].hand_size();
+            assert_eq!(after.hand_size(), players_hand);
+
+            state = sync(state, &players);
+            if pass != 5 {
+                state = show_card(state, &players);
+            }
+        }
+        // The first card is discarded and the others are shown, in the order they were played
+        let dummy = dummy(&mut state);
+        assert_eq!(dummy.get_discard(), played[..1].to_vec());
+        assert_eq!(dummy.get_show(), played[1..].to_vec());
+        assert_eq!(dummy.hand_size(), 1);
+
+        // Every card that was dealt is still in the game, and only the players are scored
+        let meta = state.metadata().unwrap();
+        assert_eq!(meta.cards().len(), AustraliaDeck::default().cards().len());
+        assert_eq!(crate::australia::rules::invariants::check(meta), Ok(()));
+        score_state(state, &players);
+    }
+
+    // ==============================================================================
+    //                              Helpers
+    // ==============================================================================
+    fn play_game(players: &Vec<usize>) {
+        let mut state = deal_all(players);
         for i in 0..4 {
             if i != 0 {
-                state = deal_from_state(state);
+                state = deal_from_state(state, players);
             }
-            state = sync(state);
-            state = discard_card(state);
+            state = sync(state, players);
+            state = discard_card(state, players);
             for j in 0..6 {
                 if j != 5 {
                     println!("Action {:?} round {:?}, pass_hand", j + 1, i);
-                    state = pass_hand(state, Direction::Forward);
+                    state = pass_hand(state, Direction::Forward, players);
                     println!("Action {:?}, pass_hand forward Ok", j + 1);
                 } else {
                     println!("Action {:?} round {:?}, pass_hand", j + 1, i);
-                    state = pass_hand(state, Direction::Backward);
+                    state = pass_hand(state, Direction::Backward, players);
                     println!("Action {:?}, pass_hand forward Ok", j + 1);
                 }
                 println!("Action {:?} round {:?}, sync", j + 1, i);
-                state = sync(state);
+                state = sync(state, players);
                 println!("Action {:?}, sync Ok", j + 1);
 
                 if j != 5 {
                     println!("Action {:?} round {:?}, show_card", j + 1, i);
-                    state = show_card(state);
+                    state = show_card(state, players);
                     println!("Action {:?}, show_card Ok", j + 1);
                 } else {
                     // Here we are going  to score things
                     println!("Action {:?} round {:?}, score_state", j + 1, i);
-                    state = score_state(state);
+                    state = score_state(state, players);
                     if i != 3 {
-                        state = sync(state);
+                        state = sync(state, players);
                     }
                 }
             }
         }
 
         println!("Action game_end");
-        game_end(state);
+        game_end(state, players);
         println!("Game end Ok");
     }
-
-    // ==============================================================================
-    //                              Helpers
-    // ==============================================================================
     fn test_waiting_state_with_players(
         players: Vec<usize>,
-        expect: (Vec<Action<New, Event>>, Option<Box<dyn GameState>>),
+        expect: (Vec<Action<New, Event>>, Option<Box<dyn GameState<AustraliaEdition>>>),
     ) {
-        let mut waiting_state = WaitingForPlayers::<DealingCards>::new(None);
+        let mut waiting_state =
+            WaitingForPlayers::<AustraliaEdition, DealingCards>::new(None, GameConfig::default());
 
         let (_duration, actions, _next_state) = waiting_state.get_next_action(&players);
         assert_eq!(expect.0, actions);
     }
+    /// Returns the hand of every seat, the dummy hand of a two player game is last
+    fn seat_hands(state: &mut Box<dyn GameState<AustraliaEdition>>) -> Vec<Vec<AustraliaCard>> {
+        let meta = state.metadata().expect("Metadata is not available.");
+        meta.seats().map(|seat| seat.get_hand()).collect()
+    }
     fn hands(players: &Vec<AustraliaPlayer>) -> Vec<Vec<AustraliaCard>> {
         let mut ret = Vec::new();
         for player in players {
@@ -293,13 +527,15 @@ mod test {
         }
         ret
     }
-    fn deal_from_state(mut current_state: Box<dyn GameState>) -> Box<dyn GameState> {
+    fn deal_from_state(
+        mut current_state: Box<dyn GameState<AustraliaEdition>>,
+        players: &Vec<usize>,
+    ) -> Box<dyn GameState<AustraliaEdition>> {
         println!("State: {:?}", current_state);
-        let players = vec![0, 1, 2, 3];
         let mut next_state = None;
         let mut card_counter = 0;
         while let None = next_state {
-            let (_duration, actions, state) = current_state.get_next_action(&players);
+            let (_duration, actions, state) = current_state.get_next_action(players);
 
             println!("Requested actions: {:?}", actions);
             match state {
@@ -342,15 +578,14 @@ mod test {
         assert_eq!(card_counter, 7);
         next_state.unwrap()
     }
-    fn deal_all() -> Box<dyn GameState> {
-        let players = vec![0, 1, 2, 3];
+    fn deal_all(players: &Vec<usize>) -> Box<dyn GameState<AustraliaEdition>> {
         // Create a mock DealingCards state with the game logic.
-        let mut dealing_cards_state = DealingCards::new(&players);
+        let mut dealing_cards_state = DealingCards::new(players, GameConfig::default());
         let mut next_state = None;
         let mut card_counter = 0;
         // Simulate dealing the card
         while let None = next_state {
-            let (_duration, actions, state) = dealing_cards_state.get_next_action(&players);
+            let (_duration, actions, state) = dealing_cards_state.get_next_action(players);
             match state {
                 None => {
                     for action in actions.iter() {
@@ -391,12 +626,14 @@ mod test {
         assert_eq!(card_counter, 7);
         next_state.unwrap()
     }
-    fn sync(mut current_state: Box<dyn GameState>) -> Box<dyn GameState> {
-        let players = vec![0, 1, 2, 3];
+    fn sync(
+        mut current_state: Box<dyn GameState<AustraliaEdition>>,
+        players: &Vec<usize>,
+    ) -> Box<dyn GameState<AustraliaEdition>> {
         let mut next_state = None;
 
         while let None = next_state {
-            let (_duration, actions, state) = current_state.get_next_action(&players);
+            let (_duration, actions, state) = current_state.get_next_action(players);
             println!("Requested actions: {:?}", actions);
 
             for action in actions.iter() {
@@ -422,22 +659,23 @@ mod test {
         next_state.unwrap()
     }
     fn pass_hand(
-        mut current_state: Box<dyn GameState>,
+        mut current_state: Box<dyn GameState<AustraliaEdition>>,
         direction: Direction,
-    ) -> Box<dyn GameState> {
-        let players = vec![0, 1, 2, 3];
+        players: &Vec<usize>,
+    ) -> Box<dyn GameState<AustraliaEdition>> {
         let mut next_state = None;
 
-        // The hands before passing
-        let mut initial_hands = hands(
-            &current_state
-                .metadata()
-                .expect("Metadata is not available.")
-                .hands(),
-        );
+        // The hands before passing, in a two player game the dummy plays the first cards of its
+        // hand until it holds as many cards as the players before its hand is passed on
+        let mut initial_hands = seat_hands(&mut current_state);
+        if players.len() == 2 {
+            let hand_size = initial_hands[0].len();
+            let dummy = initial_hands.last_mut().unwrap();
+            dummy.drain(..dummy.len() - hand_size);
+        }
 
         while let None = next_state {
-            let (_duration, actions, state) = current_state.get_next_action(&players);
+            let (_duration, actions, state) = current_state.get_next_action(players);
             println!("Requested actions: {:?}", actions);
 
             for action in actions.iter() {
@@ -460,12 +698,7 @@ mod test {
             next_state = state;
         }
         let mut state = next_state.unwrap();
-        let mut final_hands = hands(
-            &state
-                .metadata()
-                .expect("Metadata is not available.")
-                .hands(),
-        );
+        let mut final_hands = seat_hands(&mut state);
         println!(
             "Initial hands : {:?} \nfinal_hands : {:?}",
             initial_hands, final_hands
@@ -483,13 +716,15 @@ mod test {
 
         state
     }
-    fn discard_card(mut current_state: Box<dyn GameState>) -> Box<dyn GameState> {
-        let players = vec![0, 1, 2, 3];
+    fn discard_card(
+        mut current_state: Box<dyn GameState<AustraliaEdition>>,
+        players: &Vec<usize>,
+    ) -> Box<dyn GameState<AustraliaEdition>> {
         let mut next_state = None;
         let mut discard_counter = 0;
         // Simulate dealing the card
         while let None = next_state {
-            let (_duration, actions, state) = current_state.get_next_action(&players);
+            let (_duration, actions, state) = current_state.get_next_action(players);
             println!("Requested actions : {:?}", actions);
             match state {
                 None => {
@@ -511,16 +746,18 @@ mod test {
             }
             next_state = state;
         }
-        assert_eq!(discard_counter, 4);
+        assert_eq!(discard_counter, players.len());
         next_state.unwrap()
     }
-    fn show_card(mut current_state: Box<dyn GameState>) -> Box<dyn GameState> {
-        let players = vec![0, 1, 2, 3];
+    fn show_card(
+        mut current_state: Box<dyn GameState<AustraliaEdition>>,
+        players: &Vec<usize>,
+    ) -> Box<dyn GameState<AustraliaEdition>> {
         let mut next_state = None;
         let mut show_counter = 0;
 
         while let None = next_state {
-            let (_duration, actions, state) = current_state.get_next_action(&players);
+            let (_duration, actions, state) = current_state.get_next_action(players);
             println!("Requested actions: {:?}", actions);
 
             match state {
@@ -547,17 +784,19 @@ mod test {
             next_state = state;
         }
 
-        assert_eq!(show_counter, 4);
+        assert_eq!(show_counter, players.len());
 
         next_state.unwrap()
     }
 
-    fn score_state(mut current_state: Box<dyn GameState>) -> Box<dyn GameState> {
-        let players: Vec<usize> = vec![0, 1, 2, 3];
-        let mut next_state: Option<Box<dyn GameState>> = None;
+    fn score_state(
+        mut current_state: Box<dyn GameState<AustraliaEdition>>,
+        players: &Vec<usize>,
+    ) -> Box<dyn GameState<AustraliaEdition>> {
+        let mut next_state: Option<Box<dyn GameState<AustraliaEdition>>> = None;
         let mut score_counter = 0;
         while let None = next_state {
-            let (_duration, actions, state) = current_state.get_next_action(&players);
+            let (_duration, actions, state) = current_state.get_next_action(players);
             println!("Requested actions: {:?}", actions);
 
             match state {
@@ -595,15 +834,14 @@ mod test {
             }
         }
 
-        assert_eq!(score_counter, 4);
+        assert_eq!(score_counter, players.len());
 
         next_state.unwrap()
     }
 
-    fn game_end(mut current_state: Box<dyn GameState>) {
-        let players = vec![0, 1, 2, 3];
+    fn game_end(mut current_state: Box<dyn GameState<AustraliaEdition>>, players: &Vec<usize>) {
         let mut score_recv_counter = 0;
-        let (_duration, actions, state) = current_state.get_next_action(&players);
+        let (_duration, actions, state) = current_state.get_next_action(players);
         println!("Requested actions: {:?}", actions);
 
         match state {
@@ -622,6 +860,6 @@ mod test {
             _ => {}
         }
 
-        assert_eq!(score_recv_counter, 4);
+        assert_eq!(score_recv_counter, players.len());
     }
 }
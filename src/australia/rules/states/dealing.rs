@@ -1,25 +1,25 @@
 //! Defines a state where the cards are being dealt to the players
 
-use log::info;
+use tracing::info;
 
 use crate::{
-    engine::rules::{Action, Error, New, Received}, australia::{rules::{meta::GameMetaData, states::Syncing}, protocol::Event},
+    engine::rules::{Action, Error, New, Received}, australia::{rules::{config::GameConfig, edition::Edition, meta::GameMetaData, states::Syncing}, protocol::Event},
 };
 
-use super::{DealingCards, DiscardCard, GameState, AsMetaData};
+use super::{AsMetaData, DealingCards, DiscardCard, GameState, StateNode, Transitions};
 
-impl DealingCards {
-    pub fn new(players: &[usize]) -> Self {
+impl<E: Edition> DealingCards<E> {
+    pub fn new(players: &[usize], config: GameConfig) -> Self {
         Self {
             pending_actions: Vec::with_capacity(players.len()),
             validated: Vec::new(),
-            state: GameMetaData::new(players),
+            state: GameMetaData::new(players, config),
         }
     }
 }
 
-impl From<GameMetaData> for DealingCards {
-    fn from(metadata: GameMetaData) -> Self {
+impl<E: Edition> From<GameMetaData<E>> for DealingCards<E> {
+    fn from(metadata: GameMetaData<E>) -> Self {
         Self {
             pending_actions: Vec::new(),
             validated: Vec::new(),
@@ -28,14 +28,17 @@ impl From<GameMetaData> for DealingCards {
     }
 }
 
-impl GameState for DealingCards {
+impl<E: Edition> GameState<E> for DealingCards<E> {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
     fn get_next_action<'a>(
         &'a mut self,
         players: &Vec<usize>,
     ) -> (
         tokio::time::Duration,
-        Vec<Action<New, Event>>,
-        Option<Box<dyn GameState>>,
+        Vec<Action<New, Event<E>>>,
+        Option<Box<dyn GameState<E>>>,
     ) {
         info!("State : {:?}", self);
         let mut actions = Vec::new();
@@ -59,7 +62,7 @@ impl GameState for DealingCards {
             return (
                 tokio::time::Duration::from_millis(500),
                 actions,
-                Some(Box::new(Syncing::<DiscardCard>::new(
+                Some(Box::new(Syncing::<E, DiscardCard<E>>::new(
                     self.state.clone(),
                     Box::new(DiscardCard::new(self.state.clone())),
                 ))),
@@ -71,16 +74,16 @@ impl GameState for DealingCards {
 
     fn register_message(
         &mut self,
-        _action: &Action<New, Event>,
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
+        _action: &Action<New, Event<E>>,
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         // Check if that player has an outstanding action        
         Err(Error::UnexpectedMessage)
     }
 
     fn register_response(
         &mut self,
-        action: (Event, &Action<Received, Event>),
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
+        action: (Event<E>, &Action<Received, Event<E>>),
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         let (response, action) = action;
         let (player, action) = (action.player(), action.action());
 
@@ -113,7 +116,14 @@ impl GameState for DealingCards {
         }
     }
 
-    fn metadata(&mut self) -> Option<&mut GameMetaData> {
+    fn metadata(&mut self) -> Option<&mut GameMetaData<E>> {
         Some(AsMetaData::metadata(self))
     }
 }
+
+impl<E: Edition> Transitions for DealingCards<E> {
+    const NAME: &'static str = "DealingCards";
+    fn transitions() -> Vec<StateNode> {
+        vec![StateNode::of::<Syncing<E, DiscardCard<E>>>()]
+    }
+}
@@ -0,0 +1,163 @@
+//! Draws the state machine from the [`Transitions`] that the states declare.
+//!
+//! `boomerang diagram --rules australia` prints the diagram of a rule set as Graphviz DOT, or
+//! as Mermaid with `--format mermaid`, so the drawing always matches the states.
+
+use std::collections::VecDeque;
+
+use clap::ValueEnum;
+
+use crate::australia::rules::edition::Edition;
+
+use super::{DealingCards, StateNode, WaitingForPlayers};
+
+/// The formats that a [`StateMachine`] can be drawn in
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum DiagramFormat {
+    /// Graphviz, `dot -Tpng` draws it
+    #[default]
+    Dot,
+    /// A state diagram that renders in markdown
+    Mermaid,
+}
+
+/// The states of a game and the transitions in between them
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateMachine {
+    /// The state that a game starts in
+    pub initial: &'static str,
+    /// The states in the order that they are first reached
+    pub states: Vec<&'static str>,
+    pub transitions: Vec<(&'static str, &'static str)>,
+}
+
+impl StateMachine {
+    /// Follows the declared transitions from the state that a game of `E` starts in
+    pub fn new<E: Edition>() -> Self {
+        let initial = StateNode::of::<WaitingForPlayers<E, DealingCards<E>>>();
+        let mut states = vec![initial.name];
+        let mut transitions = Vec::new();
+        // States of the same name are drawn as one, but are followed by different states
+        let mut visited = vec![initial.type_name()];
+        let mut queue = VecDeque::from([initial]);
+        while let Some(state) = queue.pop_front() {
+            for next in state.next() {
+                if !states.contains(&next.name) {
+                    states.push(next.name);
+                }
+                if !transitions.contains(&(state.name, next.name)) {
+                    transitions.push((state.name, next.name));
+                }
+                if !visited.contains(&next.type_name()) {
+                    visited.push(next.type_name());
+                    queue.push_back(next);
+                }
+            }
+        }
+        Self {
+            initial: initial.name,
+            states,
+            transitions,
+        }
+    }
+
+    /// Draws the machine, `name` is the title of the diagram
+    pub fn draw(&self, name: &str, format: DiagramFormat) -> String {
+        let mut lines = Vec::new();
+        match format {
+            DiagramFormat::Dot => {
+                lines.push(format!("digraph \"{}\" {{", name.replace('"', "\\\"")));
+                lines.push("    start [shape=point];".to_owned());
+                lines.push(format!("    start -> {};", self.initial));
+                for (from, to) in &self.transitions {
+                    lines.push(format!("    {} -> {};", from, to));
+                }
+                lines.push("}".to_owned());
+            }
+            DiagramFormat::Mermaid => {
+                lines.push("---".to_owned());
+                lines.push(format!("title: {}", name));
+                lines.push("---".to_owned());
+                lines.push("stateDiagram-v2".to_owned());
+                lines.push(format!("    [*] --> {}", self.initial));
+                for (from, to) in &self.transitions {
+                    lines.push(format!("    {} --> {}", from, to));
+                }
+            }
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+/// Draws the state machine of `E`
+pub fn draw<E: Edition>(name: &str, format: DiagramFormat) -> String {
+    StateMachine::new::<E>().draw(name, format)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{australia::rules::AustraliaEdition, europe::rules::EuropeEdition};
+
+    use super::{draw, DiagramFormat, StateMachine};
+
+    #[test]
+    fn test_australia() {
+        let machine = StateMachine::new::<AustraliaEdition>();
+        assert_eq!(machine.initial, "WaitingForPlayers");
+        assert_eq!(
+            machine.transitions,
+            vec![
+                ("WaitingForPlayers", "DealingCards"),
+                ("DealingCards", "Syncing"),
+                ("Syncing", "DiscardCard"),
+                ("DiscardCard", "PassHand"),
+                ("PassHand", "Syncing"),
+                ("Syncing", "ShowCard"),
+                ("ShowCard", "PassHand"),
+                ("Syncing", "Scoring"),
+                ("Scoring", "Syncing"),
+                ("Scoring", "Final"),
+                ("Syncing", "DealingCards"),
+                ("Final", "WaitingForPlayers"),
+            ]
+        );
+        assert_eq!(machine.states.len(), 8);
+
+        // Europe scores the round in a state of its own
+        let machine = StateMachine::new::<EuropeEdition>();
+        assert!(machine.transitions.contains(&("Syncing", "ScoreRound")));
+        assert!(machine.transitions.contains(&("ScoreRound", "Final")));
+        assert!(!machine.states.contains(&"Scoring"));
+    }
+
+    #[test]
+    fn test_draw() {
+        let machine = StateMachine::new::<AustraliaEdition>();
+        let dot = machine.draw("australia", DiagramFormat::Dot);
+        assert!(dot.starts_with("digraph \"australia\" {\n"));
+        assert!(dot.contains("    start -> WaitingForPlayers;\n"));
+        assert!(dot.contains("    ShowCard -> PassHand;\n"));
+        assert!(dot.ends_with("}\n"));
+
+        let mermaid = machine.draw("australia", DiagramFormat::Mermaid);
+        assert!(mermaid.contains("stateDiagram-v2\n    [*] --> WaitingForPlayers\n"));
+        assert!(mermaid.contains("    Scoring --> Final\n"));
+        assert_eq!(
+            mermaid.lines().filter(|line| line.contains("-->")).count(),
+            machine.transitions.len() + 1
+        );
+    }
+
+    #[test]
+    fn test_readme() {
+        // The drawing in the README has to be updated when the states change
+        let readme = include_str!("../../../../README.md");
+        let start = readme.find("```mermaid\n").unwrap() + "```mermaid\n".len();
+        let end = start + readme[start..].find("```").unwrap();
+        assert_eq!(
+            &readme[start..end],
+            draw::<AustraliaEdition>("australia", DiagramFormat::Mermaid)
+        );
+    }
+}
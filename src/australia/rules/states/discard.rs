@@ -2,20 +2,20 @@
 //! This card is hidden from the other players
 
 
-use log::info;
+use tracing::{debug, info};
 
 
 
 
 use crate::{
-    australia::{protocol::Event, rules::meta::GameMetaData},
+    australia::{protocol::Event, rules::{edition::Edition, meta::GameMetaData}},
     engine::rules::{Action, Error, New, Received},
 };
 
-use super::{pass::Direction, AsMetaData, DiscardCard, GameState, PassHand, ShowCard};
+use super::{AsMetaData, DiscardCard, GameState, PassHand, ShowCard, StateNode, Transitions};
 
-impl DiscardCard {
-    pub fn new(state: GameMetaData) -> Self {
+impl<E: Edition> DiscardCard<E> {
+    pub fn new(state: GameMetaData<E>) -> Self {
         Self {
             state,
             pending: Vec::new(),
@@ -24,24 +24,27 @@ impl DiscardCard {
     }
 }
 
-impl From<GameMetaData> for DiscardCard {
-    fn from(metadata: GameMetaData) -> Self {
+impl<E: Edition> From<GameMetaData<E>> for DiscardCard<E> {
+    fn from(metadata: GameMetaData<E>) -> Self {
         Self::new(metadata)
     }
 }
 
-impl GameState for DiscardCard {
+impl<E: Edition> GameState<E> for DiscardCard<E> {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
     fn get_next_action(
         &mut self,
         players: &Vec<usize>,
     ) -> (
         tokio::time::Duration,
-        Vec<Action<New, Event>>,
-        Option<Box<dyn GameState>>,
+        Vec<Action<New, Event<E>>>,
+        Option<Box<dyn GameState<E>>>,
     ) {
         info!("State : {:?}", self);
         let mut actions = Vec::new();
-        let mut request = |event: Event| {
+        let mut request = |event: Event<E>| {
             for player in players {
                 if !self.pending.contains(&(*player as u8)) {
                     actions.push(Action::new(*player, event.clone()));
@@ -61,12 +64,13 @@ impl GameState for DiscardCard {
             }
             self.requested = true;
         } else {
+            let direction = self.state.next_pass();
             return (
                 tokio::time::Duration::from_millis(500),
                 actions,
-                Some(Box::new(PassHand::<ShowCard>::new(
+                Some(Box::new(PassHand::<E, ShowCard<E>>::new(
                     self.state.clone(),
-                    Direction::Forward,
+                    direction,
                 ))),
             );
         }
@@ -75,19 +79,18 @@ impl GameState for DiscardCard {
 
     fn register_message(
         &mut self,
-        _action: &Action<New, Event>,
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
+        _action: &Action<New, Event<E>>,
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         Err(Error::UnexpectedMessage)
     }
 
     fn register_response(
         &mut self,
-        action: (Event, &Action<Received, Event>),
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
-        println!("{:?}", action);
+        action: (Event<E>, &Action<Received, Event<E>>),
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         let (response, action) = action;
         let (player, action) = (action.player(), action.action());
-        println!("{:?},{:?},{:?}", response, player, action);
+        debug!(?response, uid = player, ?action, "Registering response");
 
         let mut outstanding_request = None;
         for (idx, &id) in self.pending.iter().enumerate() {
@@ -114,7 +117,14 @@ impl GameState for DiscardCard {
             _ => Err(Error::UnexpectedResponse),
         }
     }
-    fn metadata(&mut self) -> Option<&mut GameMetaData> {
+    fn metadata(&mut self) -> Option<&mut GameMetaData<E>> {
         Some(AsMetaData::metadata(self))
     }
 }
+
+impl<E: Edition> Transitions for DiscardCard<E> {
+    const NAME: &'static str = "DiscardCard";
+    fn transitions() -> Vec<StateNode> {
+        vec![StateNode::of::<PassHand<E, ShowCard<E>>>()]
+    }
+}
@@ -1,15 +1,23 @@
 //! Defines a final state where the  score is distributed to the players.
+//!
+//! In a match of several games the final state records the game in the match standings
+//! and, unless the match is over, starts the next game after a ready check.
 
-use log::info;
+use tracing::info;
 
 use crate::{
-    engine::rules::{Action, Error, New, Received}, australia::{rules::meta::GameMetaData, protocol::Event},
+    engine::rules::{Action, Error, New, Received}, australia::{rules::{edition::Edition, meta::GameMetaData}, protocol::Event},
 };
 
-use super::{Final, GameState, AsMetaData};
+use super::{
+    AsMetaData, DealingCards, Final, GameState, StateNode, Transitions, WaitingForPlayers,
+};
+
+/// Time for the players to read the standings before the next game of a match
+const STANDINGS_DELAY: tokio::time::Duration = tokio::time::Duration::from_secs(5);
 
-impl Final {
-    pub fn new(state: GameMetaData) -> Self {
+impl<E: Edition> Final<E> {
+    pub fn new(state: GameMetaData<E>) -> Self {
         Self {
             state,
             delivered: false,
@@ -17,33 +25,61 @@ impl Final {
     }
 }
 
-impl From<GameMetaData> for Final {
-    fn from(value: GameMetaData) -> Self {
+impl<E: Edition> From<GameMetaData<E>> for Final<E> {
+    fn from(value: GameMetaData<E>) -> Self {
         Self::new(value)
     }
 }
 
-impl GameState for Final {
+impl<E: Edition> GameState<E> for Final<E> {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
     fn get_next_action(
         &mut self,
         _players: &Vec<usize>,
     ) -> (
         tokio::time::Duration,
-        Vec<Action<New, Event>>,
-        Option<Box<dyn GameState>>,
+        Vec<Action<New, Event<E>>>,
+        Option<Box<dyn GameState<E>>>,
     ) {
         info!("State : {:?}", self);
         let mut actions = Vec::new();
         if !self.delivered {
             let result = self.state.rank();
+            self.state.record_game(&result);
+            let standings = self.state.standings().clone();
+            let is_match = self.state.config().games > 1;
             for player in self.state.get_players() {
                 actions.push(Action::new(
                     player.id as usize,
                     Event::FinalResult(player.id, result.clone()),
                 ));
+                if is_match {
+                    actions.push(Action::new(
+                        player.id as usize,
+                        Event::MatchStandings(standings.clone()),
+                    ));
+                }
             }
             self.delivered = true;
-            (tokio::time::Duration::from_millis(500), actions, None)
+            let delay = match standings.over() {
+                true => tokio::time::Duration::from_millis(500),
+                false => STANDINGS_DELAY,
+            };
+            (delay, actions, None)
+        } else if !self.state.standings().over() {
+            info!(standings = ?self.state.standings(), "Starting the next game of the match");
+            let next = DealingCards::from(self.state.next_game());
+            let config = self.state.config().clone();
+            (
+                tokio::time::Duration::from_millis(500),
+                actions,
+                Some(Box::new(WaitingForPlayers::<E, DealingCards<E>>::new(
+                    Some(Box::new(next)),
+                    config,
+                ))),
+            )
         } else {
             (tokio::time::Duration::from_secs(100), actions, None)
         }
@@ -51,18 +87,29 @@ impl GameState for Final {
 
     fn register_message(
         &mut self,
-        _action: &Action<New, Event>,
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
+        _action: &Action<New, Event<E>>,
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         Err(Error::UnexpectedMessage)
     }
 
     fn register_response(
         &mut self,
-        _action: (Event, &Action<Received, Event>),
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
+        _action: (Event<E>, &Action<Received, Event<E>>),
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         Err(Error::UnexpectedResponse)
     }
-    fn metadata(&mut self) -> Option<&mut GameMetaData> {
+    fn metadata(&mut self) -> Option<&mut GameMetaData<E>> {
         Some(AsMetaData::metadata(self))
     }
+    fn finished(&self) -> bool {
+        self.delivered && self.state.standings().over()
+    }
+}
+
+impl<E: Edition> Transitions for Final<E> {
+    const NAME: &'static str = "Final";
+    /// The next game of a match waits for the players to be ready again
+    fn transitions() -> Vec<StateNode> {
+        vec![StateNode::of::<WaitingForPlayers<E, DealingCards<E>>>()]
+    }
 }
@@ -5,23 +5,24 @@
 //! 
 use std::marker::PhantomData;
 
-use log::info;
+use serde::{Deserialize, Serialize};
+use tracing::info;
 
 use crate::{
-    australia::{protocol::Event, rules::meta::GameMetaData},
+    australia::{protocol::Event, rules::{edition::Edition, meta::GameMetaData}},
     engine::rules::{Action, Error, New, Received},
 };
 
-use super::{AsMetaData, GameState, PassHand, Syncing};
+use super::{AsMetaData, GameState, PassHand, StateNode, Syncing, Transitions};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Direction {
     Forward,
     Backward,
 }
 
-impl<Next: AsMetaData + Send + Sync + From<GameMetaData>> PassHand<Next> {
-    pub fn new(state: GameMetaData, direction: Direction) -> Self {
+impl<E: Edition, Next: AsMetaData<E> + Send + Sync + From<GameMetaData<E>>> PassHand<E, Next> {
+    pub fn new(state: GameMetaData<E>, direction: Direction) -> Self {
         Self {
             state,
             pending: Vec::new(),
@@ -32,14 +33,19 @@ impl<Next: AsMetaData + Send + Sync + From<GameMetaData>> PassHand<Next> {
     }
 }
 
-impl<Next: AsMetaData + Send + Sync + From<GameMetaData> + 'static> GameState for PassHand<Next> {
+impl<E: Edition, Next: AsMetaData<E> + Transitions + Send + Sync + From<GameMetaData<E>> + 'static> GameState<E>
+    for PassHand<E, Next>
+{
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
     fn get_next_action(
         &mut self,
         players: &Vec<usize>,
     ) -> (
         tokio::time::Duration,
-        Vec<Action<New, Event>>,
-        Option<Box<dyn GameState>>,
+        Vec<Action<New, Event<E>>>,
+        Option<Box<dyn GameState<E>>>,
     ) {
         info!("State : {:?}", self);
         let mut actions = Vec::new();
@@ -77,15 +83,15 @@ impl<Next: AsMetaData + Send + Sync + From<GameMetaData> + 'static> GameState fo
 
     fn register_message(
         &mut self,
-        _action: &Action<New, Event>,
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
+        _action: &Action<New, Event<E>>,
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         Err(Error::UnexpectedMessage)
     }
 
     fn register_response(
         &mut self,
-        action: (Event, &Action<Received, Event>),
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
+        action: (Event<E>, &Action<Received, Event<E>>),
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         let (response, action) = action;
         let (player, action) = (action.player(), action.action());
 
@@ -111,7 +117,18 @@ impl<Next: AsMetaData + Send + Sync + From<GameMetaData> + 'static> GameState fo
             _ => Ok(None),
         }
     }
-    fn metadata(&mut self) -> Option<&mut GameMetaData> {
+    fn metadata(&mut self) -> Option<&mut GameMetaData<E>> {
         Some(AsMetaData::metadata(self))
     }
 }
+
+impl<E, Next> Transitions for PassHand<E, Next>
+where
+    E: Edition,
+    Next: AsMetaData<E> + Transitions + Send + Sync + From<GameMetaData<E>> + 'static,
+{
+    const NAME: &'static str = "PassHand";
+    fn transitions() -> Vec<StateNode> {
+        vec![StateNode::of::<Syncing<E, Next>>()]
+    }
+}
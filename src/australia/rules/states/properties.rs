@@ -0,0 +1,413 @@
+//! Generative tests for the state machine.
+//!
+//! A game is played from [`DealingCards`] to [`Final`] with random, but valid, answers to
+//! every request. The answers are given in a random order and some are held back until the
+//! state has been polled again. Invalid answers, answers for players that have nothing to
+//! answer, repeated answers and unprompted messages are mixed in, every one of them has to
+//! be rejected without changing the state.
+//!
+//! Every round is scored again by a reference scorer that follows the rules as printed on
+//! the score sheet, and the two scores have to agree.
+
+use proptest::prelude::*;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use server::engine::rules::{Action, New, Received};
+
+use crate::australia::{
+    protocol,
+    rules::{
+        cards::{
+            Animal, AustraliaCard, AustralianActivity, AustralianAnimal, AustralianRegion, Card,
+            Collection,
+        },
+        config::GameConfig,
+        edition::{Edition, RoundScore},
+        invariants, AustraliaEdition, AustraliaPlayer,
+    },
+};
+
+use super::{pass::Direction, GameState};
+
+type Event = protocol::Event<AustraliaEdition>;
+type DealingCards = super::DealingCards<AustraliaEdition>;
+type State = Box<dyn GameState<AustraliaEdition>>;
+
+/// Number of polls that a game may take before it is considered stuck
+const MAX_STEPS: usize = 10_000;
+/// A player id that is never seated
+const STRANGER: usize = 100;
+/// Points for scoring an activity with 0, 1, 2 ... matching cards
+const ACTIVITY_POINTS: [usize; 7] = [0, 0, 2, 4, 7, 10, 15];
+
+/// How often the driver misbehaves, all probabilities
+#[derive(Debug, Clone, Copy)]
+struct Chaos {
+    /// Sends an invalid answer before the valid one
+    noise: f64,
+    /// Holds an answer back until the state has been polled again
+    delay: f64,
+    /// Sends the valid answer a second time
+    duplicate: f64,
+}
+
+/// What the players held when a round was scored
+#[derive(Debug)]
+struct Round {
+    players: Vec<AustraliaPlayer>,
+    activities: Vec<Option<AustralianActivity>>,
+}
+
+struct Driver {
+    rng: StdRng,
+    chaos: Chaos,
+    players: Vec<usize>,
+    state: State,
+    /// Requests that have not been answered yet
+    outstanding: Vec<Action<New, Event>>,
+    rounds: Vec<Round>,
+    final_results: usize,
+}
+
+impl Driver {
+    fn new(players: usize, config: GameConfig, seed: u64, chaos: Chaos) -> Self {
+        let players: Vec<usize> = (0..players).collect();
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            chaos,
+            state: Box::new(DealingCards::new(&players, config)),
+            players,
+            outstanding: Vec::new(),
+            rounds: Vec::new(),
+            final_results: 0,
+        }
+    }
+
+    /// Plays until the game is over, panics if it does not end
+    fn play(&mut self) {
+        for _ in 0..MAX_STEPS {
+            if self.state.finished() {
+                return;
+            }
+            let (_delay, actions, next) = self.state.get_next_action(&self.players);
+            for action in actions {
+                match action.action() {
+                    Event::FinalResult(..) => self.final_results += 1,
+                    event if needs_answer(&event) => self.outstanding.push(action),
+                    _ => {}
+                }
+            }
+            if let Some(next) = next {
+                self.enter(next);
+            }
+            let mut outstanding = std::mem::take(&mut self.outstanding);
+            outstanding.shuffle(&mut self.rng);
+            for (idx, action) in outstanding.into_iter().enumerate() {
+                // At least one answer is given every poll so that the game moves on
+                if idx > 0 && self.rng.gen_bool(self.chaos.delay) {
+                    self.outstanding.push(action);
+                } else {
+                    self.answer(action);
+                }
+            }
+        }
+        panic!("The game did not end within {} polls", MAX_STEPS);
+    }
+
+    fn enter(&mut self, next: State) {
+        self.state = next;
+        let meta = self
+            .state
+            .metadata()
+            .expect("Every state after dealing has meta data");
+        if let Err(violation) = invariants::check(meta) {
+            panic!("{}, {:?}", violation, meta);
+        }
+        if self.state.name() == "Scoring" {
+            let players = self.state.metadata().unwrap().get_players().clone();
+            self.rounds.push(Round {
+                activities: vec![None; players.len()],
+                players,
+            });
+        }
+    }
+
+    fn answer(&mut self, action: Action<New, Event>) {
+        while self.rng.gen_bool(self.chaos.noise) {
+            self.misbehave(&action);
+        }
+        let response = self.valid(&action);
+        let received = action.clone().transition().transition();
+        match self.state.register_response((response.clone(), &received)) {
+            Ok(Some(next)) => self.enter(next),
+            Ok(None) => {}
+            Err(e) => panic!(
+                "{:?} was rejected as an answer to {:?}, {:?}",
+                response, action, e
+            ),
+        }
+        if let (Event::ScoreActivity(activity), Some(round)) = (&response, self.rounds.last_mut()) {
+            round.activities[action.player()] = *activity;
+        }
+        if self.rng.gen_bool(self.chaos.duplicate) {
+            self.assert_rejected(response, &received);
+        }
+    }
+
+    /// Returns a random valid answer to the request
+    fn valid(&mut self, action: &Action<New, Event>) -> Event {
+        match action.action() {
+            Event::DiscardRequest => {
+                let hand_size = self.hand_size(action);
+                Event::Discard(self.rng.gen_range(0..hand_size))
+            }
+            Event::ShowRequest => {
+                let hand_size = self.hand_size(action);
+                Event::Show(self.rng.gen_range(0..hand_size))
+            }
+            Event::ScoreActivityQuery(options) => {
+                Event::ScoreActivity(options.get(self.rng.gen_range(0..=options.len())).copied())
+            }
+            _ => Event::Accept,
+        }
+    }
+
+    /// Sends something that the state has to reject
+    fn misbehave(&mut self, action: &Action<New, Event>) {
+        let received = action.clone().transition().transition();
+        match self.rng.gen_range(0..4) {
+            0 => {
+                let wrong = match action.action() {
+                    Event::DiscardRequest => Event::Show(0),
+                    Event::ShowRequest | Event::ScoreActivityQuery(_) => Event::Accept,
+                    _ => Event::Discard(0),
+                };
+                self.assert_rejected(wrong, &received);
+            }
+            1 => {
+                let out_of_range = match action.action() {
+                    Event::DiscardRequest => Event::Discard(self.hand_size(action)),
+                    Event::ShowRequest => Event::Show(self.hand_size(action) + 3),
+                    Event::ScoreActivityQuery(options) => {
+                        match AustralianActivity::to_vec()
+                            .into_iter()
+                            .find(|activity| !options.contains(activity))
+                        {
+                            Some(activity) => Event::ScoreActivity(Some(activity)),
+                            None => Event::Accept,
+                        }
+                    }
+                    _ => Event::ScoreActivity(None),
+                };
+                self.assert_rejected(out_of_range, &received);
+            }
+            2 => {
+                let response = self.valid(action);
+                let stranger = Action::<New, Event>::new(STRANGER, action.action());
+                self.assert_rejected(response, &stranger.transition().transition());
+            }
+            _ => {
+                let message = Action::<New, Event>::new(action.player(), self.valid(action));
+                let before = format!("{:?}", self.state);
+                assert!(self.state.register_message(&message).is_err());
+                assert_eq!(format!("{:?}", self.state), before);
+            }
+        }
+    }
+
+    fn assert_rejected(&mut self, response: Event, action: &Action<Received, Event>) {
+        let before = format!("{:?}", self.state);
+        let result = self.state.register_response((response.clone(), action));
+        assert!(
+            result.is_err(),
+            "{:?} was accepted as an answer to {:?}",
+            response,
+            action
+        );
+        assert_eq!(format!("{:?}", self.state), before);
+    }
+
+    fn hand_size(&mut self, action: &Action<New, Event>) -> usize {
+        let meta = self.state.metadata().unwrap();
+        let player = meta
+            .get_players()
+            .iter()
+            .find(|player| player.id as usize == action.player());
+        player.unwrap().hand_size()
+    }
+
+    /// Scores every round with the reference scorer and compares it to the score of the game
+    fn assert_scores(&mut self) {
+        let meta = self.state.metadata().unwrap();
+        let config = meta.config().clone();
+        let players = meta.get_players().clone();
+        assert_eq!(self.rounds.len(), config.rounds);
+        assert_eq!(self.final_results, players.len());
+
+        let mut unclaimed = AustralianRegion::to_vec();
+        let mut visited: Vec<Vec<char>> = vec![Vec::new(); players.len()];
+        for (idx, round) in self.rounds.iter().enumerate() {
+            let mut completed = Vec::new();
+            for (uid, player) in round.players.iter().enumerate() {
+                let (parts, regions) =
+                    reference_score(player, &mut visited[uid], &unclaimed, round.activities[uid]);
+                let score = &players[uid].scores()[idx];
+                let found: Vec<usize> = score
+                    .parts()
+                    .into_iter()
+                    .map(|(_, points)| points)
+                    .collect();
+                assert_eq!(
+                    found, parts,
+                    "round {} of player {}, {:?}",
+                    idx, uid, player
+                );
+                assert_eq!(score.completed_regions(), regions);
+                completed.extend(regions);
+            }
+            unclaimed.retain(|region| !completed.contains(region));
+        }
+    }
+}
+
+fn needs_answer(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::Deal(_)
+            | Event::Sync(_)
+            | Event::ReassignHand(_)
+            | Event::DiscardRequest
+            | Event::ShowRequest
+            | Event::ScoreActivityQuery(_)
+    )
+}
+
+/// Scores a round as the score sheet describes it
+///
+/// Returns the throw and catch, tourist site, collection, animal and activity points in that
+/// order, along with the regions that were completed. The sites of the round are added to
+/// `visited`.
+fn reference_score(
+    player: &AustraliaPlayer,
+    visited: &mut Vec<char>,
+    unclaimed: &[AustralianRegion],
+    activity: Option<AustralianActivity>,
+) -> (Vec<usize>, Vec<AustralianRegion>) {
+    let throw = player.discard_pile[0];
+    let catch = player.hand[0];
+    let cards: Vec<AustraliaCard> = player
+        .discard_pile
+        .iter()
+        .chain(&player.show_pile)
+        .chain(&player.hand)
+        .copied()
+        .collect();
+
+    let throw_catch = throw.number().abs_diff(catch.number());
+
+    let mut sites = 0;
+    for card in &cards {
+        if !visited.contains(&card.to_char()) {
+            visited.push(card.to_char());
+            sites += 1;
+        }
+    }
+    let deck = AustraliaEdition::deck().cards();
+    let regions: Vec<AustralianRegion> = unclaimed
+        .iter()
+        .filter(|region| {
+            deck.iter()
+                .filter(|card| card.region() == **region)
+                .all(|card| visited.contains(&card.to_char()))
+        })
+        .copied()
+        .collect();
+    sites += 3 * regions.len();
+
+    let collected: usize = cards
+        .iter()
+        .filter_map(|card| card.collection())
+        .map(|c| c.score())
+        .sum();
+    let collections = if collected <= 7 {
+        2 * collected
+    } else {
+        collected
+    };
+
+    let animals = AustralianAnimal::to_vec()
+        .into_iter()
+        .map(|animal| {
+            let count = cards
+                .iter()
+                .filter(|card| card.animal() == Some(animal))
+                .count();
+            count / 2 * animal.score()
+        })
+        .sum();
+
+    let activity = match activity {
+        Some(activity) => {
+            ACTIVITY_POINTS[cards
+                .iter()
+                .filter(|card| card.activity() == Some(activity))
+                .count()]
+        }
+        None => 0,
+    };
+
+    (
+        vec![throw_catch, sites, collections, animals, activity],
+        regions,
+    )
+}
+
+fn config(rounds: usize, hand_size: usize, backward: Vec<bool>, seed: u64) -> GameConfig {
+    GameConfig {
+        rounds,
+        hand_size,
+        pass_sequence: backward[..hand_size - 1]
+            .iter()
+            .map(|backward| match backward {
+                true => Direction::Backward,
+                false => Direction::Forward,
+            })
+            .collect(),
+        seed: Some(seed),
+        ..Default::default()
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_valid_answers_finish(
+        players in 2usize..=4,
+        rounds in 1usize..=4,
+        hand_size in 3usize..=7,
+        backward in proptest::collection::vec(any::<bool>(), 6),
+        seed in any::<u64>(),
+        delay in 0.0..0.9f64,
+    ) {
+        let chaos = Chaos { noise: 0.0, delay, duplicate: 0.0 };
+        let mut driver = Driver::new(players, config(rounds, hand_size, backward, seed), seed, chaos);
+        driver.play();
+        driver.assert_scores();
+    }
+
+    #[test]
+    fn test_invalid_answers_are_rejected(
+        players in 2usize..=4,
+        hand_size in 3usize..=7,
+        backward in proptest::collection::vec(any::<bool>(), 6),
+        seed in any::<u64>(),
+        noise in 0.0..0.7f64,
+        delay in 0.0..0.5f64,
+        duplicate in 0.0..1.0f64,
+    ) {
+        let chaos = Chaos { noise, delay, duplicate };
+        let mut driver = Driver::new(players, config(2, hand_size, backward, seed), seed, chaos);
+        driver.play();
+        driver.assert_scores();
+    }
+}
@@ -3,20 +3,20 @@
 //! typically done at either the end of a round
 //! or the end of the game.
 //! 
-use log::info;
+use tracing::info;
 
 use crate::{
     australia::{
         protocol::Event,
-        rules::{meta::GameMetaData, states::Final},
+        rules::{edition::Edition, meta::GameMetaData, states::Final},
     },
     engine::rules::{Action, Error, New, Received},
 };
 
-use super::{AsMetaData, DealingCards, GameState, Scoring, Syncing};
+use super::{AsMetaData, DealingCards, GameState, Scoring, StateNode, Syncing, Transitions};
 
-impl Scoring {
-    pub fn new(state: GameMetaData) -> Self {
+impl<E: Edition> Scoring<E> {
+    pub fn new(state: GameMetaData<E>) -> Self {
         Self {
             state,
             pending: Vec::new(),
@@ -26,20 +26,23 @@ impl Scoring {
     }
 }
 
-impl From<GameMetaData> for Scoring {
-    fn from(value: GameMetaData) -> Self {
+impl<E: Edition> From<GameMetaData<E>> for Scoring<E> {
+    fn from(value: GameMetaData<E>) -> Self {
         Self::new(value)
     }
 }
 
-impl GameState for Scoring {
+impl<E: Edition> GameState<E> for Scoring<E> {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
     fn get_next_action(
         &mut self,
         players: &Vec<usize>,
     ) -> (
         tokio::time::Duration,
-        Vec<Action<New, Event>>,
-        Option<Box<dyn GameState>>,
+        Vec<Action<New, Event<E>>>,
+        Option<Box<dyn GameState<E>>>,
     ) {
         info!("State : {:?}", self);
         let mut actions = Vec::new();
@@ -91,15 +94,15 @@ impl GameState for Scoring {
 
     fn register_message(
         &mut self,
-        _action: &Action<New, Event>,
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
+        _action: &Action<New, Event<E>>,
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         Err(Error::UnexpectedMessage)
     }
 
     fn register_response(
         &mut self,
-        action: (Event, &Action<Received, Event>),
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
+        action: (Event<E>, &Action<Received, Event<E>>),
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         let (response, action) = action;
         let (player, action) = (action.player(), action.action());
 
@@ -143,7 +146,18 @@ impl GameState for Scoring {
             _ => Err(Error::UnexpectedMessage),
         }
     }
-    fn metadata(&mut self) -> Option<&mut GameMetaData> {
+    fn metadata(&mut self) -> Option<&mut GameMetaData<E>> {
         Some(AsMetaData::metadata(self))
     }
 }
+
+impl<E: Edition> Transitions for Scoring<E> {
+    const NAME: &'static str = "Scoring";
+    /// The game ends after the last round, otherwise a new round is dealt
+    fn transitions() -> Vec<StateNode> {
+        vec![
+            StateNode::of::<Syncing<E, DealingCards<E>>>(),
+            StateNode::of::<Final<E>>(),
+        ]
+    }
+}
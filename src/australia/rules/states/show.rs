@@ -1,16 +1,16 @@
 //! Defines a state where the player is asked to show a card
 //! and then that pile of show cards is shown to the rest of the players.
 
-use log::info;
+use tracing::{debug, info};
 
 use crate::{
-    engine::rules::{Action, Error, New, Received}, australia::{rules::meta::GameMetaData, protocol::Event},
+    engine::rules::{Action, Error, New, Received}, australia::{rules::{edition::Edition, meta::GameMetaData}, protocol::Event},
 };
 
-use super::{pass::Direction, GameState, PassHand, AsMetaData, Scoring, ShowCard};
+use super::{AsMetaData, GameState, PassHand, ShowCard, StateNode, Transitions};
 
-impl ShowCard {
-    pub fn new(state: GameMetaData) -> Self {
+impl<E: Edition> ShowCard<E> {
+    pub fn new(state: GameMetaData<E>) -> Self {
         Self {
             state,
             pending: Vec::new(),
@@ -19,24 +19,27 @@ impl ShowCard {
     }
 }
 
-impl From<GameMetaData> for ShowCard {
-    fn from(value: GameMetaData) -> Self {
+impl<E: Edition> From<GameMetaData<E>> for ShowCard<E> {
+    fn from(value: GameMetaData<E>) -> Self {
         Self::new(value)
     }
 }
 
-impl GameState for ShowCard {
+impl<E: Edition> GameState<E> for ShowCard<E> {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
     fn get_next_action(
         &mut self,
         players: &Vec<usize>,
     ) -> (
         tokio::time::Duration,
-        Vec<Action<New, Event>>,
-        Option<Box<dyn GameState>>,
+        Vec<Action<New, Event<E>>>,
+        Option<Box<dyn GameState<E>>>,
     ) {
         info!("State : {:?}", self);
         let mut actions = Vec::new();
-        let mut request = |event: Event| {
+        let mut request = |event: Event<E>| {
             for player in players {
                 if !self.pending.contains(&(*player as u8)) {
                     actions.push(Action::new(*player, event.clone()))
@@ -75,23 +78,24 @@ impl GameState for ShowCard {
                     }
                 }
             }
+            let direction = self.state.next_pass();
             if self.state.hands_singleton() {
                 // Now we move to scoring
                 return (
                     tokio::time::Duration::from_millis(500),
                     actions,
-                    Some(Box::new(PassHand::<Scoring>::new(
+                    Some(Box::new(PassHand::<E, E::RoundScoring>::new(
                         self.state.clone(),
-                        Direction::Backward,
+                        direction,
                     ))),
                 );
             } else {
                 return (
                     tokio::time::Duration::from_millis(500),
                     actions,
-                    Some(Box::new(PassHand::<ShowCard>::new(
+                    Some(Box::new(PassHand::<E, ShowCard<E>>::new(
                         self.state.clone(),
-                        Direction::Forward,
+                        direction,
                     ))),
                 );
             }
@@ -101,20 +105,19 @@ impl GameState for ShowCard {
 
     fn register_message(
         &mut self,
-        _action: &Action<New, Event>,
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
+        _action: &Action<New, Event<E>>,
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         Err(Error::UnexpectedMessage)
     }
 
     fn register_response(
         &mut self,
-        action: (Event, &Action<Received, Event>),
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
-        println!("{:?}", action);
+        action: (Event<E>, &Action<Received, Event<E>>),
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         let (response, action) = action;
         let (player, action) = (action.player(), action.action());
 
-        println!("{:?},{:?},{:?}", response, player, action);
+        debug!(?response, uid = player, ?action, "Registering response");
 
         let mut outstanding_request = None;
         for (idx, &id) in self.pending.iter().enumerate() {
@@ -141,7 +144,18 @@ impl GameState for ShowCard {
             _ => Err(Error::UnexpectedResponse),
         }
     }
-    fn metadata(&mut self) -> Option<&mut GameMetaData> {
+    fn metadata(&mut self) -> Option<&mut GameMetaData<E>> {
         Some(AsMetaData::metadata(self))
     }
 }
+
+impl<E: Edition> Transitions for ShowCard<E> {
+    const NAME: &'static str = "ShowCard";
+    /// The hand is passed on to the next card, or to scoring after the last card
+    fn transitions() -> Vec<StateNode> {
+        vec![
+            StateNode::of::<PassHand<E, ShowCard<E>>>(),
+            StateNode::of::<PassHand<E, E::RoundScoring>>(),
+        ]
+    }
+}
@@ -1,15 +1,15 @@
 //! Defines a state where the server sends it current state of the player to that player
 //! to ensure that they are not out of sync.
-use log::info;
+use tracing::info;
 
 use crate::{
-    engine::rules::{Action, Error, New, Received}, australia::{rules::meta::GameMetaData, protocol::Event},
+    engine::rules::{Action, Error, New, Received}, australia::{rules::{edition::Edition, meta::GameMetaData}, protocol::Event},
 };
 
-use super::{GameState, AsMetaData, Syncing};
+use super::{AsMetaData, GameState, StateNode, Syncing, Transitions};
 
-impl<Next: AsMetaData + Send + Sync> Syncing<Next> {
-    pub fn new(state: GameMetaData, next_state: Box<Next>) -> Self {
+impl<E: Edition, Next: AsMetaData<E> + Send + Sync> Syncing<E, Next> {
+    pub fn new(state: GameMetaData<E>, next_state: Box<Next>) -> Self {
         Self {
             state,
             pending: Vec::new(),
@@ -18,14 +18,17 @@ impl<Next: AsMetaData + Send + Sync> Syncing<Next> {
         }
     }
 }
-impl<Next: AsMetaData + Send + Sync + 'static> GameState for Syncing<Next> {
+impl<E: Edition, Next: AsMetaData<E> + Transitions + Send + Sync + 'static> GameState<E> for Syncing<E, Next> {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
     fn get_next_action(
         &mut self,
         _players: &Vec<usize>,
     ) -> (
         tokio::time::Duration,
-        Vec<Action<New, Event>>,
-        Option<Box<dyn GameState>>,
+        Vec<Action<New, Event<E>>>,
+        Option<Box<dyn GameState<E>>>,
     ) {
         info!("State : {:?}", self);
         let mut actions = Vec::new();
@@ -52,15 +55,15 @@ impl<Next: AsMetaData + Send + Sync + 'static> GameState for Syncing<Next> {
 
     fn register_message(
         &mut self,
-        _action: &Action<New, Event>,
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
+        _action: &Action<New, Event<E>>,
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         Err(Error::UnexpectedMessage)
     }
 
     fn register_response(
         &mut self,
-        action: (Event, &Action<Received, Event>),
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
+        action: (Event<E>, &Action<Received, Event<E>>),
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         let (response, action) = action;
         let (player, action) = (action.player(), action.action());
 
@@ -92,7 +95,16 @@ impl<Next: AsMetaData + Send + Sync + 'static> GameState for Syncing<Next> {
             _ => Err(Error::UnexpectedMessage),
         }
     }
-    fn metadata(&mut self) -> Option<&mut GameMetaData> {
+    fn metadata(&mut self) -> Option<&mut GameMetaData<E>> {
         Some(&mut self.state)
     }
 }
+
+impl<E: Edition, Next: AsMetaData<E> + Transitions + Send + Sync + 'static> Transitions
+    for Syncing<E, Next>
+{
+    const NAME: &'static str = "Syncing";
+    fn transitions() -> Vec<StateNode> {
+        vec![StateNode::of::<Next>()]
+    }
+}
@@ -1,44 +1,50 @@
 //! Defines a waiting state, this is the initial state although it could be used at later stages too.
 
-use log::info;
+use std::marker::PhantomData;
+
+use tracing::info;
 
 use crate::{
-    engine::rules::{Action, Error, New, Received}, australia::{protocol::Event, rules::meta::GameMetaData},
+    engine::rules::{Action, Error, New, Received}, australia::{protocol::Event, rules::{config::GameConfig, edition::Edition, meta::GameMetaData}},
 };
 
-use super::{DealingCards, GameState, AsMetaData, WaitingForPlayers};
+use super::{AsMetaData, DealingCards, GameState, StateNode, Transitions, WaitingForPlayers};
 
-impl<Next: AsMetaData + Send + 'static> WaitingForPlayers<Next> {
-    pub fn new(next_state: Option<Box<Next>>) -> Self {
+impl<E: Edition, Next: AsMetaData<E> + Send + 'static> WaitingForPlayers<E, Next> {
+    pub fn new(next_state: Option<Box<Next>>, config: GameConfig) -> Self {
         Self {
             ready: Vec::new(),
             pending_ready: Vec::new(),
             next_state: next_state,
+            config,
+            edition: PhantomData,
         }
     }
 }
 
-impl<Next: AsMetaData + Send + 'static> GameState for WaitingForPlayers<Next> {
+impl<E: Edition, Next: AsMetaData<E> + Transitions + Send + 'static> GameState<E> for WaitingForPlayers<E, Next> {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
     fn get_next_action(
         &mut self,
         players: &Vec<usize>,
     ) -> (
         tokio::time::Duration,
-        Vec<Action<New, Event>>,
-        Option<Box<dyn GameState>>,
+        Vec<Action<New, Event<E>>>,
+        Option<Box<dyn GameState<E>>>,
     ) {
         info!("State : {:?}", self);
-        println!("In waiting state");
 
         let mut actions = Vec::new();
 
         if players.len() < 2 {
             for player in players {
-                actions.push(Action::<New, Event>::new(*player, Event::WaitingForPlayers));
+                actions.push(Action::<New, Event<E>>::new(*player, Event::WaitingForPlayers));
             }
         } else if players.len() > 4 {
             for player in players {
-                actions.push(Action::<New, Event>::new(*player, Event::LobbyFull));
+                actions.push(Action::<New, Event<E>>::new(*player, Event::LobbyFull));
             }
         } else {
             // We have enough players, this means that all players need to be ready.
@@ -47,21 +53,31 @@ impl<Next: AsMetaData + Send + 'static> GameState for WaitingForPlayers<Next> {
                 // Go to the next state, this is likely the dealing cards state but if some
                 // players disconnected it might be another state
                 let state = std::mem::replace(&mut self.next_state, None);
-                return (
-                    tokio::time::Duration::from_millis(1000),
-                    actions,
-                    Some(match state {
-                        Some(state) => state,
-                        None => Box::new(DealingCards::new(players)),
-                    }),
-                );
+                let mut next: Box<dyn GameState<E>> = match state {
+                    Some(state) => state,
+                    None => Box::new(DealingCards::<E>::new(players, self.config.clone())),
+                };
+                // The players score their moves with the config, the seed stays on the server
+                let config = GameConfig {
+                    seed: None,
+                    ..self.config.clone()
+                };
+                let seats: Vec<u8> = next
+                    .metadata()
+                    .map(|meta| meta.get_players().iter().map(|player| player.id()).collect())
+                    .unwrap_or_default();
+                for player in players {
+                    actions.push(Action::<New, Event<E>>::new(*player, Event::Config(config.clone())));
+                    actions.push(Action::<New, Event<E>>::new(*player, Event::Seats(seats.clone())));
+                }
+                return (tokio::time::Duration::from_millis(1000), actions, Some(next));
             }
             for player in players {
                 if !self.ready.contains(&(*player as u8))
                     && !self.pending_ready.contains(&(*player as u8))
                 {
                     // This player is not ready
-                    actions.push(Action::<New, Event>::new(*player, Event::ReadyCheck));
+                    actions.push(Action::<New, Event<E>>::new(*player, Event::ReadyCheck));
                     self.pending_ready.push(*player as u8);
                 }
             }
@@ -71,15 +87,15 @@ impl<Next: AsMetaData + Send + 'static> GameState for WaitingForPlayers<Next> {
 
     fn register_message(
         &mut self,
-        _action: &Action<New, Event>,
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
+        _action: &Action<New, Event<E>>,
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         Err(Error::UnexpectedMessage)
     }
 
     fn register_response(
         &mut self,
-        action: (Event, &Action<Received, Event>),
-    ) -> Result<Option<Box<dyn GameState>>, Error> {
+        action: (Event<E>, &Action<Received, Event<E>>),
+    ) -> Result<Option<Box<dyn GameState<E>>>, Error> {
         // This state can only handle connected or ready checks
         let (response, request) = action;
 
@@ -122,7 +138,20 @@ impl<Next: AsMetaData + Send + 'static> GameState for WaitingForPlayers<Next> {
         };
         Ok(None)
     }
-    fn metadata(&mut self) -> Option<&mut GameMetaData> {
+    fn metadata(&mut self) -> Option<&mut GameMetaData<E>> {
         None
     }
+    /// In between the games of a match the seats are taken, no new players are seated
+    fn accepts_players(&self) -> bool {
+        self.next_state.is_none()
+    }
+}
+
+impl<E: Edition, Next: AsMetaData<E> + Transitions + Send + 'static> Transitions
+    for WaitingForPlayers<E, Next>
+{
+    const NAME: &'static str = "WaitingForPlayers";
+    fn transitions() -> Vec<StateNode> {
+        vec![StateNode::of::<Next>()]
+    }
 }
@@ -0,0 +1,373 @@
+//! Defines the score sheet of a finished game.
+//!
+//! The sheet mirrors the printed scorecard, every player has one row per round with a
+//! column for each part of the score, the regions that the player completed and the
+//! round total, followed by a row with the totals of the game. A server started with
+//! `--score-sheets <dir>` writes the sheet of every finished game to the directory, as
+//! JSON, CSV or Markdown.
+
+use std::{
+    fs::OpenOptions,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    profiles::GameRecord,
+    rules::edition::{Edition, RoundScore},
+};
+
+/// Name of the column with the completed regions
+const REGIONS: &str = "Completed regions";
+/// Name of the column with the sum of the other columns
+const TOTAL: &str = "Total";
+/// The number of names that are tried for a score sheet before giving up
+const MAX_ATTEMPTS: usize = 100;
+
+/// The formats that a [`ScoreSheet`] can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum SheetFormat {
+    #[default]
+    Json,
+    /// One `player,nickname,place,round,...` row per round, and one with the totals
+    Csv,
+    /// One table per player
+    Markdown,
+}
+
+impl SheetFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            SheetFormat::Json => "json",
+            SheetFormat::Csv => "csv",
+            SheetFormat::Markdown => "md",
+        }
+    }
+}
+
+/// The scores of a player in one round, or in the whole game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SheetRow {
+    /// The parts of the score, in the order of [`ScoreSheet::columns`]
+    pub scores: Vec<usize>,
+    pub completed_regions: Vec<String>,
+    pub total: usize,
+}
+
+/// The column of a player on the score sheet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SheetPlayer {
+    pub player: u8,
+    pub nickname: Option<String>,
+    /// The place in the final standings, tied players have the same place
+    pub place: usize,
+    /// One row per round, the first round first
+    pub rounds: Vec<SheetRow>,
+    pub total: SheetRow,
+}
+
+/// The score sheet of a finished game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreSheet {
+    /// The seed that the match was shuffled with
+    pub seed: u64,
+    /// The number of the game in its match, starting at 1
+    pub game: usize,
+    /// The names of the parts of the score
+    pub columns: Vec<String>,
+    /// The players in the order of the final standings
+    pub players: Vec<SheetPlayer>,
+}
+
+impl ScoreSheet {
+    pub fn new<E: Edition>(record: &GameRecord<E::Score>) -> Self {
+        // Every category that was scored by any player in any round
+        let mut columns: Vec<String> = Vec::new();
+        for score in record.seats.iter().flat_map(|seat| seat.rounds.iter()) {
+            for (name, _) in score.parts() {
+                if !columns.iter().any(|column| column == name) {
+                    columns.push(name.to_owned());
+                }
+            }
+        }
+        let players = record
+            .seats
+            .iter()
+            .map(|seat| SheetPlayer {
+                player: seat.player,
+                nickname: seat.nickname.clone(),
+                place: seat.place,
+                rounds: seat
+                    .rounds
+                    .iter()
+                    .map(|score| row::<E>(score, &columns))
+                    .collect(),
+                total: row::<E>(&seat.rounds.iter().cloned().sum(), &columns),
+            })
+            .collect();
+        Self {
+            seed: record.seed,
+            game: record.game,
+            columns,
+            players,
+        }
+    }
+
+    pub fn write(&self, format: SheetFormat) -> String {
+        match format {
+            // Only plain numbers and strings are serialized
+            SheetFormat::Json => serde_json::to_string_pretty(self).unwrap(),
+            SheetFormat::Csv => self.csv(),
+            SheetFormat::Markdown => self.markdown(),
+        }
+    }
+
+    /// Writes the sheet to a new file in `dir` and returns the path of the file
+    ///
+    /// The file is named after the time that it was written, the seed and the game. Sheets
+    /// that would get the same name, such as the games of two lobbies finishing with the same
+    /// seed in the same second, are numbered. An existing file is never overwritten.
+    pub fn save(&self, dir: &Path, format: SheetFormat) -> std::io::Result<PathBuf> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_secs())
+            .unwrap_or_default();
+        std::fs::create_dir_all(dir)?;
+        let name = format!("{}-{}-{}", now, self.seed, self.game);
+        for attempt in 0..MAX_ATTEMPTS {
+            let path = match attempt {
+                0 => dir.join(format!("{}.{}", name, format.extension())),
+                _ => dir.join(format!("{}-{}.{}", name, attempt, format.extension())),
+            };
+            let mut file = match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => file,
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            };
+            file.write_all(self.write(format).as_bytes())?;
+            return Ok(path);
+        }
+        Err(std::io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!("the first {} names for {} are taken", MAX_ATTEMPTS, name),
+        ))
+    }
+
+    fn csv(&self) -> String {
+        let mut header = vec!["player", "nickname", "place", "round"];
+        header.extend(self.columns.iter().map(String::as_str));
+        header.extend([REGIONS, TOTAL]);
+        let mut rows = vec![header
+            .into_iter()
+            .map(csv_field)
+            .collect::<Vec<_>>()
+            .join(",")];
+        for player in &self.players {
+            let nickname = player.nickname.clone().unwrap_or_default();
+            let numbered = player.rounds.iter().enumerate();
+            let rounds = numbered.map(|(idx, row)| ((idx + 1).to_string(), row));
+            for (round, row) in rounds.chain([(TOTAL.to_lowercase(), &player.total)]) {
+                let mut fields = vec![
+                    player.player.to_string(),
+                    csv_field(&nickname),
+                    player.place.to_string(),
+                    round,
+                ];
+                fields.extend(row.scores.iter().map(usize::to_string));
+                fields.push(csv_field(&row.completed_regions.join(";")));
+                fields.push(row.total.to_string());
+                rows.push(fields.join(","));
+            }
+        }
+        rows.push(String::new());
+        rows.join("\n")
+    }
+
+    fn markdown(&self) -> String {
+        let mut lines = vec![format!("# Game {} (seed {})", self.game, self.seed)];
+        let mut header = vec!["Round"];
+        header.extend(self.columns.iter().map(String::as_str));
+        header.extend([REGIONS, TOTAL]);
+        for player in &self.players {
+            let name = match &player.nickname {
+                Some(nickname) => format!("{} (player {})", markdown_cell(nickname), player.player),
+                None => format!("Player {}", player.player),
+            };
+            lines.push(String::new());
+            lines.push(format!("## #{} {}", player.place, name));
+            lines.push(String::new());
+            lines.push(format!("| {} |", header.join(" | ")));
+            lines.push(format!("|{}", "---|".repeat(header.len())));
+            for (idx, row) in player.rounds.iter().enumerate() {
+                lines.push(markdown_row(&(idx + 1).to_string(), row, false));
+            }
+            lines.push(markdown_row(TOTAL, &player.total, true));
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+/// Returns the row of a score, a column that the score has no part for is 0
+fn row<E: Edition>(score: &E::Score, columns: &[String]) -> SheetRow {
+    let parts = score.parts();
+    SheetRow {
+        scores: columns
+            .iter()
+            .map(|column| {
+                parts
+                    .iter()
+                    .find(|(name, _)| name == column)
+                    .map(|(_, score)| *score)
+                    .unwrap_or_default()
+            })
+            .collect(),
+        completed_regions: score
+            .completed_regions()
+            .iter()
+            .map(|region| format!("{:?}", region))
+            .collect(),
+        total: score.total_score(),
+    }
+}
+
+/// Quotes a field that holds a separator or a quote
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_owned(),
+    }
+}
+
+fn markdown_cell(cell: &str) -> String {
+    cell.replace('|', "\\|")
+}
+
+fn markdown_row(round: &str, row: &SheetRow, bold: bool) -> String {
+    let mut cells = vec![round.to_owned()];
+    cells.extend(row.scores.iter().map(usize::to_string));
+    cells.push(row.completed_regions.join(", "));
+    cells.push(row.total.to_string());
+    if bold {
+        cells = cells
+            .into_iter()
+            .map(|cell| match cell.is_empty() {
+                true => cell,
+                false => format!("**{}**", cell),
+            })
+            .collect();
+    }
+    format!("| {} |", cells.join(" | "))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::australia::{
+        profiles::{GameRecord, Seat},
+        rules::{cards::AustralianRegion, scoring::AustraliaScoring, AustraliaEdition},
+    };
+
+    use super::{ScoreSheet, SheetFormat};
+
+    fn record() -> GameRecord<AustraliaScoring> {
+        GameRecord {
+            seed: 42,
+            game: 1,
+            seats: vec![
+                Seat {
+                    player: 1,
+                    nickname: Some("Ana, \"the\" | best".to_owned()),
+                    place: 1,
+                    rounds: vec![
+                        AustraliaScoring::from_values(
+                            3,
+                            4,
+                            10,
+                            0,
+                            0,
+                            vec![AustralianRegion::Victoria],
+                        ),
+                        AustraliaScoring::from_values(2, 3, 6, 0, 0, Vec::new()),
+                    ],
+                },
+                Seat {
+                    player: 0,
+                    nickname: None,
+                    place: 2,
+                    rounds: vec![
+                        AustraliaScoring::from_values(1, 2, 4, 0, 0, Vec::new()),
+                        AustraliaScoring::from_values(0, 5, 2, 0, 0, Vec::new()),
+                    ],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_sheet() {
+        let sheet = ScoreSheet::new::<AustraliaEdition>(&record());
+        assert_eq!(sheet.columns.len(), 5);
+        assert_eq!(sheet.columns[0], "Throw Catch");
+        assert_eq!(sheet.players[0].rounds.len(), 2);
+        assert_eq!(sheet.players[0].rounds[0].scores, vec![3, 4, 10, 0, 0]);
+        assert_eq!(
+            sheet.players[0].rounds[0].completed_regions,
+            vec!["Victoria"]
+        );
+        assert_eq!(sheet.players[0].total.scores, vec![5, 7, 16, 0, 0]);
+        assert_eq!(sheet.players[0].total.total, 28);
+        assert_eq!(sheet.players[1].total.total, 14);
+    }
+
+    #[test]
+    fn test_formats() {
+        let sheet = ScoreSheet::new::<AustraliaEdition>(&record());
+
+        let json = sheet.write(SheetFormat::Json);
+        assert_eq!(serde_json::from_str::<ScoreSheet>(&json).unwrap(), sheet);
+
+        let csv = sheet.write(SheetFormat::Csv);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            rows[0],
+            "player,nickname,place,round,Throw Catch,Tourist sites,Collections,Animals,Activity,Completed regions,Total"
+        );
+        assert_eq!(
+            rows[1],
+            "1,\"Ana, \"\"the\"\" | best\",1,1,3,4,10,0,0,Victoria,17"
+        );
+        assert_eq!(
+            rows[3],
+            "1,\"Ana, \"\"the\"\" | best\",1,total,5,7,16,0,0,Victoria,28"
+        );
+        assert_eq!(rows[6], "0,,2,total,1,7,6,0,0,,14");
+        assert_eq!(rows.len(), 7);
+
+        let markdown = sheet.write(SheetFormat::Markdown);
+        assert!(markdown.starts_with("# Game 1 (seed 42)"));
+        assert!(markdown.contains("## #1 Ana, \"the\" \\| best (player 1)"));
+        assert!(markdown.contains("| 1 | 3 | 4 | 10 | 0 | 0 | Victoria | 17 |"));
+        assert!(
+            markdown.contains("| **Total** | **1** | **7** | **6** | **0** | **0** |  | **14** |")
+        );
+    }
+
+    #[test]
+    fn test_save() {
+        let dir = std::env::temp_dir().join(format!("boomerang-sheets-{}", std::process::id()));
+        let sheet = ScoreSheet::new::<AustraliaEdition>(&record());
+        // The same game saved twice in the same second gets two files
+        let first = sheet.save(&dir, SheetFormat::Json).unwrap();
+        let second = sheet.save(&dir, SheetFormat::Json).unwrap();
+        assert_ne!(first, second);
+        for path in [&first, &second] {
+            let saved = std::fs::read_to_string(path).unwrap();
+            assert_eq!(serde_json::from_str::<ScoreSheet>(&saved).unwrap(), sheet);
+        }
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,549 @@
+//! Defines an offline simulation of many games between bots.
+//!
+//! The games are played on the [`states`](super::rules::states) directly, without a lobby or
+//! sockets, every seat is played by an [`Autopilot`]. The results are collected in a
+//! [`Report`] that can be written as JSON or CSV.
+
+use std::collections::HashMap;
+
+use clap::ValueEnum;
+use serde::Serialize;
+use server::engine::rules::Error;
+use tracing::{debug, error};
+
+use super::{
+    bot::{Autopilot, Difficulty},
+    protocol::Event,
+    rules::{
+        config::{ConfigError, GameConfig},
+        edition::{Edition, RoundScore},
+        invariants,
+        ranking::Placement,
+        states::{diagram::StateMachine, DealingCards, GameState, WaitingForPlayers},
+        Player,
+    },
+    MAX_PLAYERS,
+};
+
+/// Number of actions that a game may take before it is considered stuck
+const MAX_STEPS: usize = 10_000;
+/// Name of the total score in the score distributions
+const TOTAL: &str = "Total";
+
+/// Settings for a simulation, collected from the command line
+#[derive(Debug, Clone)]
+pub struct SimulationOptions {
+    pub games: usize,
+    pub players: usize,
+    /// The strategies that are seated, repeated until every seat is taken
+    pub strategies: Vec<Difficulty>,
+    pub config: GameConfig,
+    /// Seed of the first game, every following game adds one to it
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug)]
+pub enum SimulationError {
+    /// The game config can not be played
+    Config(ConfigError),
+    /// A game needs two to four players
+    Players { players: usize, max: usize },
+    /// No strategy was given to seat
+    NoStrategies,
+    /// The rules rejected a move
+    Rules { game: usize, error: Error },
+    /// The game did not end within [`MAX_STEPS`] actions
+    Stalled { game: usize },
+    /// A state transition broke one of the game [`invariants`]
+    Invariant { game: usize, violation: String },
+}
+
+impl std::fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Config(e) => write!(f, "invalid game config, {}", e),
+            Self::Players { players, max } => {
+                write!(f, "{} players can not play, a game needs 2 to {}", players, max)
+            }
+            Self::NoStrategies => write!(f, "at least one strategy has to be given"),
+            Self::Rules { game, error } => write!(f, "game {} broke the rules, {:?}", game, error),
+            Self::Stalled { game } => write!(f, "game {} did not end", game),
+            Self::Invariant { game, violation } => {
+                write!(f, "game {} broke an invariant, {}", game, violation)
+            }
+        }
+    }
+}
+
+/// Summary of a score category
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Distribution {
+    pub category: String,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: usize,
+    pub median: usize,
+    pub max: usize,
+}
+
+impl Distribution {
+    fn new(category: &str, mut values: Vec<usize>) -> Self {
+        values.sort();
+        let count = values.len().max(1) as f64;
+        let mean = values.iter().sum::<usize>() as f64 / count;
+        let variance = values
+            .iter()
+            .map(|value| (*value as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        Self {
+            category: category.to_owned(),
+            mean,
+            std_dev: variance.sqrt(),
+            min: values.first().copied().unwrap_or(0),
+            median: values.get(values.len() / 2).copied().unwrap_or(0),
+            max: values.last().copied().unwrap_or(0),
+        }
+    }
+}
+
+/// Results of every seat that was played by one strategy
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StrategyReport {
+    pub strategy: String,
+    /// Number of seats that the strategy played
+    pub seats: usize,
+    pub wins: usize,
+    pub win_rate: f64,
+    /// Final scores per category, the total comes last
+    pub scores: Vec<Distribution>,
+}
+
+/// How often a region was completed by at least one player in a game
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RegionReport {
+    pub region: String,
+    pub completed: usize,
+    pub rate: f64,
+}
+
+/// The points that an activity gave the rounds that it was scored in
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActivityReport {
+    pub activity: String,
+    pub scored: usize,
+    pub mean: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub games: usize,
+    pub players: usize,
+    pub strategies: Vec<StrategyReport>,
+    pub regions: Vec<RegionReport>,
+    pub activities: Vec<ActivityReport>,
+}
+
+/// The formats that a [`Report`] can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum ReportFormat {
+    #[default]
+    Json,
+    /// One `section,strategy,name,statistic,value` row per number
+    Csv,
+}
+
+impl Report {
+    pub fn write(&self, format: ReportFormat) -> String {
+        match format {
+            // Only plain numbers and strings are serialized
+            ReportFormat::Json => serde_json::to_string_pretty(self).unwrap(),
+            ReportFormat::Csv => self.csv(),
+        }
+    }
+
+    fn csv(&self) -> String {
+        let mut rows = vec!["section,strategy,name,statistic,value".to_owned()];
+        let mut row = |section: &str, strategy: &str, name: &str, statistic: &str, value: String| {
+            rows.push(format!("{},{},{},{},{}", section, strategy, name, statistic, value))
+        };
+        for strategy in &self.strategies {
+            let name = strategy.strategy.as_str();
+            row("result", name, "", "seats", strategy.seats.to_string());
+            row("result", name, "", "wins", strategy.wins.to_string());
+            row("result", name, "", "win_rate", strategy.win_rate.to_string());
+            for score in &strategy.scores {
+                let category = score.category.as_str();
+                row("score", name, category, "mean", score.mean.to_string());
+                row("score", name, category, "std_dev", score.std_dev.to_string());
+                row("score", name, category, "min", score.min.to_string());
+                row("score", name, category, "median", score.median.to_string());
+                row("score", name, category, "max", score.max.to_string());
+            }
+        }
+        for region in &self.regions {
+            row("region", "", &region.region, "completed", region.completed.to_string());
+            row("region", "", &region.region, "rate", region.rate.to_string());
+        }
+        for activity in &self.activities {
+            row("activity", "", &activity.activity, "scored", activity.scored.to_string());
+            row("activity", "", &activity.activity, "mean", activity.mean.to_string());
+        }
+        rows.push(String::new());
+        rows.join("\n")
+    }
+}
+
+/// The outcome of a single game
+#[derive(Debug)]
+struct GameResult<E: Edition> {
+    /// Final standings, the winners first
+    standings: Vec<Placement<E::Score>>,
+    /// Every activity that was scored and the points that it gave
+    activities: Vec<(E::Activity, usize)>,
+    /// The names of the states of every transition, in the order that they happened
+    transitions: Vec<(&'static str, &'static str)>,
+}
+
+/// Plays the games and collects the statistics
+pub fn simulate<E: Edition>(options: &SimulationOptions) -> Result<Report, SimulationError> {
+    if options.players < 2 || options.players > MAX_PLAYERS {
+        return Err(SimulationError::Players {
+            players: options.players,
+            max: MAX_PLAYERS,
+        });
+    }
+    if options.strategies.is_empty() {
+        return Err(SimulationError::NoStrategies);
+    }
+    options
+        .config
+        .validate::<E>(MAX_PLAYERS)
+        .map_err(SimulationError::Config)?;
+
+    let names: Vec<String> = options.strategies.iter().map(name).collect();
+    let mut seats: HashMap<&str, usize> = HashMap::new();
+    let mut wins: HashMap<&str, usize> = HashMap::new();
+    let mut scores: HashMap<&str, Vec<E::Score>> = HashMap::new();
+    let mut regions: Vec<(E::Region, usize)> =
+        E::regions().into_iter().map(|region| (region, 0)).collect();
+    let mut activities: HashMap<E::Activity, Vec<usize>> = HashMap::new();
+
+    for game in 0..options.games {
+        // The strategies move one seat every game so that no strategy always starts
+        let seating: Vec<usize> = (0..options.players)
+            .map(|seat| (seat + game) % options.strategies.len())
+            .collect();
+        let mut config = options.config.clone();
+        config.seed = options.seed.map(|seed| seed.wrapping_add(game as u64));
+        let strategies = seating.iter().map(|idx| options.strategies[*idx]).collect();
+        let result = play::<E>(game, config, strategies)?;
+
+        for placement in &result.standings {
+            let strategy = names[seating[placement.player as usize]].as_str();
+            *seats.entry(strategy).or_default() += 1;
+            // A shared first place is a win for every player that shares it
+            if placement.place == 1 {
+                *wins.entry(strategy).or_default() += 1;
+            }
+            scores.entry(strategy).or_default().push(placement.score.clone());
+        }
+        for (region, completed) in regions.iter_mut() {
+            let scored = result
+                .standings
+                .iter()
+                .any(|placement| placement.score.completed_regions().contains(region));
+            if scored {
+                *completed += 1;
+            }
+        }
+        for (activity, points) in result.activities {
+            activities.entry(activity).or_default().push(points);
+        }
+        debug!(game, "Simulated game");
+    }
+
+    let mut strategies = Vec::new();
+    for strategy in names.iter().map(String::as_str) {
+        if strategies.iter().any(|report: &StrategyReport| report.strategy == strategy) {
+            continue;
+        }
+        let seats = seats.get(strategy).copied().unwrap_or(0);
+        let wins = wins.get(strategy).copied().unwrap_or(0);
+        strategies.push(StrategyReport {
+            strategy: strategy.to_owned(),
+            seats,
+            wins,
+            win_rate: rate(wins, seats),
+            scores: distributions::<E>(scores.remove(strategy).unwrap_or_default()),
+        });
+    }
+    Ok(Report {
+        games: options.games,
+        players: options.players,
+        strategies,
+        regions: regions
+            .into_iter()
+            .map(|(region, completed)| RegionReport {
+                region: format!("{:?}", region),
+                completed,
+                rate: rate(completed, options.games),
+            })
+            .collect(),
+        activities: E::activities()
+            .into_iter()
+            .map(|activity| {
+                let points = activities.remove(&activity).unwrap_or_default();
+                ActivityReport {
+                    activity: activity.into(),
+                    scored: points.len(),
+                    mean: rate(points.iter().sum(), points.len()),
+                }
+            })
+            .collect(),
+    })
+}
+
+/// Returns the name that the strategy is selected by on the command line
+fn name(strategy: &Difficulty) -> String {
+    // Every variant is a possible value
+    strategy.to_possible_value().unwrap().get_name().to_owned()
+}
+
+fn rate(count: usize, total: usize) -> f64 {
+    match total {
+        0 => 0.0,
+        total => count as f64 / total as f64,
+    }
+}
+
+fn distributions<E: Edition>(scores: Vec<E::Score>) -> Vec<Distribution> {
+    let mut categories: Vec<(&str, Vec<usize>)> = Vec::new();
+    for score in &scores {
+        let parts = score.parts().into_iter().chain([(TOTAL, score.total_score())]);
+        for (category, value) in parts {
+            match categories.iter_mut().find(|(name, _)| *name == category) {
+                Some((_, values)) => values.push(value),
+                None => categories.push((category, vec![value])),
+            }
+        }
+    }
+    categories
+        .into_iter()
+        .map(|(category, values)| Distribution::new(category, values))
+        .collect()
+}
+
+/// Plays one game where the player with id `n` is played by the `n`th strategy
+fn play<E: Edition>(
+    game: usize,
+    config: GameConfig,
+    strategies: Vec<Difficulty>,
+) -> Result<GameResult<E>, SimulationError> {
+    // Every simulated game is counted on its own, a match would play several games in one
+    let config = GameConfig { games: 1, ..config };
+    let checked = invariants::ALWAYS_CHECKED || config.check_invariants;
+    let players: Vec<usize> = (0..strategies.len()).collect();
+    let mut autopilots: Vec<Autopilot<E>> = strategies
+        .iter()
+        .enumerate()
+        .map(|(uid, strategy)| Autopilot::new(uid as u8, strategy.strategy()))
+        .collect();
+    // The players as they were last synced, the activities are valued on these
+    let mut views: Vec<Player<E>> = players.iter().map(|uid| Player::new(*uid as u8)).collect();
+    let mut state: Box<dyn GameState<E>> =
+        Box::new(WaitingForPlayers::<E, DealingCards<E>>::new(None, config.clone()));
+    let mut result = GameResult {
+        standings: Vec::new(),
+        activities: Vec::new(),
+        transitions: Vec::new(),
+    };
+
+    for _ in 0..MAX_STEPS {
+        if state.finished() {
+            if checked {
+                check_transitions::<E>(game, &result.transitions)?;
+            }
+            return Ok(result);
+        }
+        let (_delay, actions, next) = state.get_next_action(&players);
+        if let Some(next) = next {
+            result.transitions.push((state.name(), next.name()));
+            state = next;
+            check(game, checked, &mut state)?;
+        }
+        for action in actions {
+            let uid = action.player();
+            match action.action() {
+                Event::Sync(player) => views[uid] = player,
+                Event::FinalResult(_, standings) => result.standings = standings,
+                _ => {}
+            }
+            // The strategies of a simulation do not wait on I/O, their answers are ready at once
+            let respond = autopilots[uid].respond(action.action());
+            let response = match async_std::task::block_on(respond) {
+                Some(response) => response,
+                None => continue,
+            };
+            if let Event::ScoreActivity(Some(activity)) = response {
+                let points = activity_points(&config, &views[uid], activity);
+                result.activities.push((activity, points));
+            }
+            let received = action.transition().transition();
+            match state.register_response((response, &received)) {
+                Ok(Some(next)) => {
+                    result.transitions.push((state.name(), next.name()));
+                    state = next;
+                    check(game, checked, &mut state)?;
+                }
+                Ok(None) => {}
+                Err(error) => return Err(SimulationError::Rules { game, error }),
+            }
+        }
+    }
+    Err(SimulationError::Stalled { game })
+}
+
+/// Checks the [`invariants`] of the state that was just entered, if `checked`
+fn check<E: Edition>(
+    game: usize,
+    checked: bool,
+    state: &mut Box<dyn GameState<E>>,
+) -> Result<(), SimulationError> {
+    let meta = match state.metadata() {
+        Some(meta) if checked => meta,
+        _ => return Ok(()),
+    };
+    invariants::check(meta).map_err(|violation| {
+        error!(game, %violation, ?meta, "Game invariant broken");
+        SimulationError::Invariant {
+            game,
+            violation: violation.to_string(),
+        }
+    })
+}
+
+/// Checks that every transition of the game is declared by the [`Transitions`] of its states
+///
+/// [`Transitions`]: super::rules::states::Transitions
+fn check_transitions<E: Edition>(
+    game: usize,
+    transitions: &[(&'static str, &'static str)],
+) -> Result<(), SimulationError> {
+    let machine = StateMachine::new::<E>();
+    match transitions
+        .iter()
+        .find(|transition| !machine.transitions.contains(transition))
+    {
+        Some((from, to)) => Err(SimulationError::Invariant {
+            game,
+            violation: format!("{} went to {} which it does not declare", from, to),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Returns the points that scoring the activity adds to the players round
+fn activity_points<E: Edition>(
+    config: &GameConfig,
+    player: &Player<E>,
+    activity: E::Activity,
+) -> usize {
+    let regions = E::regions();
+    let with = E::score(config, &mut player.clone(), &regions, Some(activity)).total_score();
+    let without = E::score(config, &mut player.clone(), &regions, None).total_score();
+    with.saturating_sub(without)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        australia::{
+            bot::Difficulty,
+            rules::{
+                config::GameConfig,
+                edition::Edition,
+                states::diagram::StateMachine,
+                AustraliaEdition,
+            },
+        },
+        europe::rules::EuropeEdition,
+    };
+
+    use super::{play, simulate, ReportFormat, SimulationError, SimulationOptions};
+
+    fn options(games: usize, players: usize) -> SimulationOptions {
+        SimulationOptions {
+            games,
+            players,
+            strategies: vec![Difficulty::Greedy, Difficulty::Random],
+            config: GameConfig::default(),
+            seed: Some(7),
+        }
+    }
+
+    #[test]
+    fn test_simulate() {
+        let report = simulate::<AustraliaEdition>(&options(6, 4)).unwrap();
+        assert_eq!(report.strategies.len(), 2);
+        let seats: usize = report.strategies.iter().map(|strategy| strategy.seats).sum();
+        let wins: usize = report.strategies.iter().map(|strategy| strategy.wins).sum();
+        assert_eq!(seats, 6 * 4);
+        assert_eq!(wins, 6);
+        for strategy in &report.strategies {
+            assert_eq!(strategy.scores.last().unwrap().category, "Total");
+            assert!(strategy.scores.iter().all(|score| score.min <= score.max));
+        }
+        assert!(report.regions.iter().all(|region| region.completed <= 6));
+
+        let csv = report.write(ReportFormat::Csv);
+        assert!(csv.starts_with("section,strategy,name,statistic,value\n"));
+        assert!(csv.contains("result,greedy,,wins,"));
+        assert!(report.write(ReportFormat::Json).contains("\"win_rate\""));
+    }
+
+    /// Plays a game and compares the transitions that it took with the declared ones
+    fn check_transitions<E: Edition>() {
+        let strategies = vec![Difficulty::Greedy, Difficulty::Random];
+        let config = GameConfig {
+            seed: Some(3),
+            ..GameConfig::default()
+        };
+        let result = play::<E>(0, config, strategies).unwrap();
+        let machine = StateMachine::new::<E>();
+        for transition in &result.transitions {
+            assert!(
+                machine.transitions.contains(transition),
+                "{:?} is not declared",
+                transition
+            );
+        }
+        // A single game never goes on to the next game of a match
+        let unseen: Vec<_> = machine
+            .transitions
+            .iter()
+            .filter(|transition| !result.transitions.contains(transition))
+            .collect();
+        assert_eq!(unseen, vec![&("Final", "WaitingForPlayers")]);
+    }
+
+    #[test]
+    fn test_transitions() {
+        check_transitions::<AustraliaEdition>();
+        check_transitions::<EuropeEdition>();
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(matches!(
+            simulate::<AustraliaEdition>(&options(1, 5)),
+            Err(SimulationError::Players { .. })
+        ));
+        let mut no_strategies = options(1, 2);
+        no_strategies.strategies.clear();
+        assert!(matches!(
+            simulate::<AustraliaEdition>(&no_strategies),
+            Err(SimulationError::NoStrategies)
+        ));
+    }
+}
@@ -7,10 +7,11 @@
 use ratatui::{
     prelude::{Backend, Constraint, Direction, Layout, Rect},
     style::{Color, Style},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
 use tui::{
+    maps::Map,
     tui::{controls::EventApi, TuiPage},
     ui::{Card as _, Hand},
 };
@@ -19,15 +20,40 @@ use self::pages::main_page::CardArea;
 
 use super::rules::{
     cards::{AustraliaCard, Card},
-    scoring::Scoring,
-    AustraliaPlayer,
+    edition::{Edition, RoundScore, Site},
+    AustraliaEdition, Player,
 };
 
 pub mod map;
 pub mod mediator;
 pub mod pages;
 
-/// A wrapper for [`Vec<Scoring>`]
+/// The parts of the ui that differ in between the editions
+pub trait EditionUi: Edition {
+    /// The map that the tourist sites are drawn on
+    type Map: Map<REGION = Self::Region> + Send + Sync + 'static;
+    /// Returns the lines that describe the card, drawn below the site and number
+    fn card_details(card: &Self::Card) -> Vec<String>;
+}
+
+impl EditionUi for AustraliaEdition {
+    type Map = map::australia::Map;
+    fn card_details(card: &AustraliaCard) -> Vec<String> {
+        let mut details = vec![format!("Region : {:?}", card.region())];
+        if let Some(collection) = card.collection() {
+            details.push(format!("Collection : {:?}", collection));
+        }
+        if let Some(animal) = card.animal() {
+            details.push(format!("Animal : {:?}", animal));
+        }
+        if let Some(activity) = card.activity() {
+            details.push(format!("Activity : {:?}", activity));
+        }
+        details
+    }
+}
+
+/// A wrapper for a list of round scores
 ///
 /// This is needed due to implementations
 /// on foreign types is disallowed.
@@ -35,19 +61,19 @@ pub mod pages;
 /// ## Drawable
 ///
 /// This type is a valid [`TuiPage`]
-pub struct ScoreList(Vec<Scoring>);
+pub struct ScoreList<S: RoundScore>(Vec<S>);
 
-impl EventApi for ScoreList {
+impl<S: RoundScore> EventApi for ScoreList<S> {
     fn handle_input(&mut self, _control: tui::tui::controls::Controls) {
         todo!()
     }
 }
-impl Default for ScoreList {
+impl<S: RoundScore> Default for ScoreList<S> {
     fn default() -> Self {
         Self(Vec::new())
     }
 }
-impl ScoreList {
+impl<S: RoundScore> ScoreList<S> {
     fn format_elements(&self, elements: Vec<usize>) -> String {
         if elements.is_empty() {
             return String::from("{}");
@@ -67,66 +93,78 @@ impl ScoreList {
         ret.join(" = ")
     }
 }
-impl TuiPage for ScoreList {
+impl<S: RoundScore> TuiPage for ScoreList<S> {
     fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, block: Rect) {
         let score_area = Block::default()
             .title("Score")
             .borders(Borders::all())
             .style(Style::default());
+
+        // One row per part of the score and one for the total
+        let mut rows: Vec<(&str, Vec<usize>)> = Vec::new();
+        let mut sum = 0;
+        for (round, score) in self.0.iter().enumerate() {
+            sum += score.total_score();
+            for (label, value) in score.parts() {
+                match rows.iter_mut().find(|(name, _)| *name == label) {
+                    Some((_, values)) => values.push(value),
+                    // A category that was not scored in the earlier rounds
+                    None => {
+                        let mut values = vec![0; round];
+                        values.push(value);
+                        rows.push((label, values));
+                    }
+                }
+            }
+            // A category that was not scored this round
+            for (_, values) in rows.iter_mut() {
+                values.resize(round + 1, 0);
+            }
+        }
+        let count = rows.len() + 1;
+
+        // The ledger of the latest score is listed below the parts, if there is room
+        let ledger = self.0.last().map(|score| score.ledger()).unwrap_or_default();
+        let (block, ledger_area) = match ledger.is_empty() {
+            true => (block, None),
+            false => {
+                let areas = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(2 * count as u16 + 2), Constraint::Min(0)])
+                    .split(block);
+                (areas[0], Some(areas[1]))
+            }
+        };
         let layout = Layout::default()
             .direction(Direction::Vertical)
             .margin(1)
-            .constraints([Constraint::Percentage((100 / 6) as u16); 6].as_ref())
+            .constraints(vec![Constraint::Percentage((100 / count) as u16); count])
             .split(block)
             .to_vec();
 
         let mut paragraphs = Vec::new();
-
-        let mut throw_catch = Vec::new();
-        let mut tourist_sites = Vec::new();
-        let mut collections = Vec::new();
-        let mut animals = Vec::new();
-        let mut activity = Vec::new();
-        let mut sum = 0;
-        // This is horrendous but I do not have time to rework this
-        for score in &self.0 {
-            sum += score.throw_catch();
-            sum += score.tourist_sites();
-            sum += score.collections();
-            sum += score.animals();
-            sum += score.activity();
-            throw_catch.push(score.throw_catch());
-            tourist_sites.push(score.tourist_sites());
-            collections.push(score.collections());
-            animals.push(score.animals());
-            activity.push(score.activity());
+        for (label, values) in rows {
+            paragraphs.push(Paragraph::new(format!(
+                "{} : {}",
+                label,
+                self.format_elements(values)
+            )));
         }
-
-        paragraphs.push(Paragraph::new(format!(
-            "Throw Catch : {}",
-            self.format_elements(throw_catch)
-        )));
-        paragraphs.push(Paragraph::new(format!(
-            "tourist_sites : {}",
-            self.format_elements(tourist_sites)
-        )));
-        paragraphs.push(Paragraph::new(format!(
-            "Collections : {}",
-            self.format_elements(collections)
-        )));
-        paragraphs.push(Paragraph::new(format!(
-            "Animals : {}",
-            self.format_elements(animals)
-        )));
-        paragraphs.push(Paragraph::new(format!(
-            "Activity : {}",
-            self.format_elements(activity)
-        )));
         paragraphs.push(Paragraph::new(format!("Total : {}", sum)));
         frame.render_widget(score_area, block);
         for (block, paragraph) in layout.iter().zip(paragraphs) {
             frame.render_widget(paragraph, *block);
         }
+        if let Some(area) = ledger_area {
+            let title = match self.0.len() {
+                1 => "Breakdown",
+                _ => "Breakdown of the last round",
+            };
+            let paragraph = Paragraph::new(ledger.join("\n"))
+                .block(Block::default().title(title).borders(Borders::all()))
+                .wrap(Wrap { trim: true });
+            frame.render_widget(paragraph, area);
+        }
     }
 
     fn set_title(&mut self, _title: String) {}
@@ -136,9 +174,9 @@ impl TuiPage for ScoreList {
     }
 }
 
-impl CardArea<AustraliaCard> for AustraliaPlayer
+impl<E: EditionUi> CardArea<E::Card> for Player<E>
 where
-    Self: Hand<AustraliaCard>,
+    Self: Hand<E::Card>,
 {
     fn increment(&mut self) {
         let count = self.hand_size();
@@ -202,18 +240,11 @@ where
             let mut paragraphs = Vec::new();
             paragraphs.push(Paragraph::new(format!(
                 "Site : {:?} | # : {:?}",
-                card.to_char(),
-                Card::number(card)
+                card.site(),
+                card.number()
             )));
-            paragraphs.push(Paragraph::new(format!("Region : {:?}", card.region())));
-            if let Some(collection) = card.collection() {
-                paragraphs.push(Paragraph::new(format!("Collection : {:?}", collection)));
-            }
-            if let Some(animal) = card.animal() {
-                paragraphs.push(Paragraph::new(format!("Animal : {:?}", animal)));
-            }
-            if let Some(activity) = card.activity() {
-                paragraphs.push(Paragraph::new(format!("Activity : {:?}", activity)));
+            for line in E::card_details(card) {
+                paragraphs.push(Paragraph::new(line));
             }
 
             frame.render_widget(rect, *area);
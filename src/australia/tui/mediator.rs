@@ -2,39 +2,39 @@
 //! [`player`](crate::australia::player) and the [`tui`]
 use std::sync::Arc;
 
-use log::{error, info};
+use tracing::{error, info};
 use tokio::sync::{broadcast, RwLock};
 use tui::{
     tui::{
         popup::{self, info::Info, select::Select, Popup},
         Tui, TuiMonitor,
     },
-    ui::{Hand, UiElement},
+    ui::Hand,
 };
 
 use crate::{
     australia::protocol::Message,
-    australia::rules::{
-        cards::{AustraliaCard, AustralianActivity, Card},
-        AustraliaPlayer,
-    },
+    australia::rules::{edition::RoundScore, Player},
 };
 
 use super::{
-    map::australia::Map,
-    pages::{main_page::MainPage, map_page, score_popup::Score, show_page::ShowPage},
-    ScoreList,
+    pages::{
+        leaderboard::LeaderboardPage, main_page::MainPage, map_page, score_popup::Score,
+        show_page::ShowPage,
+    },
+    EditionUi, ScoreList,
 };
 
 #[async_trait::async_trait]
-impl TuiMonitor<Message, Info, Select>
+impl<E: EditionUi> TuiMonitor<Message<E>, Info, Select>
     for Tui<
-        MainPage<AustraliaCard, AustraliaPlayer>,
-        map_page::DefaultTuiMap<Map, ScoreList>,
-        ShowPage<AustraliaCard, AustraliaPlayer>,
+        MainPage<E, Player<E>>,
+        map_page::DefaultTuiMap<E::Map, ScoreList<E::Score>>,
+        ShowPage<E::Card, Player<E>, E::Map>,
+        LeaderboardPage,
         Info,
         Select,
-        Score,
+        Score<E::Score>,
     >
 {
     /// Opens up a input box, this allows the user to select an option
@@ -91,11 +91,13 @@ impl TuiMonitor<Message, Info, Select>
     /// Manages message passing between the [`player`](super::player) and the TUI.
     async fn monitor(
         page: Arc<RwLock<Box<Self>>>,
-        mut channel: broadcast::Receiver<Message>,
-        transmit: broadcast::Sender<Message>,
+        mut channel: broadcast::Receiver<Message<E>>,
+        transmit: broadcast::Sender<Message<E>>,
     ) {
         // If there was time I would clean up this function to be multiple functions but alas I am out of time
 
+        // The result of the last game, shown again with the match standings
+        let mut final_result = None;
         loop {
             // Poll for events every second
             let msg = channel.recv().await;
@@ -141,6 +143,8 @@ impl TuiMonitor<Message, Info, Select>
                         .unwrap();
                 }
                 Message::ReadyCheck => {
+                    // A new game is about to start, hide the previous result
+                    page.write().await.clear_final_result();
                     let (write_part, mut read_part) = broadcast::channel(32);
                     let popup = Select::new(
                         write_part,
@@ -165,31 +169,43 @@ impl TuiMonitor<Message, Info, Select>
                     let num_options = options.len();
                     // Cloning here is fin since the vector is small
                     let mut selectable: Vec<String> =
-                        AustralianActivity::to_string_vec(options.clone());
+                        options.iter().map(|&activity| activity.into()).collect();
                     let mut locked = page.write().await;
                     let mut cards = locked.main_page().get_hand().get_hand();
                     cards.extend(locked.main_page().get_show().get_hand());
+                    let synced = locked.main_page().get_synced().cloned();
+                    let config = locked.main_page().get_config().clone();
 
-                    let mut dict: std::collections::HashMap<AustralianActivity, usize> =
+                    let mut dict: std::collections::HashMap<E::Activity, usize> =
                         std::collections::HashMap::new();
                     for card in cards {
-                        if let Some(activity) = card.activity() {
+                        if let Some(activity) = E::activity(&card) {
                             let count = dict.entry(activity).or_insert(0);
                             *count += 1;
                         }
                     }
                     // Avoid deadlocking our selves
                     drop(locked);
+                    // The round score that each choice would give, scored like the server does
+                    let projected = |activity| match &synced {
+                        Some(player) => format!(
+                            ", round score {}",
+                            player.projected_score(&config, activity).total_score()
+                        ),
+                        None => String::new(),
+                    };
                     for (text, activity) in selectable.iter_mut().zip(options.clone()) {
-                        if let Some(&count) = dict.get(&activity) {
-                            let intermediate = format!("\n({})", count);
-                            *text = text.to_owned() + &intermediate;
-                        } else {
-                            let intermediate = format!("\n({})", 0);
-                            *text = text.to_owned() + &intermediate;
-                        }
+                        let count = dict.get(&activity).copied().unwrap_or(0);
+                        let intermediate = format!("\n({}{})", count, projected(Some(activity)));
+                        *text = text.to_owned() + &intermediate;
                     }
-                    selectable.push("Do not score anything".to_owned());
+                    selectable.push(match &synced {
+                        Some(player) => format!(
+                            "Do not score anything\n(round score {})",
+                            player.projected_score(&config, None).total_score()
+                        ),
+                        None => "Do not score anything".to_owned(),
+                    });
                     let popup = Select::new(
                         write_part,
                         selectable,
@@ -232,12 +248,12 @@ impl TuiMonitor<Message, Info, Select>
                 Message::ShowOtherHand(uid, cards, visited) => {
                     let _new_player = page.write().await.paginate().replace_into(ShowPage::new(
                         uid,
-                        AustraliaPlayer::new(0).set_cards(cards),
+                        Player::new(0).set_cards(cards),
                         visited,
                     ));
                 }
                 Message::ReassignHand(cards) => {
-                    let mut new_hand: AustraliaPlayer = AustraliaPlayer::new(0);
+                    let mut new_hand: Player<E> = Player::new(0);
                     for card in cards {
                         new_hand.add_card(card);
                     }
@@ -250,7 +266,7 @@ impl TuiMonitor<Message, Info, Select>
                     tokio::spawn(async move { Self::info(page_clone.clone(), popup).await });
                 }
                 Message::Sync(mut player) => {
-                    let hand = AustraliaPlayer::new(0).set_cards(player.get_hand());
+                    let hand = Player::new(0).set_cards(player.get_hand());
                     let scores = player.scores();
                     let mut locked_page = page.write().await;
                     locked_page
@@ -259,9 +275,10 @@ impl TuiMonitor<Message, Info, Select>
                         .replace_score(ScoreList(scores));
                     let mut discard = player.get_discard();
                     discard.extend(player.get_show());
-                    let discard = AustraliaPlayer::new(0).set_cards(discard);
+                    let discard = Player::new(0).set_cards(discard);
                     locked_page.main_page().reassign_hand(hand);
                     locked_page.main_page().reassign_show(discard);
+                    locked_page.main_page().sync(player.clone());
                     locked_page
                         .paginate()
                         .map_page()
@@ -274,11 +291,31 @@ impl TuiMonitor<Message, Info, Select>
                 Message::FinalResult(uid, scores) => {
                     info!("Game is now over");
                     info!("Trying to show the score dialog");
+                    final_result = Some((uid, scores.clone()));
                     let score = Score::new(uid, scores);
                     let mut locked = page.write().await;
-                    let _ = transmit.send(Message::Exit);
                     locked.final_result(score);
-                    return;
+                }
+                Message::MatchStandings(standings) => {
+                    info!(?standings, "Match standings");
+                    // The standings follow the result of the game, show them together
+                    if let Some((uid, scores)) = final_result.clone() {
+                        let score = Score::new(uid, scores).with_match(standings);
+                        let mut locked = page.write().await;
+                        locked.final_result(score);
+                    }
+                }
+                Message::Leaderboard(profiles) => {
+                    info!(?profiles, "Leaderboard");
+                    page.write().await.paginate().leaderboard_page().update(profiles);
+                }
+                Message::History(games) => {
+                    info!(?games, "History");
+                    page.write().await.paginate().leaderboard_page().update_history(games);
+                }
+                Message::Config(config) => {
+                    info!(?config, "Game config");
+                    page.write().await.main_page().set_config(config);
                 }
                 Message::NewRound => {
                     info!("new round");
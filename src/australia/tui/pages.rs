@@ -1,5 +1,6 @@
 //! Defines all of the TUI pages.
 
+pub mod leaderboard;
 pub mod main_page;
 pub mod map_page;
 pub mod show_page;
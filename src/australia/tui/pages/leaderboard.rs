@@ -0,0 +1,124 @@
+//! Defines the leaderboard page.
+//!
+//! Lists the highest rated players of the server and the last games of this player, it
+//! stays empty unless the server keeps [`profiles`](crate::australia::profiles).
+
+use ratatui::{
+    prelude::{Backend, Constraint, Direction, Layout, Rect},
+    style::{Style, Stylize},
+    widgets::{Block, Borders, Paragraph, Row, Table},
+    Frame,
+};
+use tui::tui::{controls::EventApi, TuiPage};
+
+use crate::australia::profiles::{PastGame, Profile};
+
+pub struct LeaderboardPage {
+    title: String,
+    /// The nickname of this player, highlighted in the list
+    nickname: Option<String>,
+    profiles: Vec<Profile>,
+    /// The last games of this player, the latest first
+    history: Vec<PastGame>,
+}
+
+impl LeaderboardPage {
+    pub fn new(nickname: Option<String>) -> Self {
+        Self {
+            title: "Leaderboard".to_owned(),
+            nickname,
+            profiles: Vec::new(),
+            history: Vec::new(),
+        }
+    }
+    pub fn update(&mut self, profiles: Vec<Profile>) {
+        self.profiles = profiles
+    }
+    pub fn update_history(&mut self, history: Vec<PastGame>) {
+        self.history = history
+    }
+}
+
+impl LeaderboardPage {
+    fn draw_history<B: Backend>(&self, frame: &mut Frame<B>, block: Rect) {
+        let rows = self.history.iter().map(|game| {
+            Row::new(vec![
+                format!("#{}", game.place),
+                game.score.to_string(),
+                format!("{:.0}", game.rating),
+                game.game.to_string(),
+                game.seed.to_string(),
+            ])
+        });
+        let table = Table::new(rows)
+            .header(Row::new(vec!["Place", "Score", "Rating", "Game", "Seed"]).gray())
+            .widths(&[
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Length(6),
+                Constraint::Min(20),
+            ])
+            .block(Block::default().title("Your last games").borders(Borders::ALL));
+        frame.render_widget(table, block);
+    }
+}
+
+impl EventApi for LeaderboardPage {
+    fn handle_input(&mut self, _control: tui::tui::controls::Controls) {}
+}
+
+impl TuiPage for LeaderboardPage {
+    fn get_title(&self) -> &str {
+        &self.title
+    }
+    fn set_title(&mut self, title: String) {
+        self.title = title
+    }
+    fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, block: Rect) {
+        let block = match self.history.is_empty() {
+            true => block,
+            false => {
+                let layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+                    .split(block);
+                self.draw_history(frame, layout[1]);
+                layout[0]
+            }
+        };
+        let area = Block::default().title("Leaderboard").borders(Borders::ALL);
+        if self.profiles.is_empty() {
+            let text = match self.nickname {
+                Some(_) => "No rated games yet",
+                None => "Start the client with --nickname to be rated",
+            };
+            frame.render_widget(Paragraph::new(text).block(area), block);
+            return;
+        }
+        let rows = self.profiles.iter().enumerate().map(|(idx, profile)| {
+            let row = Row::new(vec![
+                format!("#{}", idx + 1),
+                profile.nickname.clone(),
+                format!("{:.0}", profile.rating),
+                profile.games.to_string(),
+                profile.wins.to_string(),
+            ]);
+            match self.nickname.as_ref() == Some(&profile.nickname) {
+                true => row.style(Style::default().bold()),
+                false => row,
+            }
+        });
+        let table = Table::new(rows)
+            .header(Row::new(vec!["Place", "Nickname", "Rating", "Games", "Wins"]).gray())
+            .widths(&[
+                Constraint::Length(6),
+                Constraint::Min(24),
+                Constraint::Length(8),
+                Constraint::Length(6),
+                Constraint::Length(6),
+            ])
+            .block(area);
+        frame.render_widget(table, block);
+    }
+}
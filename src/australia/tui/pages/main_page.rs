@@ -6,10 +6,11 @@
 
 use std::marker::PhantomData;
 
-use log::info;
+use tracing::info;
 use ratatui::{
     prelude::{Backend, Constraint, Direction, Layout, Rect},
     style::Color,
+    widgets::Paragraph,
     Frame,
 };
 use tokio::sync::broadcast;
@@ -22,7 +23,14 @@ use tui::{
     ui::{Card, Hand},
 };
 
-use crate::australia::{protocol::Message, rules::cards::AustraliaCard};
+use crate::australia::{
+    protocol::Message,
+    rules::{
+        config::GameConfig,
+        edition::{Edition, RoundScore},
+        Player,
+    },
+};
 
 
 #[derive(Debug)]
@@ -41,25 +49,31 @@ pub trait CardArea<C: Card> {
     fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, block: Rect, title: &str, outline: Color);
 }
 
-pub struct MainPage<C: Card, H: Hand<C> + CardArea<C>> {
+pub struct MainPage<E: Edition, H: Hand<E::Card> + CardArea<E::Card>> {
     hand: H,
     discard_pile: H,
-    card: PhantomData<C>,
+    edition: PhantomData<E>,
     focused: bool,
     title: String,
-    feedback_channel: Option<broadcast::Sender<Message>>,
-    requested_action: Option<Message>,
+    feedback_channel: Option<broadcast::Sender<Message<E>>>,
+    requested_action: Option<Message<E>>,
+    /// The player as it was last synced, used to estimate the round score
+    synced: Option<Player<E>>,
+    /// The config of the game, the estimate is scored with it
+    config: GameConfig,
 }
-impl<C: Card, H: Hand<C> + CardArea<C>> MainPage<C, H> {
+impl<E: Edition, H: Hand<E::Card> + CardArea<E::Card>> MainPage<E, H> {
     pub fn new() -> Self {
         Self {
             hand: H::new(),
             discard_pile: H::new(),
-            card: PhantomData,
+            edition: PhantomData,
             title: "Game".to_owned(),
             focused: false,
             feedback_channel: None,
             requested_action: None,
+            synced: None,
+            config: GameConfig::default(),
         }
     }
     pub fn get_hand(&self) -> &H{
@@ -68,16 +82,42 @@ impl<C: Card, H: Hand<C> + CardArea<C>> MainPage<C, H> {
     pub fn get_show(&self) -> &H{
         &self.discard_pile
     }
+    /// Returns the player as it was last synced, if it has been synced
+    pub fn get_synced(&self) -> Option<&Player<E>> {
+        self.synced.as_ref()
+    }
+    pub fn sync(&mut self, player: Player<E>) {
+        self.synced = Some(player);
+    }
+    /// Returns the config of the game, the default one until the server has sent it
+    pub fn get_config(&self) -> &GameConfig {
+        &self.config
+    }
+    pub fn set_config(&mut self, config: GameConfig) {
+        self.config = config;
+    }
+    /// Returns the round score if the round ended now and the best activity was scored
+    fn estimate(&self) -> Option<String> {
+        let (activity, score) = self.synced.as_ref()?.best_projection(&self.config);
+        Some(match activity {
+            Some(activity) => format!(
+                "Round score estimate : {} (scoring {})",
+                score.total_score(),
+                Into::<String>::into(activity)
+            ),
+            None => format!("Round score estimate : {}", score.total_score()),
+        })
+    }
 }
 
-impl<C: Card, H: Hand<C> + CardArea<C> + std::fmt::Debug> MainPage<C, H> {
-    pub fn add_card(&mut self, card: C) {
+impl<E: Edition, H: Hand<E::Card> + CardArea<E::Card> + std::fmt::Debug> MainPage<E, H> {
+    pub fn add_card(&mut self, card: E::Card) {
         self.hand.add_card(card);
     }
     pub fn request(
         &mut self,
-        event: Message,
-        channel: broadcast::Sender<Message>,
+        event: Message<E>,
+        channel: broadcast::Sender<Message<E>>,
     ) -> Result<(), Error> {
         let pending_event = match self.requested_action {
             Some(_) => return Err(Error::PendingAction),
@@ -104,9 +144,7 @@ impl<C: Card, H: Hand<C> + CardArea<C> + std::fmt::Debug> MainPage<C, H> {
     }
 }
 
-impl<H: Hand<AustraliaCard> + CardArea<AustraliaCard>> EventApi
-    for MainPage<AustraliaCard, H>
-{
+impl<E: Edition, H: Hand<E::Card> + CardArea<E::Card>> EventApi for MainPage<E, H> {
     fn handle_input(&mut self, control: Controls) {
         let focused = match self.focused {
             true => &mut self.discard_pile,
@@ -151,9 +189,7 @@ impl<H: Hand<AustraliaCard> + CardArea<AustraliaCard>> EventApi
     }
 }
 
-impl<H: Hand<AustraliaCard> + CardArea<AustraliaCard>> TuiPage
-    for MainPage<AustraliaCard, H>
-{
+impl<E: Edition, H: Hand<E::Card> + CardArea<E::Card>> TuiPage for MainPage<E, H> {
     fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, block: Rect) {
         let layout: std::rc::Rc<[Rect]> = Layout::default()
             .direction(Direction::Vertical)
@@ -172,6 +208,9 @@ impl<H: Hand<AustraliaCard> + CardArea<AustraliaCard>> TuiPage
             false => (Color::White, Color::DarkGray),
         };
         self.hand.draw(frame, layout[0], "Hand", hand);
+        if let Some(estimate) = self.estimate() {
+            frame.render_widget(Paragraph::new(estimate), layout[1]);
+        }
 
         self.discard_pile.draw(frame, layout[2], "Board", board);
     }
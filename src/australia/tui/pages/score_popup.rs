@@ -1,12 +1,13 @@
 //! Defines a [`Score`] popup
 //!
-//! This popup is shown at the end of the game
+//! This popup is shown at the end of the game, in a match of several games it also
+//! shows the match standings below the scores of the game
 
-use log::{error, info};
+use tracing::{error, info};
 use ratatui::{
     prelude::{Constraint, Direction, Layout},
     style::Stylize,
-    widgets::{Block, Borders, Clear},
+    widgets::{Block, Borders, Clear, Paragraph},
 };
 use tokio::sync::broadcast;
 use tui::tui::{
@@ -15,27 +16,92 @@ use tui::tui::{
     TuiPage,
 };
 
-use crate::australia::{rules::scoring::Scoring, tui::ScoreList};
+use crate::australia::{
+    rules::{
+        edition::RoundScore,
+        ranking::{MatchStandings, Placement},
+    },
+    tui::ScoreList,
+};
 
 #[derive(Debug)]
-pub struct Score {
+pub struct Score<S: RoundScore> {
     id: u8,
-    scores: Vec<(u8, Scoring)>,
+    standings: Vec<Placement<S>>,
+    /// The standings of the match, if more than one game is played
+    match_standings: Option<MatchStandings>,
 }
 
-impl Score {
-    pub fn new(id: u8, scores: Vec<(u8, Scoring)>) -> Self {
-        Self { id, scores }
+impl<S: RoundScore> Score<S> {
+    pub fn new(id: u8, standings: Vec<Placement<S>>) -> Self {
+        Self {
+            id,
+            standings,
+            match_standings: None,
+        }
+    }
+
+    pub fn with_match(mut self, standings: MatchStandings) -> Self {
+        self.match_standings = Some(standings);
+        self
+    }
+
+    /// Returns the lines of the match standings table, one line per player
+    fn match_table(&self, standings: &MatchStandings) -> String {
+        let mut lines = vec![match standings.over() {
+            true => format!("Match over after {} games", standings.played),
+            false => format!("Game {} of {}", standings.played, standings.games),
+        }];
+        for placement in &standings.standings {
+            let player = match placement.player == self.id {
+                true => "You".to_owned(),
+                false => format!("Player {}", placement.player),
+            };
+            lines.push(format!(
+                "#{} {} : {} wins, {} points",
+                placement.place, player, placement.wins, placement.points
+            ));
+        }
+        if standings.over() {
+            let winners: Vec<String> = standings
+                .winners()
+                .into_iter()
+                .map(|player| match player == self.id {
+                    true => "You".to_owned(),
+                    false => format!("Player {}", player),
+                })
+                .collect();
+            lines.push(format!("Match won by {} 🏆", winners.join(" and ")));
+        }
+        lines.join("\n")
+    }
+
+    /// Returns the title of a players score, such as `#1 : You 🏆` or `Shared #2 : Player 3 🥈`
+    fn label(&self, placement: &Placement<S>) -> String {
+        let trophy = match placement.place {
+            1 => " 🏆",
+            2 => " 🥈",
+            3 => " 🥉",
+            _ => "",
+        };
+        let place = match placement.shared(&self.standings) {
+            true => format!("Shared #{}", placement.place),
+            false => format!("#{}", placement.place),
+        };
+        match placement.player == self.id {
+            true => format!("{} : You{}", place, trophy),
+            false => format!("{} : Player {}{}", place, placement.player, trophy),
+        }
     }
 }
-impl Popup for Score {
+impl<S: RoundScore> Popup for Score<S> {
     fn subscribe(&mut self) -> broadcast::Receiver<Message> {
         error!("");
         unreachable!()
     }
     fn exit(&mut self) {}
 }
-impl TuiPage for Score {
+impl<S: RoundScore> TuiPage for Score<S> {
     fn draw<B: ratatui::prelude::Backend>(
         &mut self,
         frame: &mut ratatui::Frame<B>,
@@ -62,47 +128,47 @@ impl TuiPage for Score {
         let backdrop = Block::default().borders(Borders::ALL).title("Final scores");
         frame.render_widget(Clear, area);
         frame.render_widget(backdrop, area);
+        let area = match &self.match_standings {
+            Some(standings) => {
+                let split = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(75), Constraint::Percentage(25)])
+                    .split(area);
+                let table = Paragraph::new(self.match_table(standings)).block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Match standings"),
+                );
+                frame.render_widget(table, split[1]);
+                split[0]
+            }
+            None => area,
+        };
         let scoring_cols = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
             .split(area)
             .to_vec();
-        let trophy = |idx:usize| -> String {
-            match idx {
-                0 => "🏆",
-                1 => "🥈",
-                2 => "🥉",
-                _ => ""
-            }.to_owned()
-        };
         for (idx, col) in scoring_cols.iter().enumerate() {
             let score_area = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
                 .split(*col);
-            if let Some((id, score)) = self.scores.get(idx) {
-                let label1 = format!("#{:?} : You {}", idx + 1,trophy(idx));
-                let label2 = format!("#{:?} : Player {:?} {}", idx + 1, id,trophy(idx));
-                let block = Block::default()
-                    .borders(Borders::ALL)
-                    .title(match *id == self.id {
-                        true => label1.slow_blink(),
-                        false => label2.gray(),
-                    });
-                ScoreList(vec![(*score).clone()]).draw(frame, block.inner(score_area[0]));
-                frame.render_widget(block, score_area[0]);
-            }
-            if let Some((id, score)) = self.scores.get(idx + 2) {
-                let label1 = format!("#{:?} : You {}", idx + 1,trophy(idx));
-                let label2 = format!("#{:?} : Player {:?} {}", idx + 1, id,trophy(idx));
+            // The first column holds the top two players, the second one the others
+            for (area, placement) in score_area.iter().zip([idx, idx + 2]) {
+                let placement = match self.standings.get(placement) {
+                    Some(placement) => placement,
+                    None => continue,
+                };
+                let label = self.label(placement);
                 let block = Block::default()
                     .borders(Borders::ALL)
-                    .title(match *id == self.id {
-                        true => label1.slow_blink(),
-                        false => label2.gray(),
+                    .title(match placement.player == self.id {
+                        true => label.slow_blink(),
+                        false => label.gray(),
                     });
-                ScoreList(vec![(*score).clone()]).draw(frame, block.inner(score_area[1]));
-                frame.render_widget(block, score_area[1]);
+                ScoreList(vec![placement.score.clone()]).draw(frame, block.inner(*area));
+                frame.render_widget(block, *area);
             }
         }
 
@@ -115,7 +181,7 @@ impl TuiPage for Score {
         "Final Result"
     }
 }
-impl EventApi for Score {
+impl<S: RoundScore> EventApi for Score<S> {
     fn handle_input(&mut self, control: Controls) {
         match control {
             _ => {}
@@ -20,22 +20,20 @@ use tui::{
 };
 
 
-use crate::australia::{tui::map::australia::Map, rules::cards::AustraliaCard};
-
 use super::main_page::CardArea;
 
-pub struct ShowPage<C: Card, H: Hand<C> + CardArea<C>> {
+pub struct ShowPage<C: Card, H: Hand<C> + CardArea<C>, M: MapTrait> {
     discard_pile: H,
     card: PhantomData<C>,
     title: String,
-    map: Map,
+    map: M,
     visited: Vec<char>,
 }
-impl<C: Card, H: Hand<C> + CardArea<C>> ShowPage<C, H> {
+impl<C: Card, H: Hand<C> + CardArea<C>, M: MapTrait> ShowPage<C, H, M> {
     pub fn new(uid: usize, showing: H, visited: Vec<char>) -> Self {
         Self {
             discard_pile: showing,
-            map: Map::default(),
+            map: M::default(),
             card: PhantomData,
             visited,
             title: format!("Player {}", uid).to_owned(),
@@ -43,15 +41,16 @@ impl<C: Card, H: Hand<C> + CardArea<C>> ShowPage<C, H> {
     }
 }
 
-impl<H: Hand<AustraliaCard> + CardArea<AustraliaCard>> ShowPageTrait
-    for ShowPage<AustraliaCard, H>
+impl<C: Card, H: Hand<C> + CardArea<C>, M: MapTrait> ShowPageTrait for ShowPage<C, H, M>
+where
+    M::REGION: 'static,
 {
     fn eq(&self, other: &Self) -> bool {
         self.title == other.title
     }
 }
 
-impl<H: Hand<AustraliaCard> + CardArea<AustraliaCard>> EventApi for ShowPage<AustraliaCard, H> {
+impl<C: Card, H: Hand<C> + CardArea<C>, M: MapTrait> EventApi for ShowPage<C, H, M> {
     fn handle_input(&mut self, control: Controls) {
         match control {
             Controls::Left => self.discard_pile.decrement(),
@@ -61,7 +60,10 @@ impl<H: Hand<AustraliaCard> + CardArea<AustraliaCard>> EventApi for ShowPage<Aus
     }
 }
 
-impl<H: Hand<AustraliaCard> + CardArea<AustraliaCard>> TuiPage for ShowPage<AustraliaCard, H> {
+impl<C: Card, H: Hand<C> + CardArea<C>, M: MapTrait> TuiPage for ShowPage<C, H, M>
+where
+    M::REGION: 'static,
+{
     fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, block: Rect) {
         let layout = Layout::default()
             .direction(Direction::Vertical)
@@ -79,12 +81,12 @@ impl<H: Hand<AustraliaCard> + CardArea<AustraliaCard>> TuiPage for ShowPage<Aust
             .draw(frame, layout[0], "Hand", Color::White);
 
         let canvas = Canvas::default()
-            .x_bounds([0.0, Map::WIDTH as f64])
-            .y_bounds([0.0, Map::HEIGHT as f64])
+            .x_bounds([0.0, M::WIDTH as f64])
+            .y_bounds([0.0, M::HEIGHT as f64])
             .paint(|context| {
                 let mut sites = self.map.render(context);
 
-                let mut region = <Map as MapTrait>::REGION::default();
+                let mut region = <M as MapTrait>::REGION::default();
                 let mut offset = 0;
                 let _ = sites.iter_mut().enumerate().for_each(|(_idx, site)| {
                     if self.visited.contains(&site.get_id()) {
@@ -0,0 +1,65 @@
+//! The custom rule set
+//!
+//! Plays a deck that is loaded from a [`definition`](rules::definition) file at runtime,
+//! with the boomerang australia rules. The server, the clients and the bots all need
+//! the same deck, which is given to each of them with `--deck <file>`, the built in
+//! boomerang australia deck is played otherwise. The server sends a
+//! [`fingerprint`](rules::definition::CardSet::fingerprint) of its deck in the handshake,
+//! clients with another deck refuse to play.
+
+use std::net::TcpListener;
+
+use server::engine::limits::ConnectionLimits;
+use tokio::net::TcpStream;
+
+use crate::{
+    australia::{
+        bot::BotOptions,
+        play, play_headless,
+        rules::{
+            edition::Edition,
+            states::diagram::{self, DiagramFormat},
+        },
+        serve,
+        simulation::{self, Report, SimulationError, SimulationOptions},
+    },
+    rule_sets::ServerOptions,
+};
+
+use self::rules::CustomEdition;
+
+pub mod rules;
+pub mod tui;
+
+/// The name that this rule set is registered under
+pub const NAME: &str = "custom";
+
+/// Runs a server for the custom deck
+pub async fn server(listener: TcpListener, limits: ConnectionLimits, options: ServerOptions) {
+    serve::<CustomEdition>(listener, limits, options, NAME).await;
+}
+
+/// Runs a client for the custom deck on an already connected stream
+pub async fn client(stream: TcpStream, nickname: Option<String>) {
+    play::<CustomEdition>(stream, nickname).await;
+}
+
+/// Runs a headless client for the custom deck on an already connected stream
+pub async fn bot(stream: TcpStream, options: BotOptions, nickname: Option<String>) {
+    play_headless::<CustomEdition>(stream, options.strategy(), nickname).await;
+}
+
+/// Simulates games with the custom deck between bots
+pub fn simulate(options: &SimulationOptions) -> Result<Report, SimulationError> {
+    simulation::simulate::<CustomEdition>(options)
+}
+
+/// Returns the fingerprint that clients of the custom deck have to match
+pub fn fingerprint() -> Option<String> {
+    CustomEdition::fingerprint()
+}
+
+/// Draws the state machine of the custom rule set
+pub fn diagram(format: DiagramFormat) -> String {
+    diagram::draw::<CustomEdition>(NAME, format)
+}
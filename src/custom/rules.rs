@@ -0,0 +1,66 @@
+//! The custom rules
+//!
+//! The game is played like boomerang australia, on the same
+//! [`states`](crate::australia::rules::states), with a deck that is loaded from a
+//! [`definition`] file when the app starts.
+
+pub mod cards;
+pub mod definition;
+pub mod scoring;
+
+use crate::australia::rules::{config::GameConfig, edition::Edition, states, Player};
+
+use self::{
+    cards::{CustomActivity, CustomCard, CustomDeck, CustomRegion},
+    scoring::CustomScoring,
+};
+
+/// The custom [`Edition`], played with the [`card_set`](definition::card_set)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CustomEdition;
+
+pub type CustomPlayer = Player<CustomEdition>;
+
+impl Edition for CustomEdition {
+    type Card = CustomCard;
+    type Region = CustomRegion;
+    type Activity = CustomActivity;
+    type Score = CustomScoring;
+    type RoundScoring = states::Scoring<Self>;
+
+    fn fingerprint() -> Option<String> {
+        Some(definition::card_set().fingerprint())
+    }
+
+    fn deck() -> CustomDeck {
+        CustomDeck::new(CustomCard::all())
+    }
+    fn regions() -> Vec<CustomRegion> {
+        CustomRegion::to_vec()
+    }
+    fn activities() -> Vec<CustomActivity> {
+        CustomActivity::to_vec()
+    }
+    fn activity(card: &CustomCard) -> Option<CustomActivity> {
+        card.activity()
+    }
+    fn collection(card: &CustomCard) -> Option<usize> {
+        card.collection().map(|(_, score)| score)
+    }
+    fn completed(region: &CustomRegion, visited: &[char]) -> bool {
+        region.completed(visited)
+    }
+    fn score(
+        _config: &GameConfig,
+        player: &mut CustomPlayer,
+        unclaimed: &[CustomRegion],
+        activity: Option<CustomActivity>,
+    ) -> CustomScoring {
+        CustomScoring::new()
+            .score_throw_catch(player)
+            .score_regions(player, unclaimed)
+            .score_collections(player)
+            .score_animals(player)
+            .score_activity(player, activity)
+    }
+}
@@ -0,0 +1,177 @@
+//! Defines the cards of the custom rule set.
+//!
+//! The cards, regions and activities are indices into the [`card_set`] that is played, they
+//! are sent as their site and names so that a client with another deck fails to read them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::australia::rules::{cards::Deck, edition::Site};
+
+use super::definition::card_set;
+
+/// A card of the [`card_set`]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "char", into = "char")]
+pub struct CustomCard(u8);
+
+/// A region of the [`card_set`]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CustomRegion(u8);
+
+/// An activity of the [`card_set`]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CustomActivity(u8);
+
+pub type CustomDeck = Deck<CustomCard>;
+
+impl CustomCard {
+    /// Returns every card in the card set
+    pub fn all() -> Vec<CustomCard> {
+        (0..card_set().len())
+            .map(|idx| CustomCard(idx as u8))
+            .collect()
+    }
+    pub fn to_char(self) -> char {
+        card_set().card(self.0 as usize).site
+    }
+    pub fn name(&self) -> &'static str {
+        &card_set().card(self.0 as usize).name
+    }
+    pub fn region(&self) -> CustomRegion {
+        CustomRegion(card_set().region(self.0 as usize) as u8)
+    }
+    pub fn number(&self) -> usize {
+        card_set().definition().regions[card_set().region(self.0 as usize)].number
+    }
+    /// Returns the name of the collection on the card and the points that it gives
+    pub fn collection(&self) -> Option<(&'static str, usize)> {
+        card_set()
+            .collection(self.0 as usize)
+            .map(|collection| (collection.name.as_str(), collection.score))
+    }
+    /// Returns the name of the animal on the card and the points for a pair of them
+    pub fn animal(&self) -> Option<(&'static str, usize)> {
+        card_set()
+            .animal(self.0 as usize)
+            .map(|animal| (animal.name.as_str(), animal.score))
+    }
+    pub fn activity(&self) -> Option<CustomActivity> {
+        card_set()
+            .activity(self.0 as usize)
+            .map(|activity| CustomActivity(activity as u8))
+    }
+}
+
+impl CustomRegion {
+    pub fn to_vec() -> Vec<CustomRegion> {
+        (0..card_set().definition().regions.len())
+            .map(|idx| CustomRegion(idx as u8))
+            .collect()
+    }
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+    pub fn name(&self) -> &'static str {
+        &card_set().definition().regions[self.0 as usize].name
+    }
+    pub fn sites(&self) -> &'static [char] {
+        &card_set().definition().regions[self.0 as usize].sites
+    }
+    pub fn completed(self, identifiers: &[char]) -> bool {
+        self.sites().iter().all(|site| identifiers.contains(site))
+    }
+}
+
+impl CustomActivity {
+    pub fn to_vec() -> Vec<CustomActivity> {
+        (0..card_set().definition().activities.len())
+            .map(|idx| CustomActivity(idx as u8))
+            .collect()
+    }
+    pub fn name(&self) -> &'static str {
+        &card_set().definition().activities[self.0 as usize]
+    }
+}
+
+impl std::fmt::Debug for CustomCard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+impl std::fmt::Debug for CustomRegion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+impl std::fmt::Debug for CustomActivity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl From<CustomCard> for char {
+    fn from(card: CustomCard) -> char {
+        card.to_char()
+    }
+}
+impl TryFrom<char> for CustomCard {
+    type Error = String;
+    fn try_from(site: char) -> Result<Self, Self::Error> {
+        match card_set().find(site) {
+            Some(idx) => Ok(CustomCard(idx as u8)),
+            None => Err(format!("no card has the site {:?}", site)),
+        }
+    }
+}
+impl From<CustomRegion> for String {
+    fn from(region: CustomRegion) -> String {
+        region.name().to_owned()
+    }
+}
+impl TryFrom<String> for CustomRegion {
+    type Error = String;
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        CustomRegion::to_vec()
+            .into_iter()
+            .find(|region| region.name() == name)
+            .ok_or(format!("unknown region {:?}", name))
+    }
+}
+impl From<CustomActivity> for String {
+    fn from(activity: CustomActivity) -> String {
+        activity.name().to_owned()
+    }
+}
+impl TryFrom<String> for CustomActivity {
+    type Error = String;
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        CustomActivity::to_vec()
+            .into_iter()
+            .find(|activity| activity.name() == name)
+            .ok_or(format!("unknown activity {:?}", name))
+    }
+}
+
+impl tui::ui::UiElement for CustomCard {
+    /// This should never be called
+    fn new() -> Self {
+        panic!("Card is not to be instantiated like this")
+    }
+}
+
+impl Site for CustomCard {
+    fn site(&self) -> char {
+        self.to_char()
+    }
+}
+
+impl tui::ui::Card for CustomCard {
+    fn get_name(&self) -> &str {
+        self.name()
+    }
+    fn number(&self) -> usize {
+        CustomCard::number(self)
+    }
+}
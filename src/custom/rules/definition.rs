@@ -0,0 +1,504 @@
+//! Defines the deck definition files that the custom rule set is played with.
+//!
+//! A definition lists the same things as the macros in the
+//! [`australia cards`](crate::australia::rules::cards), in TOML or JSON:
+//!
+//! ```toml
+//! name = "House rules"
+//! activity_scores = [0, 0, 2, 4, 7, 10, 15]
+//! activities = ["Swimming"]
+//!
+//! [[regions]]
+//! name = "Tasmania"
+//! number = 7
+//! sites = ["Y", "Z"]
+//!
+//! [[animals]]
+//! name = "Emus"
+//! score = 4
+//!
+//! [[collections]]
+//! name = "Leaves"
+//! score = 1
+//!
+//! [[cards]]
+//! name = "Salamanca Markets"
+//! site = "Y"
+//! region = "Tasmania"
+//! collection = "Leaves"
+//! animal = "Emus"
+//!
+//! [[cards]]
+//! name = "Richmond"
+//! site = "Z"
+//! region = "Tasmania"
+//! activity = "Swimming"
+//! ```
+//!
+//! A definition is only played once it is validated as a [`CardSet`], every site is on
+//! exactly one card, every region lists the sites of its cards and every collection,
+//! animal and activity on a card is in the score tables. The boomerang australia deck
+//! ships as [`AUSTRALIA`] and is played unless the app is started with `--deck <file>`.
+
+use std::{path::Path, sync::OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+/// The built in boomerang australia deck definition
+pub const AUSTRALIA: &str = include_str!("../../../assets/decks/australia.toml");
+
+/// The card set that is played, see [`card_set`]
+static CARD_SET: OnceLock<CardSet> = OnceLock::new();
+
+/// A region on the map and the sites in it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegionDefinition {
+    pub name: String,
+    /// The number printed on the cards of the region, used for the throw and catch score
+    pub number: usize,
+    pub sites: Vec<char>,
+}
+
+/// A collection or an animal and the points that it gives
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScoredDefinition {
+    pub name: String,
+    pub score: usize,
+}
+
+/// A card, the region, collection, animal and activity are referred to by name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CardDefinition {
+    pub name: String,
+    pub site: char,
+    pub region: String,
+    pub collection: Option<String>,
+    pub animal: Option<String>,
+    pub activity: Option<String>,
+}
+
+/// A deck as it is written in a definition file, see the [module](self) docs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeckDefinition {
+    pub name: String,
+    /// Points for scoring an activity with as many cards as the index, any more cards
+    /// score the last entry
+    pub activity_scores: Vec<usize>,
+    pub activities: Vec<String>,
+    pub regions: Vec<RegionDefinition>,
+    pub animals: Vec<ScoredDefinition>,
+    pub collections: Vec<ScoredDefinition>,
+    pub cards: Vec<CardDefinition>,
+}
+
+#[derive(Debug)]
+pub enum DeckError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    NoCards,
+    /// More cards, regions or activities than a card set can index
+    TooMany {
+        kind: &'static str,
+        max: usize,
+    },
+    Duplicate {
+        kind: &'static str,
+        name: String,
+    },
+    DuplicateSite(char),
+    /// A card refers to a region, collection, animal or activity that is not defined
+    Unknown {
+        card: String,
+        kind: &'static str,
+        name: String,
+    },
+    /// The region and the cards disagree on the sites in the region
+    Membership {
+        region: String,
+        site: char,
+    },
+    NoScore {
+        kind: &'static str,
+        name: String,
+    },
+    /// The activity scores are empty or go down for more cards
+    ActivityScores,
+}
+
+impl std::fmt::Display for DeckError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "could not read the deck definition, {}", e),
+            Self::Toml(e) => write!(f, "invalid deck definition, {}", e),
+            Self::Json(e) => write!(f, "invalid deck definition, {}", e),
+            Self::NoCards => write!(f, "the deck has no cards"),
+            Self::TooMany { kind, max } => write!(f, "a deck can have at most {} {}", max, kind),
+            Self::Duplicate { kind, name } => write!(f, "{} {:?} is defined twice", kind, name),
+            Self::DuplicateSite(site) => write!(f, "site {:?} is on more than one card", site),
+            Self::Unknown { card, kind, name } => {
+                write!(f, "card {:?} has the unknown {} {:?}", card, kind, name)
+            }
+            Self::Membership { region, site } => write!(
+                f,
+                "site {:?} is not both listed by region {:?} and on a card in it",
+                site, region
+            ),
+            Self::NoScore { kind, name } => write!(f, "{} {:?} gives no points", kind, name),
+            Self::ActivityScores => write!(
+                f,
+                "activity scores need at least one entry and can not go down for more cards"
+            ),
+        }
+    }
+}
+
+impl DeckDefinition {
+    /// Reads a definition from a file, JSON if the file ends in `.json` and TOML otherwise.
+    pub fn load(path: &Path) -> Result<Self, DeckError> {
+        let content = std::fs::read_to_string(path).map_err(DeckError::Io)?;
+        match path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            true => Self::parse_json(&content),
+            false => Self::parse_toml(&content),
+        }
+    }
+    /// Parses a definition from a TOML string, the definition is not validated.
+    pub fn parse_toml(content: &str) -> Result<Self, DeckError> {
+        toml::from_str(content).map_err(DeckError::Toml)
+    }
+    /// Parses a definition from a JSON string, the definition is not validated.
+    pub fn parse_json(content: &str) -> Result<Self, DeckError> {
+        serde_json::from_str(content).map_err(DeckError::Json)
+    }
+}
+
+/// Where the region, collection, animal and activity of a card are in the definition
+#[derive(Debug, Clone, PartialEq)]
+struct Resolved {
+    region: usize,
+    collection: Option<usize>,
+    animal: Option<usize>,
+    activity: Option<usize>,
+}
+
+/// A validated [`DeckDefinition`], with every name on the cards looked up
+#[derive(Debug, Clone, PartialEq)]
+pub struct CardSet {
+    definition: DeckDefinition,
+    cards: Vec<Resolved>,
+}
+
+impl CardSet {
+    /// Validates the definition, see the [module](self) docs for the rules
+    pub fn new(definition: DeckDefinition) -> Result<Self, DeckError> {
+        let max = u8::MAX as usize;
+        if definition.cards.is_empty() {
+            return Err(DeckError::NoCards);
+        }
+        for (kind, len) in [
+            ("cards", definition.cards.len()),
+            ("regions", definition.regions.len()),
+            ("activities", definition.activities.len()),
+        ] {
+            if len > max {
+                return Err(DeckError::TooMany { kind, max });
+            }
+        }
+        unique("card", definition.cards.iter().map(|card| &card.name))?;
+        unique(
+            "region",
+            definition.regions.iter().map(|region| &region.name),
+        )?;
+        unique(
+            "animal",
+            definition.animals.iter().map(|animal| &animal.name),
+        )?;
+        unique(
+            "collection",
+            definition
+                .collections
+                .iter()
+                .map(|collection| &collection.name),
+        )?;
+        unique("activity", definition.activities.iter())?;
+        for (idx, card) in definition.cards.iter().enumerate() {
+            if definition.cards[..idx]
+                .iter()
+                .any(|other| other.site == card.site)
+            {
+                return Err(DeckError::DuplicateSite(card.site));
+            }
+        }
+        for (kind, table) in [
+            ("animal", &definition.animals),
+            ("collection", &definition.collections),
+        ] {
+            if let Some(scored) = table.iter().find(|scored| scored.score == 0) {
+                return Err(DeckError::NoScore {
+                    kind,
+                    name: scored.name.clone(),
+                });
+            }
+        }
+        let scores = &definition.activity_scores;
+        if scores.is_empty() || scores.windows(2).any(|pair| pair[1] < pair[0]) {
+            return Err(DeckError::ActivityScores);
+        }
+
+        let mut cards = Vec::with_capacity(definition.cards.len());
+        for card in &definition.cards {
+            let unknown = |kind: &'static str, name: &String| DeckError::Unknown {
+                card: card.name.clone(),
+                kind,
+                name: name.clone(),
+            };
+            let find = |kind: &'static str, name: &Option<String>, names: Vec<&String>| match name {
+                Some(name) => match names.iter().position(|other| *other == name) {
+                    Some(idx) => Ok(Some(idx)),
+                    None => Err(unknown(kind, name)),
+                },
+                None => Ok(None),
+            };
+            let region = definition
+                .regions
+                .iter()
+                .position(|region| region.name == card.region)
+                .ok_or_else(|| unknown("region", &card.region))?;
+            let collections = definition.collections.iter().map(|c| &c.name).collect();
+            let animals = definition.animals.iter().map(|a| &a.name).collect();
+            let activities = definition.activities.iter().collect();
+            cards.push(Resolved {
+                region,
+                collection: find("collection", &card.collection, collections)?,
+                animal: find("animal", &card.animal, animals)?,
+                activity: find("activity", &card.activity, activities)?,
+            });
+        }
+        for (idx, region) in definition.regions.iter().enumerate() {
+            let on_cards: Vec<char> = definition
+                .cards
+                .iter()
+                .zip(&cards)
+                .filter(|(_, resolved)| resolved.region == idx)
+                .map(|(card, _)| card.site)
+                .collect();
+            let missing = on_cards
+                .iter()
+                .find(|site| !region.sites.contains(site))
+                .or_else(|| region.sites.iter().find(|site| !on_cards.contains(site)));
+            if let Some(site) = missing {
+                return Err(DeckError::Membership {
+                    region: region.name.clone(),
+                    site: *site,
+                });
+            }
+        }
+        Ok(Self { definition, cards })
+    }
+
+    /// Loads and validates a definition file
+    pub fn load(path: &Path) -> Result<Self, DeckError> {
+        Self::new(DeckDefinition::load(path)?)
+    }
+
+    /// Returns the built in boomerang australia card set
+    pub fn australia() -> Self {
+        DeckDefinition::parse_toml(AUSTRALIA)
+            .and_then(Self::new)
+            .expect("The built in deck is valid")
+    }
+
+    pub fn definition(&self) -> &DeckDefinition {
+        &self.definition
+    }
+    /// Returns a hash of the definition that is the same on every build, the server sends
+    /// it in the handshake so that clients with another deck are turned away
+    pub fn fingerprint(&self) -> String {
+        let json = serde_json::to_string(&self.definition).expect("A definition is valid JSON");
+        // FNV-1a, the std hashers are not guaranteed to be stable across releases
+        let hash = json.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+        format!("{hash:016x}")
+    }
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+    pub fn card(&self, card: usize) -> &CardDefinition {
+        &self.definition.cards[card]
+    }
+    /// Returns the index of the card with the site
+    pub fn find(&self, site: char) -> Option<usize> {
+        self.definition
+            .cards
+            .iter()
+            .position(|card| card.site == site)
+    }
+    /// Returns the index of the region of the card
+    pub fn region(&self, card: usize) -> usize {
+        self.cards[card].region
+    }
+    pub fn collection(&self, card: usize) -> Option<&ScoredDefinition> {
+        let collection = self.cards[card].collection?;
+        Some(&self.definition.collections[collection])
+    }
+    pub fn animal(&self, card: usize) -> Option<&ScoredDefinition> {
+        let animal = self.cards[card].animal?;
+        Some(&self.definition.animals[animal])
+    }
+    /// Returns the index of the activity of the card
+    pub fn activity(&self, card: usize) -> Option<usize> {
+        self.cards[card].activity
+    }
+    /// Returns the points for scoring an activity with `cards` cards
+    pub fn activity_score(&self, cards: usize) -> usize {
+        let scores = &self.definition.activity_scores;
+        scores[cards.min(scores.len() - 1)]
+    }
+}
+
+/// Returns an error for the first name that is used twice
+fn unique<'a>(
+    kind: &'static str,
+    names: impl Iterator<Item = &'a String>,
+) -> Result<(), DeckError> {
+    let mut seen = Vec::new();
+    for name in names {
+        if seen.contains(&name) {
+            return Err(DeckError::Duplicate {
+                kind,
+                name: name.clone(),
+            });
+        }
+        seen.push(name);
+    }
+    Ok(())
+}
+
+/// Returns the card set that is played, the built in [`AUSTRALIA`] deck unless another
+/// one was [`install`]ed first
+pub fn card_set() -> &'static CardSet {
+    CARD_SET.get_or_init(CardSet::australia)
+}
+
+/// Plays the card set instead of the built in deck, returns false if a card set is
+/// already in use
+pub fn install(card_set: CardSet) -> bool {
+    CARD_SET.set(card_set).is_ok()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::australia::rules::cards::{
+        Animal, AustraliaDeck, AustralianActivity, AustralianAnimal, AustralianCollection,
+        AustralianRegion, Card, Collection,
+    };
+
+    use super::{CardSet, DeckDefinition, DeckError, AUSTRALIA};
+
+    /// The built in definition describes the same deck as the compiled australia cards
+    #[test]
+    fn test_australia() {
+        let set = CardSet::australia();
+        let deck = AustraliaDeck::default().cards();
+        assert_eq!(set.len(), deck.len());
+        for card in deck {
+            let idx = set.find(card.to_char()).unwrap();
+            let definition = set.card(idx);
+            let region = &set.definition().regions[set.region(idx)];
+            assert_eq!(definition.name, card.name());
+            assert_eq!(region.number, card.number());
+            assert_eq!(
+                region.name,
+                AustralianRegion::to_string_vec(vec![card.region()])[0].trim()
+            );
+            assert_eq!(
+                set.collection(idx).map(|c| (c.name.clone(), c.score)),
+                card.collection()
+                    .map(|c: AustralianCollection| (c.into(), c.score()))
+            );
+            assert_eq!(
+                set.animal(idx).map(|a| (a.name.clone(), a.score)),
+                card.animal()
+                    .map(|a: AustralianAnimal| (a.into(), a.score()))
+            );
+            assert_eq!(
+                set.activity(idx)
+                    .map(|a| set.definition().activities[a].clone()),
+                card.activity().map(Into::<String>::into)
+            );
+        }
+        assert_eq!(
+            set.definition().activities,
+            AustralianActivity::to_string_vec(AustralianActivity::to_vec())
+        );
+        assert_eq!(set.activity_score(1), 0);
+        assert_eq!(set.activity_score(4), 7);
+        assert_eq!(set.activity_score(12), 15);
+    }
+
+    #[test]
+    fn test_json() {
+        let definition = DeckDefinition::parse_toml(AUSTRALIA).unwrap();
+        let json = serde_json::to_string(&definition).unwrap();
+        assert_eq!(DeckDefinition::parse_json(&json).unwrap(), definition);
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let australia = CardSet::australia();
+        assert_eq!(australia.fingerprint(), CardSet::australia().fingerprint());
+        let mut definition = australia.definition().clone();
+        definition.cards[0].name = "Somewhere else".to_owned();
+        let other = CardSet::new(definition).unwrap();
+        assert_ne!(australia.fingerprint(), other.fingerprint());
+    }
+
+    #[test]
+    fn test_validate() {
+        let australia = DeckDefinition::parse_toml(AUSTRALIA).unwrap();
+        let invalid = |change: fn(&mut DeckDefinition)| {
+            let mut definition = australia.clone();
+            change(&mut definition);
+            CardSet::new(definition).unwrap_err()
+        };
+
+        let e = invalid(|deck| deck.cards[1].site = 'A');
+        assert!(matches!(e, DeckError::DuplicateSite('A')));
+        let e = invalid(|deck| deck.cards[1].name = "Uluru".to_owned());
+        assert!(matches!(e, DeckError::Duplicate { kind: "card", .. }));
+        let e = invalid(|deck| deck.cards[0].region = "Atlantis".to_owned());
+        assert!(matches!(e, DeckError::Unknown { kind: "region", .. }));
+        let e = invalid(|deck| deck.cards[0].animal = Some("Dropbears".to_owned()));
+        assert!(matches!(e, DeckError::Unknown { kind: "animal", .. }));
+        let e = invalid(|deck| deck.cards[0].activity = Some("Surfing".to_owned()));
+        assert!(matches!(
+            e,
+            DeckError::Unknown {
+                kind: "activity",
+                ..
+            }
+        ));
+        // The card is moved to another region that does not list its site
+        let e = invalid(|deck| deck.cards[0].region = "Tasmania".to_owned());
+        assert!(matches!(e, DeckError::Membership { site: 'A', .. }));
+        let e = invalid(|deck| deck.regions[0].sites.push('!'));
+        assert!(matches!(e, DeckError::Membership { site: '!', .. }));
+        let e = invalid(|deck| deck.animals[0].score = 0);
+        assert!(matches!(e, DeckError::NoScore { kind: "animal", .. }));
+        let e = invalid(|deck| deck.activity_scores = vec![0, 4, 2]);
+        assert!(matches!(e, DeckError::ActivityScores));
+        let e = invalid(|deck| deck.cards.clear());
+        assert!(matches!(e, DeckError::NoCards));
+
+        let unknown_key = format!("{}\nvisible = true", AUSTRALIA);
+        assert!(DeckDefinition::parse_toml(&unknown_key).is_err());
+    }
+}
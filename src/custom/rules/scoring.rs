@@ -0,0 +1,137 @@
+//! Defines methods and types to help with scoring a custom round.
+//!
+//! A custom deck is scored with the boomerang australia rules, see [`Scoring`], only the
+//! points of the collections, animals and activities come from the [`card_set`].
+
+use std::collections::HashMap;
+
+use crate::australia::rules::scoring::{round_cards, LedgerEntry, Scoring, ACTIVITY, ANIMALS};
+
+use super::{cards::CustomActivity, definition::card_set, CustomEdition, CustomPlayer};
+
+pub type CustomScoring = Scoring<CustomEdition>;
+
+// Builder pattern for scoring
+impl CustomScoring {
+    /// Scores every pair of cards with the same animal
+    pub fn score_animals(mut self, player: &CustomPlayer) -> Self {
+        self.add(ANIMALS, 0);
+        // The card that is waiting for a second card with the same animal
+        let mut map = HashMap::new();
+        for card in round_cards(player) {
+            if let Some((animal, score)) = card.animal() {
+                match map.remove(animal) {
+                    Some(first) => {
+                        self.add(ANIMALS, score);
+                        self.explain(
+                            ANIMALS,
+                            LedgerEntry::AnimalPair {
+                                animal: animal.to_owned(),
+                                cards: (first, card),
+                                points: score,
+                            },
+                        );
+                    }
+                    None => {
+                        let _ = map.insert(animal, card);
+                    }
+                }
+            }
+        }
+        self
+    }
+    /// Scores the cards that match the activity that the player chose
+    pub fn score_activity(
+        mut self,
+        player: &mut CustomPlayer,
+        activity: Option<CustomActivity>,
+    ) -> Self {
+        let points = self.activity_points(ACTIVITY, player, activity, |cards| {
+            card_set().activity_score(cards)
+        });
+        self.add(ACTIVITY, points);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        australia::rules::{
+            edition::RoundScore,
+            scoring::{LedgerEntry, ACTIVITY, ANIMALS},
+        },
+        custom::rules::{
+            cards::{CustomActivity, CustomCard, CustomRegion},
+            CustomPlayer,
+        },
+    };
+
+    use super::CustomScoring;
+
+    fn card(site: char) -> CustomCard {
+        CustomCard::try_from(site).unwrap()
+    }
+
+    /// The built in deck scores like boomerang australia
+    #[test]
+    fn test_score_round() {
+        // Western Australia and two Tasmanian cards
+        let mut player = CustomPlayer::new(1).set_cards(
+            ['A', '-', 'B', 'C', 'D', 'Y', 'Z']
+                .into_iter()
+                .map(card)
+                .collect(),
+        );
+        player.discard(&0).unwrap();
+        let sightseeing = CustomActivity::try_from("Sightseeing".to_owned()).unwrap();
+
+        let score = CustomScoring::new()
+            .score_throw_catch(&player)
+            .score_regions(&mut player, &CustomRegion::to_vec())
+            .score_collections(&player)
+            .score_animals(&player)
+            .score_activity(&mut player, Some(sightseeing));
+        // The Bungle Bungles (1) is thrown and Richmond (7) is caught
+        assert_eq!(score.throw_catch(), 6);
+        assert_eq!(
+            score.parts(),
+            vec![
+                ("Throw Catch", 6),
+                ("Tourist sites", 7 + 3),
+                // Leaves, Shells, Wildflowers and Leaves sum to 7 which is doubled
+                ("Collections", 14),
+                // Three kangaroos make one pair, the emus and koalas have no pair
+                ("Animals", 3),
+                // The Pinnacles and Mount Wellington
+                ("Activity", 2),
+            ]
+        );
+        let western_australia = CustomRegion::try_from("Western Australia".to_owned()).unwrap();
+        assert_eq!(score.completed_regions(), vec![western_australia]);
+        assert!(!player.un_scored_activity().contains(&sightseeing));
+        assert!(matches!(
+            &score.ledger_of(ANIMALS)[..],
+            [LedgerEntry::AnimalPair { animal, points: 3, .. }] if animal == "Kangaroos"
+        ));
+        assert!(score.ledger_of(ACTIVITY).contains(&LedgerEntry::Activity {
+            activity: sightseeing,
+            cards: vec![card('B'), card('Z')],
+            points: 2,
+        }));
+
+        let total: CustomScoring = vec![score.clone(), score].into_iter().sum();
+        assert_eq!(total.total_score(), 2 * (6 + 10 + 14 + 3 + 2));
+    }
+
+    #[test]
+    fn test_serde() {
+        let card = card('E');
+        assert_eq!(card.name(), "Uluru");
+        let json = serde_json::to_string(&card).unwrap();
+        assert_eq!(json, "\"E\"");
+        assert_eq!(serde_json::from_str::<CustomCard>(&json).unwrap(), card);
+        assert!(serde_json::from_str::<CustomCard>("\"!\"").is_err());
+        assert!(serde_json::from_str::<CustomRegion>("\"Atlantis\"").is_err());
+    }
+}
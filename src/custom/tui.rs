@@ -0,0 +1,24 @@
+//! Implementations of [`tui`] related things for the custom rules.
+
+use crate::australia::tui::EditionUi;
+
+use super::rules::{cards::CustomCard, CustomEdition};
+
+pub mod map;
+
+impl EditionUi for CustomEdition {
+    type Map = map::Map;
+    fn card_details(card: &CustomCard) -> Vec<String> {
+        let mut details = vec![format!("Region : {:?}", card.region())];
+        if let Some((collection, _)) = card.collection() {
+            details.push(format!("Collection : {}", collection));
+        }
+        if let Some((animal, _)) = card.animal() {
+            details.push(format!("Animal : {}", animal));
+        }
+        if let Some(activity) = card.activity() {
+            details.push(format!("Activity : {:?}", activity));
+        }
+        details
+    }
+}
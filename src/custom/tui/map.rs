@@ -0,0 +1,76 @@
+//! Defines the map of a custom deck.
+//!
+//! A deck definition has no outline to draw, so the map lists every region on its own
+//! line followed by the sites in it.
+
+use ratatui::{style::Color, widgets::canvas::Shape};
+use tui::maps::{
+    sites::{Region, TouristSite},
+    Map as MapTrait,
+};
+
+use crate::custom::rules::cards::{CustomCard, CustomRegion};
+
+/// Where the sites start, the region names are printed to the left of it
+const SITES_X: f64 = 160.0;
+
+impl Region for CustomRegion {
+    fn default() -> Self {
+        CustomRegion::to_vec()[0]
+    }
+    fn coordinates(&self) -> (f64, f64) {
+        let step = Map::HEIGHT as f64 / (CustomRegion::to_vec().len() + 1) as f64;
+        (
+            SITES_X,
+            Map::HEIGHT as f64 - step * (self.index() + 1) as f64,
+        )
+    }
+}
+
+pub struct Map {
+    color: Color,
+    sites: Vec<TouristSite<CustomRegion>>,
+}
+impl MapTrait for Map {
+    type REGION = CustomRegion;
+    const WIDTH: usize = 458;
+    const HEIGHT: usize = 418;
+
+    fn default() -> Self {
+        let mut cards = CustomCard::all();
+        cards.sort_by_key(|card| card.region().index());
+        Self {
+            color: Color::White,
+            sites: cards
+                .into_iter()
+                .map(|card| TouristSite::new(card.name().to_owned(), card.to_char(), card.region()))
+                .collect(),
+        }
+    }
+
+    fn render(
+        &self,
+        ctx: &mut ratatui::widgets::canvas::Context<'_>,
+    ) -> Vec<TouristSite<CustomRegion>> {
+        for region in CustomRegion::to_vec() {
+            let (_, y) = region.coordinates();
+            ctx.print(0.0, y, region.name());
+        }
+        self.sites.to_vec()
+    }
+
+    fn map(&self) -> Vec<(usize, usize)> {
+        Vec::new()
+    }
+
+    fn set_color(&mut self, color: Color) {
+        self.color = color;
+    }
+
+    fn get_color(&self) -> Color {
+        self.color
+    }
+}
+impl Shape for Map {
+    fn draw(&self, _painter: &mut ratatui::widgets::canvas::Painter) {}
+}
@@ -0,0 +1,61 @@
+//! The boomerang europe rule set
+//!
+//! Europe is played on the same [`states`](crate::australia::rules::states) as
+//! australia, so the server and client are the generic ones from [`australia`](crate::australia).
+
+use std::net::TcpListener;
+
+use server::engine::limits::ConnectionLimits;
+use tokio::net::TcpStream;
+
+use crate::{
+    australia::{
+        bot::BotOptions,
+        play, play_headless,
+        rules::{
+            edition::Edition,
+            states::diagram::{self, DiagramFormat},
+        },
+        serve,
+        simulation::{self, Report, SimulationError, SimulationOptions},
+    },
+    rule_sets::ServerOptions,
+};
+
+use self::rules::EuropeEdition;
+
+pub mod rules;
+pub mod tui;
+
+/// The name that this rule set is registered under
+pub const NAME: &str = "europe";
+
+/// Runs a boomerang europe server
+pub async fn server(listener: TcpListener, limits: ConnectionLimits, options: ServerOptions) {
+    serve::<EuropeEdition>(listener, limits, options, NAME).await;
+}
+
+/// Runs a boomerang europe client on an already connected stream
+pub async fn client(stream: TcpStream, nickname: Option<String>) {
+    play::<EuropeEdition>(stream, nickname).await;
+}
+
+/// Runs a headless boomerang europe client on an already connected stream
+pub async fn bot(stream: TcpStream, options: BotOptions, nickname: Option<String>) {
+    play_headless::<EuropeEdition>(stream, options.strategy(), nickname).await;
+}
+
+/// Simulates boomerang europe games between bots
+pub fn simulate(options: &SimulationOptions) -> Result<Report, SimulationError> {
+    simulation::simulate::<EuropeEdition>(options)
+}
+
+/// Returns the fingerprint that clients of boomerang europe have to match
+pub fn fingerprint() -> Option<String> {
+    EuropeEdition::fingerprint()
+}
+
+/// Draws the state machine of the europe rule set
+pub fn diagram(format: DiagramFormat) -> String {
+    diagram::draw::<EuropeEdition>(NAME, format)
+}
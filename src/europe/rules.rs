@@ -0,0 +1,63 @@
+//! The boomerang europe rules
+//!
+//! The game flows like boomerang australia, so the states are shared with
+//! [`australia`](crate::australia::rules). Europe replaces the animals with transport
+//! and scores its categories automatically at the end of each round.
+
+pub mod cards;
+pub mod scoring;
+pub mod states;
+
+use crate::australia::rules::{cards::Collection, config::GameConfig, edition::Edition, Player};
+
+use self::{
+    cards::{Card, EuropeCard, EuropeDeck, EuropeanCategory, EuropeanRegion},
+    scoring::EuropeScoring,
+    states::ScoreRound,
+};
+
+/// The boomerang europe [`Edition`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EuropeEdition;
+
+pub type EuropePlayer = Player<EuropeEdition>;
+
+impl Edition for EuropeEdition {
+    type Card = EuropeCard;
+    type Region = EuropeanRegion;
+    type Activity = EuropeanCategory;
+    type Score = EuropeScoring;
+    type RoundScoring = ScoreRound;
+
+    fn deck() -> EuropeDeck {
+        EuropeDeck::default()
+    }
+    fn regions() -> Vec<EuropeanRegion> {
+        EuropeanRegion::to_vec()
+    }
+    fn activities() -> Vec<EuropeanCategory> {
+        EuropeanCategory::to_vec()
+    }
+    fn activity(card: &EuropeCard) -> Option<EuropeanCategory> {
+        card.category()
+    }
+    fn collection(card: &EuropeCard) -> Option<usize> {
+        card.collection().map(|collection| collection.score())
+    }
+    fn completed(region: &EuropeanRegion, visited: &[char]) -> bool {
+        region.completed(visited)
+    }
+    fn score(
+        _config: &GameConfig,
+        player: &mut EuropePlayer,
+        unclaimed: &[EuropeanRegion],
+        activity: Option<EuropeanCategory>,
+    ) -> EuropeScoring {
+        EuropeScoring::new()
+            .score_throw_catch(player)
+            .score_regions(player, unclaimed)
+            .score_collections(player)
+            .score_transport(player)
+            .score_category(player, activity)
+    }
+}
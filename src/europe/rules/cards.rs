@@ -0,0 +1,536 @@
+//! Defines all of the boomerang europe cards as types
+//!
+//! Also defines all of the data
+//! that relates to cards a enums
+
+use serde::{Deserialize, Serialize};
+
+use crate::australia::rules::{
+    cards::{Collection, Deck},
+    edition::Site,
+};
+
+pub trait Transport: PartialEq {
+    /// Returns the score for having visited this many distinct transports
+    fn score(distinct: usize) -> usize;
+}
+
+pub trait Card: std::fmt::Debug {
+    fn to_char(&self) -> char;
+    fn name(&self) -> &str;
+    fn number(&self) -> usize;
+    fn collection(&self) -> Option<EuropeanCollection>;
+    fn transport(&self) -> Option<EuropeanTransport>;
+    fn category(&self) -> Option<EuropeanCategory>;
+    fn region(&self) -> EuropeanRegion;
+}
+
+pub type EuropeDeck = Deck<EuropeCard>;
+
+impl tui::ui::UiElement for EuropeCard {
+    /// This should never be called
+    fn new() -> Self {
+        panic!("Card is not to be instantiated like this")
+    }
+}
+
+impl Site for EuropeCard {
+    fn site(&self) -> char {
+        self.to_char()
+    }
+}
+
+impl tui::ui::Card for EuropeCard {
+    fn get_name(&self) -> &str {
+        self.name()
+    }
+    fn number(&self) -> usize {
+        Card::number(self)
+    }
+}
+
+macro_rules! regions {
+    ($(($variant:ident,$name:literal, $number:literal $($site:literal)+))*) => {
+        #[derive(Debug, Serialize, Deserialize, Clone,Copy,PartialEq)]
+        pub enum EuropeanRegion {
+            $($variant,)*
+        }
+        impl From<EuropeanRegion> for String{
+            fn from(value: EuropeanRegion) -> String{
+                match value{
+                    $(
+                        EuropeanRegion::$variant => $name.to_owned(),
+                    )+
+                }
+            }
+        }
+        impl EuropeanRegion {
+            pub fn to_vec() -> Vec<EuropeanRegion> {
+                vec![$(EuropeanRegion::$variant,)*]
+            }
+            pub fn number(&self) -> usize{
+                match self {
+                    $(
+                        EuropeanRegion::$variant => $number,
+                    )+
+                }
+            }
+            pub fn completed(self,identifiers:&[char]) -> bool{
+                match self{
+                    $(
+                        EuropeanRegion::$variant => {
+                            [$($site,)+].iter().all(|el| identifiers.contains(el))
+                        }
+                    )+
+                }
+            }
+        }
+    };
+}
+macro_rules! categories {
+    ($(($variant:ident,$name:literal)),*) => {
+        #[derive(Debug, Serialize, Deserialize, Clone,Copy,PartialEq,Hash,Eq)]
+        pub enum EuropeanCategory {
+            $($variant,)*
+        }
+
+        impl From<EuropeanCategory> for String{
+            fn from(value: EuropeanCategory) -> String{
+                match value{
+                    $(
+                        EuropeanCategory::$variant => $name.to_owned(),
+                    )+
+                }
+            }
+        }
+        impl EuropeanCategory {
+            pub fn to_vec() -> Vec<EuropeanCategory> {
+                vec![$(EuropeanCategory::$variant,)*]
+            }
+        }
+    };
+}
+macro_rules! collections {
+    ($(($variant:ident,$name:literal,$score:literal))+) => {
+        #[derive(Debug, Serialize, Deserialize, Clone,Copy,PartialEq)]
+        pub enum EuropeanCollection {
+            $($variant,)*
+        }
+
+        impl From<EuropeanCollection> for String{
+            fn from(value: EuropeanCollection) -> String{
+                match value{
+                    $(
+                        EuropeanCollection::$variant => $name.to_owned(),
+                    )+
+                }
+            }
+        }
+        impl Collection for EuropeanCollection{
+            fn score(&self) -> usize{
+                match self{
+                    $(
+                        EuropeanCollection::$variant => $score,
+                    )+
+                }
+            }
+        }
+    };
+}
+macro_rules! transports {
+    ($(($variant:ident,$name:literal))+ scores : [$($score:literal),+]) => {
+        #[derive(Debug, Serialize, Deserialize, Clone,Copy,PartialEq,Hash,Eq)]
+        pub enum EuropeanTransport {
+            $($variant,)*
+        }
+
+        impl From<EuropeanTransport> for String{
+            fn from(value: EuropeanTransport) -> String{
+                match value{
+                    $(
+                        EuropeanTransport::$variant => $name.to_owned(),
+                    )+
+                }
+            }
+        }
+        impl Transport for EuropeanTransport{
+            fn score(distinct: usize) -> usize{
+                let scores = [$($score,)+];
+                scores[distinct.min(scores.len() - 1)]
+            }
+        }
+    };
+}
+
+collections! {
+    (Postcards,"Postcards",1)
+    (Stamps,"Stamps",2)
+    (Magnets,"Magnets",3)
+    (Souvenirs,"Souvenirs",5)
+}
+
+transports! {
+    (Train,"Train")
+    (Boat,"Boat")
+    (Bus,"Bus")
+    (Bicycle,"Bicycle")
+    scores : [0, 1, 3, 6, 10]
+}
+
+categories! {
+    (Art,"Art"),
+    (Cuisine,"Cuisine"),
+    (History,"History"),
+    (Nature,"Nature"),
+    (Architecture,"Architecture")
+}
+
+regions! {
+    (BritishIsles,"British Isles", 1 'A' 'B' 'C' 'D')
+    (Iberia,"Iberia", 5 'E' 'F' 'G' 'H')
+    (France,"France", 3 'I' 'J' 'K' 'L')
+    (Italy,"Italy", 6 'M' 'N' 'O' 'P')
+    (CentralEurope,"Central Europe", 2 'Q' 'R' 'S' 'T')
+    (Scandinavia,"Scandinavia", 4 'U' 'V' 'W' 'X')
+    (SouthEastEurope,"South East Europe", 7 'Y' 'Z' '*' '-')
+}
+
+macro_rules! card {
+    ($(
+        $name:ident : {
+            name : $str_rpr:literal
+            site : $site:literal
+            region : $region:ident
+            $(collection : $collection:ident)?
+            $(transport : $transport:ident)?
+            $(category : $category:ident)?
+        }
+    )+) => {
+        #[derive(Debug,Serialize,Deserialize,Clone,Copy,PartialEq,Hash,Eq)]
+        pub enum EuropeCard{
+            $(
+                $name,
+            )+
+        }
+
+        impl Card for EuropeCard {
+            fn to_char(&self) -> char{
+                match self{
+                    $(
+                        EuropeCard::$name => {$site},
+                    )+
+                }
+            }
+
+            fn region(&self) -> EuropeanRegion{
+                match self{
+                    $(
+                        EuropeCard::$name => EuropeanRegion::$region,
+                    )+
+                }
+            }
+
+            fn name(&self) -> &str{
+                match self{
+                    $(
+                        EuropeCard::$name => $str_rpr,
+                    )+
+                }
+            }
+
+            fn number(&self) -> usize{
+                self.region().number()
+            }
+            fn collection(&self) -> Option<EuropeanCollection>{
+                match self{
+                    $(
+                        EuropeCard::$name => {
+                            $(return Some(EuropeanCollection::$collection);)?
+                            #[allow(unreachable_code)]
+                            None
+                        },
+                    )+
+                }
+            }
+            fn transport(&self) -> Option<EuropeanTransport>{
+                match self{
+                    $(
+                        EuropeCard::$name => {
+                            $(return Some(EuropeanTransport::$transport);)?
+                            #[allow(unreachable_code)]
+                            None
+                        },
+                    )+
+                }
+            }
+            fn category(&self) -> Option<EuropeanCategory>{
+                match self{
+                    $(
+                        EuropeCard::$name => {
+                            $(return Some(EuropeanCategory::$category);)?
+                            #[allow(unreachable_code)]
+                            None
+                        },
+                    )+
+                }
+            }
+        }
+        impl Default for EuropeDeck{
+            fn default() -> Self{
+                Deck::new(vec![$(EuropeCard::$name,)+])
+            }
+        }
+    };
+}
+card! {
+    BigBen: {
+        name: "Big Ben"
+        site: 'A'
+        region: BritishIsles
+        collection: Postcards
+        category: Architecture
+    }
+
+    Stonehenge: {
+        name: "Stonehenge"
+        site: 'B'
+        region: BritishIsles
+        transport: Bus
+        category: History
+    }
+
+    EdinburghCastle: {
+        name: "Edinburgh Castle"
+        site: 'C'
+        region: BritishIsles
+        collection: Stamps
+        transport: Train
+    }
+
+    CliffsOfMoher: {
+        name: "Cliffs of Moher"
+        site: 'D'
+        region: BritishIsles
+        transport: Bicycle
+        category: Nature
+    }
+
+    SagradaFamilia: {
+        name: "Sagrada Família"
+        site: 'E'
+        region: Iberia
+        collection: Magnets
+        category: Architecture
+    }
+
+    Alhambra: {
+        name: "Alhambra"
+        site: 'F'
+        region: Iberia
+        transport: Bus
+        category: History
+    }
+
+    BelemTower: {
+        name: "Belém Tower"
+        site: 'G'
+        region: Iberia
+        collection: Postcards
+        transport: Boat
+    }
+
+    Mezquita: {
+        name: "Mezquita of Córdoba"
+        site: 'H'
+        region: Iberia
+        collection: Souvenirs
+        category: Art
+    }
+
+    EiffelTower: {
+        name: "Eiffel Tower"
+        site: 'I'
+        region: France
+        transport: Train
+        category: Architecture
+    }
+
+    MontSaintMichel: {
+        name: "Mont Saint-Michel"
+        site: 'J'
+        region: France
+        transport: Boat
+        category: History
+    }
+
+    Versailles: {
+        name: "Versailles"
+        site: 'K'
+        region: France
+        collection: Stamps
+        category: Art
+    }
+
+    Louvre: {
+        name: "The Louvre"
+        site: 'L'
+        region: France
+        collection: Postcards
+        category: Art
+    }
+
+    Colosseum: {
+        name: "The Colosseum"
+        site: 'M'
+        region: Italy
+        collection: Magnets
+        category: History
+    }
+
+    Pisa: {
+        name: "Leaning Tower of Pisa"
+        site: 'N'
+        region: Italy
+        transport: Train
+        category: Cuisine
+    }
+
+    Venice: {
+        name: "Venice"
+        site: 'O'
+        region: Italy
+        transport: Boat
+        category: Art
+    }
+
+    AmalfiCoast: {
+        name: "Amalfi Coast"
+        site: 'P'
+        region: Italy
+        collection: Souvenirs
+        category: Cuisine
+    }
+
+    BrandenburgGate: {
+        name: "Brandenburg Gate"
+        site: 'Q'
+        region: CentralEurope
+        transport: Train
+        category: History
+    }
+
+    Neuschwanstein: {
+        name: "Neuschwanstein"
+        site: 'R'
+        region: CentralEurope
+        collection: Magnets
+        transport: Bus
+    }
+
+    CharlesBridge: {
+        name: "Charles Bridge"
+        site: 'S'
+        region: CentralEurope
+        transport: Bicycle
+        category: Cuisine
+    }
+
+    Matterhorn: {
+        name: "Matterhorn"
+        site: 'T'
+        region: CentralEurope
+        collection: Stamps
+        category: Nature
+    }
+
+    Nyhavn: {
+        name: "Nyhavn"
+        site: 'U'
+        region: Scandinavia
+        transport: Bicycle
+        category: Cuisine
+    }
+
+    Geirangerfjord: {
+        name: "Geirangerfjord"
+        site: 'V'
+        region: Scandinavia
+        transport: Boat
+        category: Nature
+    }
+
+    GamlaStan: {
+        name: "Gamla Stan"
+        site: 'W'
+        region: Scandinavia
+        collection: Postcards
+        category: Architecture
+    }
+
+    Lofoten: {
+        name: "Lofoten"
+        site: 'X'
+        region: Scandinavia
+        collection: Souvenirs
+        category: Nature
+    }
+
+    Acropolis: {
+        name: "The Acropolis"
+        site: 'Y'
+        region: SouthEastEurope
+        transport: Bus
+        category: History
+    }
+
+    Santorini: {
+        name: "Santorini"
+        site: 'Z'
+        region: SouthEastEurope
+        transport: Boat
+        category: Cuisine
+    }
+
+    Dubrovnik: {
+        name: "Dubrovnik"
+        site: '*'
+        region: SouthEastEurope
+        collection: Magnets
+        category: Architecture
+    }
+
+    Meteora: {
+        name: "Meteora"
+        site: '-'
+        region: SouthEastEurope
+        collection: Stamps
+        category: Nature
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Card, EuropeDeck, EuropeanRegion, EuropeanTransport, Transport};
+
+    #[test]
+    fn test_deck() {
+        let deck = EuropeDeck::default();
+        let cards = deck.cards();
+        assert_eq!(cards.len(), 28);
+        for region in EuropeanRegion::to_vec() {
+            let sites: Vec<char> = cards
+                .iter()
+                .filter(|card| card.region() == region)
+                .map(|card| card.to_char())
+                .collect();
+            assert_eq!(sites.len(), 4);
+            assert!(region.completed(&sites));
+        }
+    }
+
+    #[test]
+    fn test_transport_table() {
+        let scores: Vec<usize> = (0..=5).map(EuropeanTransport::score).collect();
+        assert_eq!(scores, vec![0, 1, 3, 6, 10, 10]);
+    }
+}
@@ -0,0 +1,178 @@
+//! Defines methods and types to help with scoring a boomerang europe round.
+//!
+//! The throw and catch, the tourist sites and the collections are scored like boomerang
+//! australia, see [`Scoring`].
+
+use crate::australia::rules::{
+    config::ACTIVITY_POINTS,
+    scoring::{round_cards, LedgerEntry, Scoring},
+};
+
+use super::{
+    cards::{Card, EuropeanCategory, EuropeanTransport, Transport},
+    EuropeEdition, EuropePlayer,
+};
+
+/// The name of the points for the distinct means of transport
+pub const TRANSPORT: &str = "Transport";
+/// The name of the points for the category that was scored
+pub const CATEGORY: &str = "Category";
+
+pub type EuropeScoring = Scoring<EuropeEdition>;
+
+/// Returns the category that the player has not yet scored with the most cards this round.
+///
+/// Returns None if no such category would give any points.
+pub fn best_category(player: &EuropePlayer) -> Option<EuropeanCategory> {
+    let cards = round_cards(player);
+    let mut best = None;
+    let mut most = 1;
+    for category in player.un_scored_activity() {
+        let count = cards
+            .iter()
+            .filter(|card| card.category() == Some(category))
+            .count();
+        if count > most {
+            best = Some(category);
+            most = count;
+        }
+    }
+    best
+}
+
+// Builder pattern for scoring
+impl EuropeScoring {
+    /// Scores the number of distinct means of transport used this round
+    pub fn score_transport(mut self, player: &EuropePlayer) -> Self {
+        let mut distinct: Vec<EuropeanTransport> = Vec::new();
+        for transport in round_cards(player).iter().filter_map(|card| card.transport()) {
+            if !distinct.contains(&transport) {
+                distinct.push(transport);
+            }
+        }
+        let points = EuropeanTransport::score(distinct.len());
+        if !distinct.is_empty() {
+            self.explain(
+                TRANSPORT,
+                LedgerEntry::Transport {
+                    transports: distinct.into_iter().map(Into::into).collect(),
+                    points,
+                },
+            );
+        }
+        self.add(TRANSPORT, points);
+        self
+    }
+    /// Scores the cards that match the category that the player chose
+    pub fn score_category(
+        mut self,
+        player: &mut EuropePlayer,
+        category: Option<EuropeanCategory>,
+    ) -> Self {
+        let points = self.activity_points(CATEGORY, player, category, |cards| {
+            ACTIVITY_POINTS[cards.min(ACTIVITY_POINTS.len() - 1)]
+        });
+        self.add(CATEGORY, points);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        australia::rules::{edition::RoundScore, scoring::LedgerEntry},
+        europe::rules::{
+            cards::{EuropeCard, EuropeanCategory, EuropeanRegion},
+            EuropePlayer,
+        },
+    };
+
+    use super::{best_category, EuropeScoring, CATEGORY, TRANSPORT};
+
+    fn player() -> EuropePlayer {
+        let mut player = EuropePlayer::new(1).set_cards(vec![
+            EuropeCard::BigBen,
+            EuropeCard::Meteora,
+            EuropeCard::Stonehenge,
+            EuropeCard::EdinburghCastle,
+            EuropeCard::CliffsOfMoher,
+            EuropeCard::Acropolis,
+            EuropeCard::Alhambra,
+        ]);
+        player.discard(&0).unwrap();
+        player
+    }
+
+    #[test]
+    fn test_score_round() {
+        let mut player = player();
+        let category = best_category(&player);
+        assert_eq!(category, Some(EuropeanCategory::History));
+
+        let score = EuropeScoring::new()
+            .score_throw_catch(&player)
+            .score_regions(&mut player, &EuropeanRegion::to_vec())
+            .score_collections(&player)
+            .score_transport(&player)
+            .score_category(&mut player, category);
+        // Big Ben (1) is thrown and Meteora (7) is caught
+        assert_eq!(score.throw_catch(), 6);
+        // Postcards, Stamps and Stamps sum to 5 which is doubled
+        assert_eq!(
+            score.parts(),
+            vec![
+                ("Throw Catch", 6),
+                ("Tourist sites", 7 + 3),
+                ("Collections", 10),
+                ("Transport", 6),
+                ("Category", 4),
+            ]
+        );
+        assert_eq!(score.completed_regions(), vec![EuropeanRegion::BritishIsles]);
+        assert_eq!(score.total_score(), 36);
+        assert!(!player
+            .un_scored_activity()
+            .contains(&EuropeanCategory::History));
+        assert_eq!(best_category(&player), Some(EuropeanCategory::Nature));
+
+        // Every category is explained in the ledger
+        let ledger = score.ledger();
+        assert!(ledger.contains(&LedgerEntry::ThrowCatch {
+            throw: EuropeCard::BigBen,
+            catch: EuropeCard::Meteora,
+            points: 6,
+        }));
+        assert!(ledger.contains(&LedgerEntry::Region {
+            region: EuropeanRegion::BritishIsles,
+            points: 3,
+        }));
+        assert_eq!(score.ledger_of(TRANSPORT).len(), 1);
+        assert!(matches!(
+            score.ledger_of(CATEGORY)[..],
+            [LedgerEntry::Activity { points: 4, .. }]
+        ));
+        assert_eq!(RoundScore::ledger(&score).len(), ledger.len());
+    }
+
+    #[test]
+    fn test_sum() {
+        let mut player = player();
+        let score = EuropeScoring::new()
+            .score_collections(&player)
+            .score_regions(&mut player, &[]);
+        let total: EuropeScoring = vec![score.clone(), score].into_iter().sum();
+        assert_eq!(total.total_score(), 2 * (10 + 7));
+        assert!(total.completed_regions().is_empty());
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut score = EuropeScoring::new();
+        score.add(TRANSPORT, usize::MAX);
+        score.add(CATEGORY, 1);
+        assert_eq!(score.total_score(), usize::MAX);
+        let total: EuropeScoring = vec![score.clone(), score].into_iter().sum();
+        assert_eq!(total.points(TRANSPORT), usize::MAX);
+        assert_eq!(total.points(CATEGORY), 2);
+    }
+}
@@ -0,0 +1,111 @@
+//! Defines the states that boomerang europe does not share with
+//! boomerang australia.
+
+use tracing::info;
+
+use crate::{
+    australia::{
+        protocol::Event,
+        rules::{
+            meta::GameMetaData,
+            states::{
+                AsMetaData, DealingCards, Final, GameState, StateNode, Syncing, Transitions,
+            },
+        },
+    },
+    engine::rules::{Action, Error, New, Received},
+};
+
+use super::{scoring::best_category, EuropeEdition};
+
+/// Scores the round without asking the players anything, every player scores
+/// the category that gives them the most points.
+#[derive(Debug)]
+pub struct ScoreRound {
+    state: GameMetaData<EuropeEdition>,
+}
+
+impl From<GameMetaData<EuropeEdition>> for ScoreRound {
+    fn from(state: GameMetaData<EuropeEdition>) -> Self {
+        Self { state }
+    }
+}
+
+impl AsMetaData<EuropeEdition> for ScoreRound {
+    fn metadata(&mut self) -> &mut GameMetaData<EuropeEdition> {
+        &mut self.state
+    }
+}
+
+impl Transitions for ScoreRound {
+    const NAME: &'static str = "ScoreRound";
+    /// The game ends after the last round, otherwise a new round is dealt
+    fn transitions() -> Vec<StateNode> {
+        vec![
+            StateNode::of::<Syncing<EuropeEdition, DealingCards<EuropeEdition>>>(),
+            StateNode::of::<Final<EuropeEdition>>(),
+        ]
+    }
+}
+
+impl GameState<EuropeEdition> for ScoreRound {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+    fn get_next_action(
+        &mut self,
+        _players: &Vec<usize>,
+    ) -> (
+        tokio::time::Duration,
+        Vec<Action<New, Event<EuropeEdition>>>,
+        Option<Box<dyn GameState<EuropeEdition>>>,
+    ) {
+        info!("State : {:?}", self);
+        let categories: Vec<_> = self
+            .state
+            .get_players()
+            .iter()
+            .map(|player| (player.id(), best_category(player)))
+            .collect();
+        if self.state.score_round(&categories) {
+            // Final state, this means game is over
+            return (
+                tokio::time::Duration::from_millis(500),
+                Vec::new(),
+                Some(Box::new(Final::from(self.state.clone()))),
+            );
+        }
+        let actions = self
+            .state
+            .get_players()
+            .iter()
+            .map(|player| Action::new(player.id().into(), Event::NewRound))
+            .collect();
+        self.state.new_round();
+        (
+            tokio::time::Duration::from_millis(500),
+            actions,
+            Some(Box::new(Syncing::new(
+                self.state.clone(),
+                Box::new(DealingCards::from(self.state.clone())),
+            ))),
+        )
+    }
+
+    fn register_message(
+        &mut self,
+        _action: &Action<New, Event<EuropeEdition>>,
+    ) -> Result<Option<Box<dyn GameState<EuropeEdition>>>, Error> {
+        Err(Error::UnexpectedMessage)
+    }
+
+    fn register_response(
+        &mut self,
+        _action: (Event<EuropeEdition>, &Action<Received, Event<EuropeEdition>>),
+    ) -> Result<Option<Box<dyn GameState<EuropeEdition>>>, Error> {
+        Err(Error::UnexpectedResponse)
+    }
+    fn metadata(&mut self) -> Option<&mut GameMetaData<EuropeEdition>> {
+        Some(&mut self.state)
+    }
+}
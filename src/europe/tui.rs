@@ -0,0 +1,27 @@
+//! Implementations of [`tui`] related things for boomerang europe.
+
+use crate::australia::tui::EditionUi;
+
+use super::rules::{
+    cards::{Card, EuropeCard},
+    EuropeEdition,
+};
+
+pub mod map;
+
+impl EditionUi for EuropeEdition {
+    type Map = map::europe::Map;
+    fn card_details(card: &EuropeCard) -> Vec<String> {
+        let mut details = vec![format!("Region : {:?}", card.region())];
+        if let Some(collection) = card.collection() {
+            details.push(format!("Collection : {:?}", collection));
+        }
+        if let Some(transport) = card.transport() {
+            details.push(format!("Transport : {:?}", transport));
+        }
+        if let Some(category) = card.category() {
+            details.push(format!("Category : {:?}", category));
+        }
+        details
+    }
+}
@@ -0,0 +1,4 @@
+//! Defines the boomerang europe game map
+
+pub mod europe;
+pub mod sites;
@@ -0,0 +1,2686 @@
+//! Defines a dot matrix map of europe.
+
+use ratatui::{style::Color, widgets::canvas::Shape};
+use tui::maps::{sites::TouristSite, Map as MapTrait};
+
+use crate::europe::rules::cards::EuropeanRegion;
+
+pub struct Map {
+    color: Color,
+    sites: Vec<TouristSite<EuropeanRegion>>,
+}
+impl MapTrait for Map {
+    type REGION = EuropeanRegion;
+    const WIDTH: usize = 320;
+    const HEIGHT: usize = 444;
+
+    fn default() -> Self {
+        Self {
+            color: Color::White,
+            sites: EuropeanRegion::sites(),
+        }
+    }
+
+    fn render(&self, ctx: &mut ratatui::widgets::canvas::Context<'_>) -> Vec<TouristSite<EuropeanRegion>> {
+        ctx.draw(self);
+        self.sites.to_vec()
+    }
+
+    fn map(&self) -> Vec<(usize, usize)> {
+        [
+            (265, 6),
+            (266, 6),
+            (267, 6),
+            (268, 6),
+            (269, 6),
+            (270, 6),
+            (271, 6),
+            (272, 6),
+            (273, 6),
+            (274, 6),
+            (275, 6),
+            (263, 7),
+            (264, 7),
+            (276, 7),
+            (277, 7),
+            (278, 7),
+            (279, 7),
+            (280, 7),
+            (281, 7),
+            (282, 7),
+            (283, 7),
+            (284, 7),
+            (285, 7),
+            (286, 7),
+            (261, 8),
+            (262, 8),
+            (287, 8),
+            (288, 8),
+            (289, 8),
+            (258, 9),
+            (259, 9),
+            (260, 9),
+            (290, 9),
+            (291, 9),
+            (292, 9),
+            (256, 10),
+            (257, 10),
+            (293, 10),
+            (294, 10),
+            (295, 10),
+            (254, 11),
+            (255, 11),
+            (296, 11),
+            (297, 11),
+            (298, 11),
+            (251, 12),
+            (252, 12),
+            (253, 12),
+            (299, 12),
+            (300, 12),
+            (301, 12),
+            (248, 13),
+            (249, 13),
+            (250, 13),
+            (302, 13),
+            (303, 13),
+            (304, 13),
+            (305, 13),
+            (245, 14),
+            (246, 14),
+            (247, 14),
+            (306, 14),
+            (307, 14),
+            (308, 14),
+            (242, 15),
+            (243, 15),
+            (244, 15),
+            (309, 15),
+            (310, 15),
+            (311, 15),
+            (239, 16),
+            (240, 16),
+            (241, 16),
+            (312, 16),
+            (313, 16),
+            (314, 16),
+            (315, 16),
+            (235, 17),
+            (236, 17),
+            (237, 17),
+            (238, 17),
+            (316, 17),
+            (317, 17),
+            (229, 18),
+            (230, 18),
+            (231, 18),
+            (232, 18),
+            (233, 18),
+            (234, 18),
+            (225, 19),
+            (226, 19),
+            (227, 19),
+            (228, 19),
+            (223, 20),
+            (224, 20),
+            (221, 21),
+            (222, 21),
+            (219, 22),
+            (220, 22),
+            (217, 23),
+            (218, 23),
+            (215, 24),
+            (216, 24),
+            (213, 25),
+            (214, 25),
+            (212, 26),
+            (211, 27),
+            (209, 28),
+            (210, 28),
+            (208, 29),
+            (207, 30),
+            (205, 31),
+            (206, 31),
+            (204, 32),
+            (203, 33),
+            (201, 34),
+            (202, 34),
+            (200, 35),
+            (198, 36),
+            (199, 36),
+            (197, 37),
+            (196, 38),
+            (195, 39),
+            (194, 40),
+            (193, 41),
+            (192, 42),
+            (192, 43),
+            (191, 44),
+            (190, 45),
+            (189, 46),
+            (188, 47),
+            (187, 48),
+            (186, 49),
+            (186, 50),
+            (185, 51),
+            (184, 52),
+            (183, 53),
+            (182, 54),
+            (182, 55),
+            (181, 56),
+            (180, 57),
+            (179, 58),
+            (178, 59),
+            (178, 60),
+            (177, 61),
+            (177, 62),
+            (176, 63),
+            (176, 64),
+            (175, 65),
+            (174, 66),
+            (174, 67),
+            (173, 68),
+            (173, 69),
+            (172, 70),
+            (251, 70),
+            (252, 70),
+            (253, 70),
+            (254, 70),
+            (255, 70),
+            (256, 70),
+            (257, 70),
+            (258, 70),
+            (259, 70),
+            (260, 70),
+            (261, 70),
+            (262, 70),
+            (263, 70),
+            (171, 71),
+            (249, 71),
+            (250, 71),
+            (264, 71),
+            (170, 72),
+            (246, 72),
+            (247, 72),
+            (248, 72),
+            (265, 72),
+            (170, 73),
+            (244, 73),
+            (245, 73),
+            (266, 73),
+            (169, 74),
+            (244, 74),
+            (267, 74),
+            (168, 75),
+            (243, 75),
+            (268, 75),
+            (167, 76),
+            (242, 76),
+            (269, 76),
+            (166, 77),
+            (242, 77),
+            (267, 77),
+            (268, 77),
+            (166, 78),
+            (242, 78),
+            (266, 78),
+            (165, 79),
+            (241, 79),
+            (264, 79),
+            (265, 79),
+            (164, 80),
+            (240, 80),
+            (263, 80),
+            (163, 81),
+            (240, 81),
+            (262, 81),
+            (163, 82),
+            (240, 82),
+            (261, 82),
+            (162, 83),
+            (239, 83),
+            (260, 83),
+            (161, 84),
+            (238, 84),
+            (259, 84),
+            (160, 85),
+            (238, 85),
+            (258, 85),
+            (159, 86),
+            (237, 86),
+            (257, 86),
+            (158, 87),
+            (237, 87),
+            (256, 87),
+            (158, 88),
+            (236, 88),
+            (254, 88),
+            (255, 88),
+            (157, 89),
+            (236, 89),
+            (253, 89),
+            (156, 90),
+            (235, 90),
+            (252, 90),
+            (155, 91),
+            (235, 91),
+            (251, 91),
+            (154, 92),
+            (234, 92),
+            (250, 92),
+            (152, 93),
+            (153, 93),
+            (234, 93),
+            (249, 93),
+            (150, 94),
+            (151, 94),
+            (232, 94),
+            (233, 94),
+            (248, 94),
+            (149, 95),
+            (229, 95),
+            (230, 95),
+            (231, 95),
+            (247, 95),
+            (147, 96),
+            (148, 96),
+            (227, 96),
+            (228, 96),
+            (246, 96),
+            (145, 97),
+            (146, 97),
+            (224, 97),
+            (225, 97),
+            (226, 97),
+            (245, 97),
+            (144, 98),
+            (222, 98),
+            (223, 98),
+            (245, 98),
+            (142, 99),
+            (143, 99),
+            (221, 99),
+            (244, 99),
+            (140, 100),
+            (141, 100),
+            (220, 100),
+            (243, 100),
+            (138, 101),
+            (139, 101),
+            (219, 101),
+            (242, 101),
+            (136, 102),
+            (137, 102),
+            (218, 102),
+            (242, 102),
+            (134, 103),
+            (135, 103),
+            (217, 103),
+            (241, 103),
+            (133, 104),
+            (217, 104),
+            (240, 104),
+            (131, 105),
+            (132, 105),
+            (216, 105),
+            (240, 105),
+            (130, 106),
+            (215, 106),
+            (239, 106),
+            (129, 107),
+            (214, 107),
+            (239, 107),
+            (127, 108),
+            (128, 108),
+            (213, 108),
+            (239, 108),
+            (126, 109),
+            (212, 109),
+            (239, 109),
+            (124, 110),
+            (125, 110),
+            (212, 110),
+            (239, 110),
+            (123, 111),
+            (212, 111),
+            (239, 111),
+            (122, 112),
+            (211, 112),
+            (240, 112),
+            (121, 113),
+            (211, 113),
+            (240, 113),
+            (119, 114),
+            (120, 114),
+            (211, 114),
+            (240, 114),
+            (118, 115),
+            (211, 115),
+            (240, 115),
+            (118, 116),
+            (211, 116),
+            (240, 116),
+            (118, 117),
+            (210, 117),
+            (240, 117),
+            (118, 118),
+            (210, 118),
+            (240, 118),
+            (118, 119),
+            (210, 119),
+            (240, 119),
+            (118, 120),
+            (210, 120),
+            (240, 120),
+            (118, 121),
+            (210, 121),
+            (240, 121),
+            (118, 122),
+            (210, 122),
+            (240, 122),
+            (118, 123),
+            (209, 123),
+            (240, 123),
+            (118, 124),
+            (209, 124),
+            (240, 124),
+            (118, 125),
+            (209, 125),
+            (240, 125),
+            (118, 126),
+            (209, 126),
+            (240, 126),
+            (118, 127),
+            (209, 127),
+            (240, 127),
+            (118, 128),
+            (209, 128),
+            (240, 128),
+            (118, 129),
+            (209, 129),
+            (240, 129),
+            (118, 130),
+            (209, 130),
+            (240, 130),
+            (118, 131),
+            (209, 131),
+            (240, 131),
+            (118, 132),
+            (209, 132),
+            (240, 132),
+            (118, 133),
+            (210, 133),
+            (211, 133),
+            (241, 133),
+            (242, 133),
+            (280, 133),
+            (281, 133),
+            (282, 133),
+            (118, 134),
+            (212, 134),
+            (213, 134),
+            (243, 134),
+            (276, 134),
+            (277, 134),
+            (278, 134),
+            (279, 134),
+            (283, 134),
+            (284, 134),
+            (285, 134),
+            (286, 134),
+            (118, 135),
+            (214, 135),
+            (215, 135),
+            (244, 135),
+            (272, 135),
+            (273, 135),
+            (274, 135),
+            (275, 135),
+            (287, 135),
+            (288, 135),
+            (289, 135),
+            (290, 135),
+            (118, 136),
+            (216, 136),
+            (217, 136),
+            (245, 136),
+            (246, 136),
+            (268, 136),
+            (269, 136),
+            (270, 136),
+            (271, 136),
+            (291, 136),
+            (292, 136),
+            (293, 136),
+            (294, 136),
+            (119, 137),
+            (218, 137),
+            (247, 137),
+            (264, 137),
+            (265, 137),
+            (266, 137),
+            (267, 137),
+            (295, 137),
+            (296, 137),
+            (297, 137),
+            (119, 138),
+            (218, 138),
+            (248, 138),
+            (259, 138),
+            (260, 138),
+            (261, 138),
+            (262, 138),
+            (263, 138),
+            (298, 138),
+            (299, 138),
+            (119, 139),
+            (219, 139),
+            (249, 139),
+            (250, 139),
+            (254, 139),
+            (255, 139),
+            (256, 139),
+            (257, 139),
+            (258, 139),
+            (300, 139),
+            (301, 139),
+            (119, 140),
+            (219, 140),
+            (251, 140),
+            (252, 140),
+            (253, 140),
+            (302, 140),
+            (303, 140),
+            (119, 141),
+            (220, 141),
+            (299, 141),
+            (300, 141),
+            (301, 141),
+            (119, 142),
+            (220, 142),
+            (296, 142),
+            (297, 142),
+            (298, 142),
+            (120, 143),
+            (221, 143),
+            (294, 143),
+            (295, 143),
+            (120, 144),
+            (221, 144),
+            (269, 144),
+            (270, 144),
+            (271, 144),
+            (272, 144),
+            (273, 144),
+            (274, 144),
+            (275, 144),
+            (276, 144),
+            (277, 144),
+            (278, 144),
+            (279, 144),
+            (291, 144),
+            (292, 144),
+            (293, 144),
+            (120, 145),
+            (159, 145),
+            (220, 145),
+            (265, 145),
+            (266, 145),
+            (267, 145),
+            (268, 145),
+            (280, 145),
+            (281, 145),
+            (282, 145),
+            (283, 145),
+            (284, 145),
+            (285, 145),
+            (286, 145),
+            (287, 145),
+            (288, 145),
+            (289, 145),
+            (290, 145),
+            (120, 146),
+            (158, 146),
+            (160, 146),
+            (219, 146),
+            (261, 146),
+            (262, 146),
+            (263, 146),
+            (264, 146),
+            (121, 147),
+            (158, 147),
+            (161, 147),
+            (218, 147),
+            (257, 147),
+            (258, 147),
+            (259, 147),
+            (260, 147),
+            (121, 148),
+            (157, 148),
+            (162, 148),
+            (218, 148),
+            (255, 148),
+            (256, 148),
+            (121, 149),
+            (156, 149),
+            (162, 149),
+            (217, 149),
+            (255, 149),
+            (121, 150),
+            (156, 150),
+            (163, 150),
+            (216, 150),
+            (255, 150),
+            (122, 151),
+            (155, 151),
+            (164, 151),
+            (215, 151),
+            (255, 151),
+            (122, 152),
+            (154, 152),
+            (164, 152),
+            (214, 152),
+            (255, 152),
+            (122, 153),
+            (153, 153),
+            (165, 153),
+            (213, 153),
+            (255, 153),
+            (122, 154),
+            (152, 154),
+            (165, 154),
+            (212, 154),
+            (255, 154),
+            (123, 155),
+            (151, 155),
+            (166, 155),
+            (212, 155),
+            (255, 155),
+            (44, 156),
+            (45, 156),
+            (46, 156),
+            (47, 156),
+            (48, 156),
+            (49, 156),
+            (50, 156),
+            (51, 156),
+            (52, 156),
+            (53, 156),
+            (54, 156),
+            (55, 156),
+            (56, 156),
+            (57, 156),
+            (58, 156),
+            (59, 156),
+            (123, 156),
+            (149, 156),
+            (150, 156),
+            (166, 156),
+            (211, 156),
+            (255, 156),
+            (44, 157),
+            (58, 157),
+            (124, 157),
+            (125, 157),
+            (148, 157),
+            (167, 157),
+            (210, 157),
+            (256, 157),
+            (257, 157),
+            (43, 158),
+            (57, 158),
+            (126, 158),
+            (147, 158),
+            (167, 158),
+            (209, 158),
+            (258, 158),
+            (259, 158),
+            (43, 159),
+            (56, 159),
+            (127, 159),
+            (146, 159),
+            (168, 159),
+            (208, 159),
+            (260, 159),
+            (261, 159),
+            (43, 160),
+            (56, 160),
+            (128, 160),
+            (129, 160),
+            (143, 160),
+            (144, 160),
+            (145, 160),
+            (168, 160),
+            (207, 160),
+            (262, 160),
+            (43, 161),
+            (55, 161),
+            (130, 161),
+            (140, 161),
+            (141, 161),
+            (142, 161),
+            (168, 161),
+            (207, 161),
+            (262, 161),
+            (42, 162),
+            (54, 162),
+            (131, 162),
+            (132, 162),
+            (135, 162),
+            (136, 162),
+            (137, 162),
+            (138, 162),
+            (139, 162),
+            (168, 162),
+            (206, 162),
+            (262, 162),
+            (42, 163),
+            (53, 163),
+            (133, 163),
+            (134, 163),
+            (168, 163),
+            (205, 163),
+            (262, 163),
+            (42, 164),
+            (52, 164),
+            (53, 164),
+            (169, 164),
+            (204, 164),
+            (262, 164),
+            (42, 165),
+            (54, 165),
+            (169, 165),
+            (204, 165),
+            (262, 165),
+            (42, 166),
+            (55, 166),
+            (56, 166),
+            (57, 166),
+            (169, 166),
+            (204, 166),
+            (248, 166),
+            (249, 166),
+            (262, 166),
+            (41, 167),
+            (58, 167),
+            (59, 167),
+            (60, 167),
+            (61, 167),
+            (62, 167),
+            (160, 167),
+            (169, 167),
+            (204, 167),
+            (247, 167),
+            (250, 167),
+            (261, 167),
+            (41, 168),
+            (63, 168),
+            (64, 168),
+            (65, 168),
+            (66, 168),
+            (67, 168),
+            (158, 168),
+            (159, 168),
+            (160, 168),
+            (169, 168),
+            (204, 168),
+            (244, 168),
+            (245, 168),
+            (246, 168),
+            (250, 168),
+            (261, 168),
+            (41, 169),
+            (67, 169),
+            (157, 169),
+            (160, 169),
+            (170, 169),
+            (204, 169),
+            (243, 169),
+            (251, 169),
+            (261, 169),
+            (41, 170),
+            (67, 170),
+            (156, 170),
+            (160, 170),
+            (170, 170),
+            (204, 170),
+            (241, 170),
+            (242, 170),
+            (252, 170),
+            (261, 170),
+            (41, 171),
+            (67, 171),
+            (155, 171),
+            (159, 171),
+            (170, 171),
+            (204, 171),
+            (241, 171),
+            (252, 171),
+            (261, 171),
+            (40, 172),
+            (66, 172),
+            (153, 172),
+            (154, 172),
+            (159, 172),
+            (170, 172),
+            (204, 172),
+            (240, 172),
+            (253, 172),
+            (261, 172),
+            (40, 173),
+            (66, 173),
+            (151, 173),
+            (152, 173),
+            (159, 173),
+            (170, 173),
+            (203, 173),
+            (240, 173),
+            (254, 173),
+            (260, 173),
+            (261, 173),
+            (40, 174),
+            (66, 174),
+            (149, 174),
+            (150, 174),
+            (159, 174),
+            (171, 174),
+            (203, 174),
+            (240, 174),
+            (254, 174),
+            (257, 174),
+            (258, 174),
+            (259, 174),
+            (40, 175),
+            (66, 175),
+            (147, 175),
+            (148, 175),
+            (159, 175),
+            (171, 175),
+            (203, 175),
+            (239, 175),
+            (255, 175),
+            (256, 175),
+            (39, 176),
+            (66, 176),
+            (145, 176),
+            (146, 176),
+            (159, 176),
+            (172, 176),
+            (203, 176),
+            (239, 176),
+            (39, 177),
+            (66, 177),
+            (143, 177),
+            (144, 177),
+            (158, 177),
+            (172, 177),
+            (203, 177),
+            (238, 177),
+            (38, 178),
+            (65, 178),
+            (141, 178),
+            (142, 178),
+            (158, 178),
+            (172, 178),
+            (203, 178),
+            (238, 178),
+            (37, 179),
+            (65, 179),
+            (141, 179),
+            (158, 179),
+            (173, 179),
+            (203, 179),
+            (238, 179),
+            (37, 180),
+            (65, 180),
+            (141, 180),
+            (158, 180),
+            (173, 180),
+            (203, 180),
+            (237, 180),
+            (36, 181),
+            (65, 181),
+            (141, 181),
+            (158, 181),
+            (173, 181),
+            (202, 181),
+            (237, 181),
+            (36, 182),
+            (65, 182),
+            (141, 182),
+            (159, 182),
+            (160, 182),
+            (174, 182),
+            (201, 182),
+            (237, 182),
+            (37, 183),
+            (65, 183),
+            (141, 183),
+            (161, 183),
+            (174, 183),
+            (200, 183),
+            (237, 183),
+            (37, 184),
+            (64, 184),
+            (141, 184),
+            (162, 184),
+            (175, 184),
+            (200, 184),
+            (237, 184),
+            (37, 185),
+            (64, 185),
+            (141, 185),
+            (161, 185),
+            (175, 185),
+            (199, 185),
+            (237, 185),
+            (38, 186),
+            (64, 186),
+            (141, 186),
+            (159, 186),
+            (160, 186),
+            (171, 186),
+            (172, 186),
+            (175, 186),
+            (198, 186),
+            (237, 186),
+            (38, 187),
+            (64, 187),
+            (141, 187),
+            (158, 187),
+            (169, 187),
+            (170, 187),
+            (172, 187),
+            (175, 187),
+            (196, 187),
+            (197, 187),
+            (238, 187),
+            (38, 188),
+            (65, 188),
+            (141, 188),
+            (157, 188),
+            (167, 188),
+            (168, 188),
+            (173, 188),
+            (176, 188),
+            (194, 188),
+            (195, 188),
+            (238, 188),
+            (38, 189),
+            (66, 189),
+            (141, 189),
+            (156, 189),
+            (165, 189),
+            (166, 189),
+            (173, 189),
+            (176, 189),
+            (192, 189),
+            (193, 189),
+            (238, 189),
+            (39, 190),
+            (67, 190),
+            (68, 190),
+            (141, 190),
+            (156, 190),
+            (163, 190),
+            (164, 190),
+            (173, 190),
+            (176, 190),
+            (190, 190),
+            (191, 190),
+            (238, 190),
+            (39, 191),
+            (69, 191),
+            (141, 191),
+            (155, 191),
+            (163, 191),
+            (174, 191),
+            (176, 191),
+            (188, 191),
+            (189, 191),
+            (238, 191),
+            (39, 192),
+            (70, 192),
+            (141, 192),
+            (154, 192),
+            (163, 192),
+            (173, 192),
+            (174, 192),
+            (177, 192),
+            (185, 192),
+            (186, 192),
+            (187, 192),
+            (238, 192),
+            (40, 193),
+            (70, 193),
+            (142, 193),
+            (154, 193),
+            (163, 193),
+            (171, 193),
+            (172, 193),
+            (177, 193),
+            (180, 193),
+            (181, 193),
+            (182, 193),
+            (183, 193),
+            (184, 193),
+            (238, 193),
+            (40, 194),
+            (70, 194),
+            (142, 194),
+            (153, 194),
+            (163, 194),
+            (169, 194),
+            (170, 194),
+            (177, 194),
+            (178, 194),
+            (179, 194),
+            (237, 194),
+            (41, 195),
+            (71, 195),
+            (143, 195),
+            (153, 195),
+            (163, 195),
+            (167, 195),
+            (168, 195),
+            (237, 195),
+            (42, 196),
+            (71, 196),
+            (143, 196),
+            (154, 196),
+            (163, 196),
+            (165, 196),
+            (166, 196),
+            (236, 196),
+            (21, 197),
+            (22, 197),
+            (23, 197),
+            (24, 197),
+            (25, 197),
+            (26, 197),
+            (27, 197),
+            (28, 197),
+            (29, 197),
+            (30, 197),
+            (31, 197),
+            (32, 197),
+            (33, 197),
+            (34, 197),
+            (35, 197),
+            (36, 197),
+            (42, 197),
+            (71, 197),
+            (144, 197),
+            (154, 197),
+            (163, 197),
+            (164, 197),
+            (235, 197),
+            (21, 198),
+            (36, 198),
+            (43, 198),
+            (71, 198),
+            (144, 198),
+            (154, 198),
+            (234, 198),
+            (20, 199),
+            (37, 199),
+            (44, 199),
+            (71, 199),
+            (145, 199),
+            (154, 199),
+            (234, 199),
+            (20, 200),
+            (37, 200),
+            (44, 200),
+            (55, 200),
+            (56, 200),
+            (57, 200),
+            (72, 200),
+            (145, 200),
+            (154, 200),
+            (233, 200),
+            (20, 201),
+            (38, 201),
+            (45, 201),
+            (49, 201),
+            (50, 201),
+            (51, 201),
+            (52, 201),
+            (53, 201),
+            (54, 201),
+            (57, 201),
+            (72, 201),
+            (145, 201),
+            (155, 201),
+            (232, 201),
+            (20, 202),
+            (38, 202),
+            (46, 202),
+            (47, 202),
+            (48, 202),
+            (57, 202),
+            (72, 202),
+            (146, 202),
+            (155, 202),
+            (231, 202),
+            (19, 203),
+            (39, 203),
+            (57, 203),
+            (73, 203),
+            (146, 203),
+            (156, 203),
+            (213, 203),
+            (214, 203),
+            (215, 203),
+            (216, 203),
+            (217, 203),
+            (218, 203),
+            (219, 203),
+            (229, 203),
+            (230, 203),
+            (19, 204),
+            (39, 204),
+            (57, 204),
+            (74, 204),
+            (146, 204),
+            (157, 204),
+            (205, 204),
+            (206, 204),
+            (207, 204),
+            (208, 204),
+            (209, 204),
+            (210, 204),
+            (211, 204),
+            (212, 204),
+            (220, 204),
+            (221, 204),
+            (222, 204),
+            (228, 204),
+            (19, 205),
+            (40, 205),
+            (57, 205),
+            (75, 205),
+            (146, 205),
+            (158, 205),
+            (204, 205),
+            (223, 205),
+            (224, 205),
+            (227, 205),
+            (19, 206),
+            (40, 206),
+            (58, 206),
+            (76, 206),
+            (146, 206),
+            (158, 206),
+            (174, 206),
+            (175, 206),
+            (176, 206),
+            (201, 206),
+            (202, 206),
+            (203, 206),
+            (225, 206),
+            (226, 206),
+            (18, 207),
+            (41, 207),
+            (58, 207),
+            (76, 207),
+            (146, 207),
+            (159, 207),
+            (172, 207),
+            (173, 207),
+            (177, 207),
+            (178, 207),
+            (179, 207),
+            (180, 207),
+            (200, 207),
+            (16, 208),
+            (17, 208),
+            (18, 208),
+            (41, 208),
+            (58, 208),
+            (77, 208),
+            (146, 208),
+            (160, 208),
+            (170, 208),
+            (171, 208),
+            (181, 208),
+            (182, 208),
+            (183, 208),
+            (197, 208),
+            (198, 208),
+            (199, 208),
+            (10, 209),
+            (11, 209),
+            (12, 209),
+            (13, 209),
+            (14, 209),
+            (15, 209),
+            (41, 209),
+            (58, 209),
+            (78, 209),
+            (147, 209),
+            (161, 209),
+            (162, 209),
+            (169, 209),
+            (184, 209),
+            (195, 209),
+            (196, 209),
+            (7, 210),
+            (8, 210),
+            (9, 210),
+            (40, 210),
+            (58, 210),
+            (79, 210),
+            (147, 210),
+            (163, 210),
+            (164, 210),
+            (167, 210),
+            (168, 210),
+            (184, 210),
+            (191, 210),
+            (192, 210),
+            (193, 210),
+            (194, 210),
+            (8, 211),
+            (40, 211),
+            (58, 211),
+            (80, 211),
+            (147, 211),
+            (165, 211),
+            (166, 211),
+            (185, 211),
+            (188, 211),
+            (189, 211),
+            (190, 211),
+            (8, 212),
+            (39, 212),
+            (58, 212),
+            (80, 212),
+            (146, 212),
+            (186, 212),
+            (187, 212),
+            (9, 213),
+            (39, 213),
+            (58, 213),
+            (81, 213),
+            (146, 213),
+            (9, 214),
+            (38, 214),
+            (58, 214),
+            (81, 214),
+            (145, 214),
+            (10, 215),
+            (38, 215),
+            (58, 215),
+            (81, 215),
+            (145, 215),
+            (11, 216),
+            (38, 216),
+            (59, 216),
+            (82, 216),
+            (130, 216),
+            (131, 216),
+            (132, 216),
+            (133, 216),
+            (134, 216),
+            (135, 216),
+            (136, 216),
+            (137, 216),
+            (138, 216),
+            (139, 216),
+            (140, 216),
+            (141, 216),
+            (142, 216),
+            (143, 216),
+            (144, 216),
+            (11, 217),
+            (37, 217),
+            (59, 217),
+            (82, 217),
+            (125, 217),
+            (126, 217),
+            (127, 217),
+            (128, 217),
+            (129, 217),
+            (12, 218),
+            (37, 218),
+            (48, 218),
+            (49, 218),
+            (50, 218),
+            (59, 218),
+            (83, 218),
+            (121, 218),
+            (122, 218),
+            (123, 218),
+            (124, 218),
+            (12, 219),
+            (36, 219),
+            (48, 219),
+            (51, 219),
+            (52, 219),
+            (53, 219),
+            (54, 219),
+            (55, 219),
+            (56, 219),
+            (59, 219),
+            (83, 219),
+            (120, 219),
+            (13, 220),
+            (36, 220),
+            (49, 220),
+            (57, 220),
+            (58, 220),
+            (59, 220),
+            (83, 220),
+            (119, 220),
+            (12, 221),
+            (36, 221),
+            (49, 221),
+            (84, 221),
+            (118, 221),
+            (12, 222),
+            (36, 222),
+            (50, 222),
+            (84, 222),
+            (118, 222),
+            (11, 223),
+            (36, 223),
+            (50, 223),
+            (85, 223),
+            (117, 223),
+            (10, 224),
+            (36, 224),
+            (50, 224),
+            (86, 224),
+            (87, 224),
+            (116, 224),
+            (9, 225),
+            (36, 225),
+            (51, 225),
+            (88, 225),
+            (116, 225),
+            (9, 226),
+            (36, 226),
+            (51, 226),
+            (89, 226),
+            (115, 226),
+            (8, 227),
+            (37, 227),
+            (51, 227),
+            (90, 227),
+            (91, 227),
+            (115, 227),
+            (8, 228),
+            (37, 228),
+            (51, 228),
+            (92, 228),
+            (115, 228),
+            (8, 229),
+            (37, 229),
+            (50, 229),
+            (93, 229),
+            (114, 229),
+            (8, 230),
+            (37, 230),
+            (50, 230),
+            (93, 230),
+            (114, 230),
+            (7, 231),
+            (37, 231),
+            (50, 231),
+            (93, 231),
+            (114, 231),
+            (7, 232),
+            (37, 232),
+            (50, 232),
+            (93, 232),
+            (113, 232),
+            (7, 233),
+            (33, 233),
+            (34, 233),
+            (35, 233),
+            (36, 233),
+            (37, 233),
+            (48, 233),
+            (49, 233),
+            (93, 233),
+            (113, 233),
+            (7, 234),
+            (31, 234),
+            (32, 234),
+            (46, 234),
+            (47, 234),
+            (93, 234),
+            (113, 234),
+            (7, 235),
+            (29, 235),
+            (30, 235),
+            (44, 235),
+            (45, 235),
+            (93, 235),
+            (112, 235),
+            (6, 236),
+            (26, 236),
+            (27, 236),
+            (28, 236),
+            (43, 236),
+            (93, 236),
+            (112, 236),
+            (6, 237),
+            (24, 237),
+            (25, 237),
+            (44, 237),
+            (45, 237),
+            (92, 237),
+            (111, 237),
+            (6, 238),
+            (22, 238),
+            (23, 238),
+            (46, 238),
+            (92, 238),
+            (110, 238),
+            (6, 239),
+            (7, 239),
+            (8, 239),
+            (9, 239),
+            (10, 239),
+            (11, 239),
+            (20, 239),
+            (21, 239),
+            (47, 239),
+            (92, 239),
+            (109, 239),
+            (12, 240),
+            (13, 240),
+            (14, 240),
+            (15, 240),
+            (16, 240),
+            (17, 240),
+            (18, 240),
+            (19, 240),
+            (48, 240),
+            (49, 240),
+            (92, 240),
+            (108, 240),
+            (50, 241),
+            (51, 241),
+            (52, 241),
+            (53, 241),
+            (54, 241),
+            (92, 241),
+            (107, 241),
+            (55, 242),
+            (56, 242),
+            (57, 242),
+            (58, 242),
+            (59, 242),
+            (92, 242),
+            (106, 242),
+            (55, 243),
+            (56, 243),
+            (57, 243),
+            (92, 243),
+            (105, 243),
+            (52, 244),
+            (53, 244),
+            (54, 244),
+            (92, 244),
+            (103, 244),
+            (104, 244),
+            (50, 245),
+            (51, 245),
+            (91, 245),
+            (92, 245),
+            (100, 245),
+            (101, 245),
+            (102, 245),
+            (49, 246),
+            (88, 246),
+            (89, 246),
+            (90, 246),
+            (98, 246),
+            (99, 246),
+            (48, 247),
+            (85, 247),
+            (86, 247),
+            (87, 247),
+            (95, 247),
+            (96, 247),
+            (97, 247),
+            (47, 248),
+            (82, 248),
+            (83, 248),
+            (84, 248),
+            (93, 248),
+            (94, 248),
+            (47, 249),
+            (79, 249),
+            (80, 249),
+            (81, 249),
+            (93, 249),
+            (46, 250),
+            (76, 250),
+            (77, 250),
+            (78, 250),
+            (93, 250),
+            (45, 251),
+            (73, 251),
+            (74, 251),
+            (75, 251),
+            (93, 251),
+            (44, 252),
+            (69, 252),
+            (70, 252),
+            (71, 252),
+            (72, 252),
+            (93, 252),
+            (43, 253),
+            (66, 253),
+            (67, 253),
+            (68, 253),
+            (93, 253),
+            (42, 254),
+            (62, 254),
+            (63, 254),
+            (64, 254),
+            (65, 254),
+            (93, 254),
+            (42, 255),
+            (58, 255),
+            (59, 255),
+            (60, 255),
+            (61, 255),
+            (93, 255),
+            (41, 256),
+            (52, 256),
+            (53, 256),
+            (54, 256),
+            (55, 256),
+            (56, 256),
+            (57, 256),
+            (93, 256),
+            (40, 257),
+            (44, 257),
+            (45, 257),
+            (46, 257),
+            (47, 257),
+            (48, 257),
+            (49, 257),
+            (50, 257),
+            (51, 257),
+            (93, 257),
+            (39, 258),
+            (40, 258),
+            (41, 258),
+            (42, 258),
+            (43, 258),
+            (92, 258),
+            (90, 259),
+            (91, 259),
+            (89, 260),
+            (88, 261),
+            (87, 262),
+            (67, 263),
+            (85, 263),
+            (86, 263),
+            (67, 264),
+            (68, 264),
+            (69, 264),
+            (70, 264),
+            (84, 264),
+            (68, 265),
+            (71, 265),
+            (79, 265),
+            (80, 265),
+            (81, 265),
+            (82, 265),
+            (83, 265),
+            (68, 266),
+            (72, 266),
+            (73, 266),
+            (74, 266),
+            (75, 266),
+            (76, 266),
+            (77, 266),
+            (78, 266),
+            (68, 267),
+            (68, 268),
+            (68, 269),
+            (69, 270),
+            (69, 271),
+            (69, 272),
+            (70, 273),
+            (58, 274),
+            (59, 274),
+            (60, 274),
+            (61, 274),
+            (62, 274),
+            (63, 274),
+            (64, 274),
+            (70, 274),
+            (55, 275),
+            (56, 275),
+            (57, 275),
+            (65, 275),
+            (66, 275),
+            (67, 275),
+            (68, 275),
+            (69, 275),
+            (70, 275),
+            (52, 276),
+            (53, 276),
+            (54, 276),
+            (49, 277),
+            (50, 277),
+            (51, 277),
+            (47, 278),
+            (48, 278),
+            (47, 279),
+            (48, 280),
+            (48, 281),
+            (48, 282),
+            (48, 283),
+            (48, 284),
+            (49, 285),
+            (49, 286),
+            (50, 286),
+            (51, 287),
+            (52, 288),
+            (53, 288),
+            (54, 288),
+            (55, 289),
+            (56, 290),
+            (57, 290),
+            (58, 290),
+            (59, 291),
+            (60, 292),
+            (61, 292),
+            (62, 292),
+            (63, 293),
+            (64, 294),
+            (65, 294),
+            (66, 295),
+            (66, 296),
+            (67, 297),
+            (68, 298),
+            (69, 299),
+            (69, 300),
+            (317, 300),
+            (70, 301),
+            (315, 301),
+            (316, 301),
+            (71, 302),
+            (313, 302),
+            (314, 302),
+            (72, 303),
+            (312, 303),
+            (72, 304),
+            (310, 304),
+            (311, 304),
+            (73, 305),
+            (308, 305),
+            (309, 305),
+            (73, 306),
+            (307, 306),
+            (73, 307),
+            (306, 307),
+            (73, 308),
+            (306, 308),
+            (73, 309),
+            (305, 309),
+            (73, 310),
+            (305, 310),
+            (73, 311),
+            (182, 311),
+            (304, 311),
+            (73, 312),
+            (179, 312),
+            (180, 312),
+            (181, 312),
+            (182, 312),
+            (304, 312),
+            (73, 313),
+            (178, 313),
+            (183, 313),
+            (303, 313),
+            (73, 314),
+            (175, 314),
+            (176, 314),
+            (177, 314),
+            (183, 314),
+            (303, 314),
+            (73, 315),
+            (174, 315),
+            (183, 315),
+            (302, 315),
+            (73, 316),
+            (172, 316),
+            (173, 316),
+            (183, 316),
+            (187, 316),
+            (188, 316),
+            (302, 316),
+            (73, 317),
+            (172, 317),
+            (184, 317),
+            (186, 317),
+            (188, 317),
+            (301, 317),
+            (73, 318),
+            (172, 318),
+            (184, 318),
+            (185, 318),
+            (189, 318),
+            (300, 318),
+            (73, 319),
+            (172, 319),
+            (189, 319),
+            (300, 319),
+            (73, 320),
+            (172, 320),
+            (190, 320),
+            (299, 320),
+            (73, 321),
+            (172, 321),
+            (190, 321),
+            (299, 321),
+            (73, 322),
+            (172, 322),
+            (191, 322),
+            (298, 322),
+            (73, 323),
+            (173, 323),
+            (191, 323),
+            (298, 323),
+            (73, 324),
+            (173, 324),
+            (192, 324),
+            (297, 324),
+            (72, 325),
+            (173, 325),
+            (192, 325),
+            (296, 325),
+            (72, 326),
+            (147, 326),
+            (173, 326),
+            (193, 326),
+            (296, 326),
+            (72, 327),
+            (146, 327),
+            (148, 327),
+            (149, 327),
+            (173, 327),
+            (193, 327),
+            (295, 327),
+            (71, 328),
+            (144, 328),
+            (145, 328),
+            (150, 328),
+            (151, 328),
+            (173, 328),
+            (194, 328),
+            (295, 328),
+            (71, 329),
+            (143, 329),
+            (152, 329),
+            (173, 329),
+            (195, 329),
+            (294, 329),
+            (71, 330),
+            (142, 330),
+            (153, 330),
+            (154, 330),
+            (174, 330),
+            (195, 330),
+            (294, 330),
+            (70, 331),
+            (141, 331),
+            (155, 331),
+            (156, 331),
+            (175, 331),
+            (196, 331),
+            (293, 331),
+            (70, 332),
+            (139, 332),
+            (140, 332),
+            (157, 332),
+            (176, 332),
+            (197, 332),
+            (292, 332),
+            (70, 333),
+            (138, 333),
+            (157, 333),
+            (177, 333),
+            (178, 333),
+            (197, 333),
+            (292, 333),
+            (69, 334),
+            (137, 334),
+            (157, 334),
+            (179, 334),
+            (198, 334),
+            (292, 334),
+            (22, 335),
+            (23, 335),
+            (24, 335),
+            (25, 335),
+            (26, 335),
+            (27, 335),
+            (28, 335),
+            (29, 335),
+            (69, 335),
+            (136, 335),
+            (158, 335),
+            (180, 335),
+            (199, 335),
+            (291, 335),
+            (21, 336),
+            (30, 336),
+            (31, 336),
+            (32, 336),
+            (33, 336),
+            (34, 336),
+            (35, 336),
+            (36, 336),
+            (37, 336),
+            (38, 336),
+            (39, 336),
+            (40, 336),
+            (41, 336),
+            (69, 336),
+            (135, 336),
+            (158, 336),
+            (181, 336),
+            (199, 336),
+            (290, 336),
+            (20, 337),
+            (42, 337),
+            (43, 337),
+            (44, 337),
+            (45, 337),
+            (46, 337),
+            (47, 337),
+            (48, 337),
+            (49, 337),
+            (68, 337),
+            (111, 337),
+            (112, 337),
+            (113, 337),
+            (114, 337),
+            (134, 337),
+            (158, 337),
+            (182, 337),
+            (200, 337),
+            (290, 337),
+            (19, 338),
+            (50, 338),
+            (51, 338),
+            (52, 338),
+            (53, 338),
+            (54, 338),
+            (55, 338),
+            (56, 338),
+            (57, 338),
+            (58, 338),
+            (59, 338),
+            (60, 338),
+            (61, 338),
+            (62, 338),
+            (63, 338),
+            (64, 338),
+            (65, 338),
+            (66, 338),
+            (67, 338),
+            (68, 338),
+            (109, 338),
+            (110, 338),
+            (115, 338),
+            (116, 338),
+            (117, 338),
+            (118, 338),
+            (119, 338),
+            (134, 338),
+            (158, 338),
+            (182, 338),
+            (201, 338),
+            (202, 338),
+            (290, 338),
+            (17, 339),
+            (18, 339),
+            (108, 339),
+            (120, 339),
+            (121, 339),
+            (122, 339),
+            (133, 339),
+            (158, 339),
+            (183, 339),
+            (203, 339),
+            (204, 339),
+            (289, 339),
+            (16, 340),
+            (107, 340),
+            (123, 340),
+            (124, 340),
+            (125, 340),
+            (132, 340),
+            (158, 340),
+            (183, 340),
+            (205, 340),
+            (206, 340),
+            (288, 340),
+            (15, 341),
+            (105, 341),
+            (106, 341),
+            (126, 341),
+            (127, 341),
+            (128, 341),
+            (131, 341),
+            (159, 341),
+            (184, 341),
+            (207, 341),
+            (208, 341),
+            (288, 341),
+            (14, 342),
+            (104, 342),
+            (129, 342),
+            (130, 342),
+            (159, 342),
+            (184, 342),
+            (209, 342),
+            (210, 342),
+            (288, 342),
+            (13, 343),
+            (104, 343),
+            (151, 343),
+            (159, 343),
+            (185, 343),
+            (211, 343),
+            (287, 343),
+            (13, 344),
+            (104, 344),
+            (150, 344),
+            (151, 344),
+            (160, 344),
+            (185, 344),
+            (212, 344),
+            (287, 344),
+            (13, 345),
+            (104, 345),
+            (149, 345),
+            (151, 345),
+            (161, 345),
+            (186, 345),
+            (213, 345),
+            (287, 345),
+            (14, 346),
+            (104, 346),
+            (148, 346),
+            (151, 346),
+            (162, 346),
+            (186, 346),
+            (214, 346),
+            (215, 346),
+            (288, 346),
+            (14, 347),
+            (104, 347),
+            (148, 347),
+            (151, 347),
+            (163, 347),
+            (186, 347),
+            (216, 347),
+            (288, 347),
+            (14, 348),
+            (104, 348),
+            (147, 348),
+            (151, 348),
+            (164, 348),
+            (187, 348),
+            (217, 348),
+            (288, 348),
+            (14, 349),
+            (104, 349),
+            (146, 349),
+            (151, 349),
+            (165, 349),
+            (187, 349),
+            (218, 349),
+            (288, 349),
+            (14, 350),
+            (105, 350),
+            (145, 350),
+            (151, 350),
+            (166, 350),
+            (188, 350),
+            (219, 350),
+            (288, 350),
+            (14, 351),
+            (105, 351),
+            (145, 351),
+            (151, 351),
+            (167, 351),
+            (188, 351),
+            (220, 351),
+            (288, 351),
+            (14, 352),
+            (105, 352),
+            (145, 352),
+            (151, 352),
+            (168, 352),
+            (189, 352),
+            (220, 352),
+            (288, 352),
+            (15, 353),
+            (105, 353),
+            (145, 353),
+            (151, 353),
+            (169, 353),
+            (189, 353),
+            (190, 353),
+            (221, 353),
+            (289, 353),
+            (15, 354),
+            (105, 354),
+            (145, 354),
+            (151, 354),
+            (169, 354),
+            (191, 354),
+            (192, 354),
+            (193, 354),
+            (194, 354),
+            (195, 354),
+            (222, 354),
+            (289, 354),
+            (15, 355),
+            (105, 355),
+            (145, 355),
+            (151, 355),
+            (170, 355),
+            (196, 355),
+            (197, 355),
+            (198, 355),
+            (223, 355),
+            (289, 355),
+            (15, 356),
+            (105, 356),
+            (146, 356),
+            (150, 356),
+            (171, 356),
+            (199, 356),
+            (200, 356),
+            (201, 356),
+            (224, 356),
+            (290, 356),
+            (15, 357),
+            (104, 357),
+            (146, 357),
+            (150, 357),
+            (172, 357),
+            (200, 357),
+            (224, 357),
+            (290, 357),
+            (16, 358),
+            (102, 358),
+            (103, 358),
+            (146, 358),
+            (149, 358),
+            (172, 358),
+            (200, 358),
+            (225, 358),
+            (291, 358),
+            (16, 359),
+            (101, 359),
+            (146, 359),
+            (148, 359),
+            (173, 359),
+            (200, 359),
+            (225, 359),
+            (292, 359),
+            (16, 360),
+            (100, 360),
+            (146, 360),
+            (147, 360),
+            (174, 360),
+            (199, 360),
+            (225, 360),
+            (292, 360),
+            (16, 361),
+            (99, 361),
+            (146, 361),
+            (147, 361),
+            (174, 361),
+            (200, 361),
+            (225, 361),
+            (293, 361),
+            (16, 362),
+            (98, 362),
+            (146, 362),
+            (175, 362),
+            (201, 362),
+            (202, 362),
+            (225, 362),
+            (293, 362),
+            (16, 363),
+            (96, 363),
+            (97, 363),
+            (176, 363),
+            (177, 363),
+            (203, 363),
+            (225, 363),
+            (294, 363),
+            (16, 364),
+            (95, 364),
+            (149, 364),
+            (178, 364),
+            (204, 364),
+            (205, 364),
+            (225, 364),
+            (295, 364),
+            (16, 365),
+            (94, 365),
+            (147, 365),
+            (148, 365),
+            (150, 365),
+            (179, 365),
+            (206, 365),
+            (225, 365),
+            (295, 365),
+            (16, 366),
+            (93, 366),
+            (145, 366),
+            (146, 366),
+            (151, 366),
+            (152, 366),
+            (180, 366),
+            (181, 366),
+            (207, 366),
+            (225, 366),
+            (295, 366),
+            (296, 366),
+            (16, 367),
+            (92, 367),
+            (143, 367),
+            (144, 367),
+            (153, 367),
+            (182, 367),
+            (208, 367),
+            (225, 367),
+            (292, 367),
+            (293, 367),
+            (294, 367),
+            (16, 368),
+            (90, 368),
+            (91, 368),
+            (142, 368),
+            (154, 368),
+            (183, 368),
+            (209, 368),
+            (210, 368),
+            (225, 368),
+            (289, 368),
+            (290, 368),
+            (291, 368),
+            (16, 369),
+            (89, 369),
+            (142, 369),
+            (154, 369),
+            (184, 369),
+            (185, 369),
+            (211, 369),
+            (225, 369),
+            (286, 369),
+            (287, 369),
+            (288, 369),
+            (16, 370),
+            (88, 370),
+            (142, 370),
+            (154, 370),
+            (186, 370),
+            (212, 370),
+            (213, 370),
+            (225, 370),
+            (260, 370),
+            (261, 370),
+            (262, 370),
+            (263, 370),
+            (264, 370),
+            (265, 370),
+            (266, 370),
+            (267, 370),
+            (268, 370),
+            (269, 370),
+            (270, 370),
+            (271, 370),
+            (272, 370),
+            (273, 370),
+            (274, 370),
+            (275, 370),
+            (283, 370),
+            (284, 370),
+            (285, 370),
+            (15, 371),
+            (87, 371),
+            (142, 371),
+            (154, 371),
+            (187, 371),
+            (214, 371),
+            (225, 371),
+            (259, 371),
+            (276, 371),
+            (280, 371),
+            (281, 371),
+            (282, 371),
+            (15, 372),
+            (87, 372),
+            (142, 372),
+            (154, 372),
+            (188, 372),
+            (189, 372),
+            (215, 372),
+            (225, 372),
+            (258, 372),
+            (277, 372),
+            (278, 372),
+            (279, 372),
+            (15, 373),
+            (86, 373),
+            (142, 373),
+            (154, 373),
+            (190, 373),
+            (216, 373),
+            (225, 373),
+            (257, 373),
+            (15, 374),
+            (86, 374),
+            (143, 374),
+            (153, 374),
+            (191, 374),
+            (209, 374),
+            (210, 374),
+            (216, 374),
+            (225, 374),
+            (256, 374),
+            (15, 375),
+            (85, 375),
+            (143, 375),
+            (153, 375),
+            (192, 375),
+            (208, 375),
+            (211, 375),
+            (212, 375),
+            (216, 375),
+            (225, 375),
+            (255, 375),
+            (15, 376),
+            (84, 376),
+            (143, 376),
+            (153, 376),
+            (193, 376),
+            (207, 376),
+            (213, 376),
+            (214, 376),
+            (217, 376),
+            (226, 376),
+            (254, 376),
+            (15, 377),
+            (84, 377),
+            (143, 377),
+            (153, 377),
+            (194, 377),
+            (195, 377),
+            (206, 377),
+            (215, 377),
+            (216, 377),
+            (218, 377),
+            (226, 377),
+            (252, 377),
+            (253, 377),
+            (15, 378),
+            (83, 378),
+            (143, 378),
+            (153, 378),
+            (196, 378),
+            (205, 378),
+            (217, 378),
+            (218, 378),
+            (227, 378),
+            (250, 378),
+            (251, 378),
+            (15, 379),
+            (83, 379),
+            (143, 379),
+            (153, 379),
+            (197, 379),
+            (204, 379),
+            (227, 379),
+            (249, 379),
+            (15, 380),
+            (82, 380),
+            (143, 380),
+            (153, 380),
+            (197, 380),
+            (205, 380),
+            (227, 380),
+            (249, 380),
+            (14, 381),
+            (81, 381),
+            (143, 381),
+            (153, 381),
+            (197, 381),
+            (205, 381),
+            (228, 381),
+            (250, 381),
+            (14, 382),
+            (81, 382),
+            (143, 382),
+            (153, 382),
+            (197, 382),
+            (206, 382),
+            (228, 382),
+            (250, 382),
+            (14, 383),
+            (80, 383),
+            (143, 383),
+            (153, 383),
+            (198, 383),
+            (206, 383),
+            (229, 383),
+            (250, 383),
+            (14, 384),
+            (80, 384),
+            (143, 384),
+            (153, 384),
+            (198, 384),
+            (207, 384),
+            (229, 384),
+            (250, 384),
+            (13, 385),
+            (79, 385),
+            (143, 385),
+            (152, 385),
+            (198, 385),
+            (207, 385),
+            (230, 385),
+            (250, 385),
+            (13, 386),
+            (79, 386),
+            (144, 386),
+            (152, 386),
+            (198, 386),
+            (208, 386),
+            (230, 386),
+            (251, 386),
+            (13, 387),
+            (79, 387),
+            (144, 387),
+            (152, 387),
+            (198, 387),
+            (207, 387),
+            (231, 387),
+            (251, 387),
+            (13, 388),
+            (79, 388),
+            (144, 388),
+            (152, 388),
+            (198, 388),
+            (207, 388),
+            (232, 388),
+            (251, 388),
+            (12, 389),
+            (79, 389),
+            (144, 389),
+            (152, 389),
+            (198, 389),
+            (206, 389),
+            (233, 389),
+            (252, 389),
+            (12, 390),
+            (79, 390),
+            (144, 390),
+            (148, 390),
+            (149, 390),
+            (150, 390),
+            (151, 390),
+            (152, 390),
+            (198, 390),
+            (205, 390),
+            (234, 390),
+            (252, 390),
+            (12, 391),
+            (79, 391),
+            (144, 391),
+            (145, 391),
+            (146, 391),
+            (147, 391),
+            (198, 391),
+            (205, 391),
+            (234, 391),
+            (252, 391),
+            (12, 392),
+            (79, 392),
+            (199, 392),
+            (204, 392),
+            (235, 392),
+            (253, 392),
+            (11, 393),
+            (78, 393),
+            (199, 393),
+            (204, 393),
+            (235, 393),
+            (253, 393),
+            (11, 394),
+            (78, 394),
+            (199, 394),
+            (203, 394),
+            (235, 394),
+            (254, 394),
+            (12, 395),
+            (13, 395),
+            (78, 395),
+            (199, 395),
+            (203, 395),
+            (236, 395),
+            (255, 395),
+            (14, 396),
+            (15, 396),
+            (78, 396),
+            (199, 396),
+            (203, 396),
+            (236, 396),
+            (256, 396),
+            (16, 397),
+            (78, 397),
+            (199, 397),
+            (202, 397),
+            (236, 397),
+            (256, 397),
+            (16, 398),
+            (78, 398),
+            (199, 398),
+            (202, 398),
+            (236, 398),
+            (257, 398),
+            (16, 399),
+            (78, 399),
+            (200, 399),
+            (201, 399),
+            (236, 399),
+            (258, 399),
+            (16, 400),
+            (78, 400),
+            (189, 400),
+            (190, 400),
+            (191, 400),
+            (192, 400),
+            (193, 400),
+            (194, 400),
+            (195, 400),
+            (196, 400),
+            (197, 400),
+            (200, 400),
+            (201, 400),
+            (236, 400),
+            (258, 400),
+            (16, 401),
+            (77, 401),
+            (180, 401),
+            (181, 401),
+            (182, 401),
+            (183, 401),
+            (184, 401),
+            (185, 401),
+            (186, 401),
+            (187, 401),
+            (188, 401),
+            (197, 401),
+            (200, 401),
+            (201, 401),
+            (237, 401),
+            (258, 401),
+            (259, 401),
+            (16, 402),
+            (77, 402),
+            (178, 402),
+            (179, 402),
+            (196, 402),
+            (200, 402),
+            (237, 402),
+            (256, 402),
+            (257, 402),
+            (16, 403),
+            (76, 403),
+            (177, 403),
+            (196, 403),
+            (200, 403),
+            (237, 403),
+            (254, 403),
+            (255, 403),
+            (16, 404),
+            (75, 404),
+            (176, 404),
+            (196, 404),
+            (237, 404),
+            (252, 404),
+            (253, 404),
+            (16, 405),
+            (75, 405),
+            (174, 405),
+            (175, 405),
+            (195, 405),
+            (237, 405),
+            (252, 405),
+            (16, 406),
+            (74, 406),
+            (173, 406),
+            (195, 406),
+            (238, 406),
+            (252, 406),
+            (16, 407),
+            (74, 407),
+            (174, 407),
+            (175, 407),
+            (195, 407),
+            (238, 407),
+            (252, 407),
+            (16, 408),
+            (73, 408),
+            (176, 408),
+            (194, 408),
+            (238, 408),
+            (252, 408),
+            (16, 409),
+            (72, 409),
+            (177, 409),
+            (178, 409),
+            (194, 409),
+            (238, 409),
+            (250, 409),
+            (252, 409),
+            (16, 410),
+            (72, 410),
+            (179, 410),
+            (180, 410),
+            (194, 410),
+            (239, 410),
+            (250, 410),
+            (253, 410),
+            (16, 411),
+            (71, 411),
+            (181, 411),
+            (193, 411),
+            (239, 411),
+            (250, 411),
+            (251, 411),
+            (253, 411),
+            (16, 412),
+            (70, 412),
+            (182, 412),
+            (183, 412),
+            (193, 412),
+            (240, 412),
+            (249, 412),
+            (252, 412),
+            (253, 412),
+            (16, 413),
+            (25, 413),
+            (26, 413),
+            (27, 413),
+            (70, 413),
+            (184, 413),
+            (193, 413),
+            (240, 413),
+            (249, 413),
+            (252, 413),
+            (253, 413),
+            (16, 414),
+            (19, 414),
+            (20, 414),
+            (21, 414),
+            (22, 414),
+            (23, 414),
+            (24, 414),
+            (28, 414),
+            (29, 414),
+            (69, 414),
+            (185, 414),
+            (186, 414),
+            (193, 414),
+            (240, 414),
+            (249, 414),
+            (252, 414),
+            (253, 414),
+            (16, 415),
+            (17, 415),
+            (18, 415),
+            (30, 415),
+            (69, 415),
+            (187, 415),
+            (193, 415),
+            (241, 415),
+            (249, 415),
+            (253, 415),
+            (31, 416),
+            (32, 416),
+            (68, 416),
+            (188, 416),
+            (189, 416),
+            (193, 416),
+            (241, 416),
+            (248, 416),
+            (33, 417),
+            (34, 417),
+            (67, 417),
+            (190, 417),
+            (193, 417),
+            (242, 417),
+            (248, 417),
+            (35, 418),
+            (67, 418),
+            (191, 418),
+            (192, 418),
+            (193, 418),
+            (242, 418),
+            (248, 418),
+            (36, 419),
+            (49, 419),
+            (50, 419),
+            (51, 419),
+            (52, 419),
+            (53, 419),
+            (54, 419),
+            (55, 419),
+            (56, 419),
+            (57, 419),
+            (58, 419),
+            (59, 419),
+            (60, 419),
+            (61, 419),
+            (62, 419),
+            (63, 419),
+            (64, 419),
+            (65, 419),
+            (66, 419),
+            (193, 419),
+            (243, 419),
+            (248, 419),
+            (36, 420),
+            (48, 420),
+            (244, 420),
+            (245, 420),
+            (247, 420),
+            (37, 421),
+            (47, 421),
+            (246, 421),
+            (247, 421),
+            (37, 422),
+            (46, 422),
+            (247, 422),
+            (38, 423),
+            (44, 423),
+            (45, 423),
+            (38, 424),
+            (43, 424),
+            (39, 425),
+            (42, 425),
+            (39, 426),
+            (41, 426),
+            (40, 427),
+            (255, 436),
+            (256, 436),
+            (257, 436),
+            (258, 436),
+            (259, 436),
+            (260, 436),
+            (261, 436),
+            (262, 436),
+            (263, 436),
+            (264, 436),
+            (265, 436),
+            (257, 437),
+            (258, 437),
+            (266, 437),
+            (267, 437),
+            (268, 437),
+            (269, 437),
+            (270, 437),
+            (271, 437),
+            (272, 437),
+            (273, 437),
+            (274, 437),
+            (275, 437),
+            (276, 437),
+            (259, 438),
+            (260, 438),
+            (261, 438),
+            (267, 438),
+            (268, 438),
+            (269, 438),
+            (270, 438),
+            (271, 438),
+            (272, 438),
+            (262, 439),
+            (263, 439),
+            (264, 439),
+            (265, 439),
+            (266, 439),
+        ]
+        .to_vec()
+    }
+
+    fn set_color(&mut self, color: ratatui::style::Color) {
+        self.color = color;
+    }
+
+    fn get_color(&self) -> ratatui::style::Color {
+        self.color
+    }
+}
+impl Shape for Map {
+    fn draw(&self, painter: &mut ratatui::widgets::canvas::Painter) {
+        for (x, y) in self.map() {
+            let y = Self::HEIGHT - y;
+            if let Some((x, y)) = painter.get_point(x as f64, y as f64) {
+                painter.paint(x, y, self.get_color());
+            }
+        }
+    }
+}
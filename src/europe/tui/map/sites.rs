@@ -0,0 +1,89 @@
+//! Maps out all of the european regions
+
+use tui::maps::sites::{Region as RegionTrait, TouristSite};
+
+use crate::europe::rules::cards::EuropeanRegion;
+
+impl RegionTrait for EuropeanRegion {
+    fn default() -> Self {
+        EuropeanRegion::BritishIsles
+    }
+    fn coordinates(&self) -> (f64, f64) {
+        match self {
+            EuropeanRegion::BritishIsles => (37.0, 215.0),
+            EuropeanRegion::Iberia => (37.0, 65.0),
+            EuropeanRegion::France => (89.0, 143.0),
+            EuropeanRegion::Italy => (170.0, 95.0),
+            EuropeanRegion::CentralEurope => (155.0, 191.0),
+            EuropeanRegion::Scandinavia => (185.0, 335.0),
+            EuropeanRegion::SouthEastEurope => (237.0, 71.0),
+        }
+    }
+}
+
+macro_rules! tourist_sites {
+	($(
+		$region:ident : {
+			$(
+				($site:literal,$id:literal)
+			)+
+		}
+	)+) => {
+		impl EuropeanRegion{
+			pub fn sites() -> Vec<TouristSite<EuropeanRegion>>{
+				[
+					$(
+						$(
+							TouristSite::new($site.to_owned(), $id ,EuropeanRegion::$region),
+						)+
+					)+
+				].to_vec()
+			}
+		}
+	};
+}
+
+tourist_sites!(
+    BritishIsles : {
+        ("Big Ben",'A')
+        ("Stonehenge",'B')
+        ("Edinburgh Castle",'C')
+        ("Cliffs of Moher",'D')
+    }
+    Iberia : {
+        ("Sagrada Família",'E')
+        ("Alhambra",'F')
+        ("Belém Tower",'G')
+        ("Mezquita of Córdoba",'H')
+    }
+    France : {
+        ("Eiffel Tower",'I')
+        ("Mont Saint-Michel",'J')
+        ("Versailles",'K')
+        ("The Louvre",'L')
+    }
+    Italy : {
+        ("The Colosseum",'M')
+        ("Leaning Tower of Pisa",'N')
+        ("Venice",'O')
+        ("Amalfi Coast",'P')
+    }
+    CentralEurope : {
+        ("Brandenburg Gate",'Q')
+        ("Neuschwanstein",'R')
+        ("Charles Bridge",'S')
+        ("Matterhorn",'T')
+    }
+    Scandinavia : {
+        ("Nyhavn",'U')
+        ("Geirangerfjord",'V')
+        ("Gamla Stan",'W')
+        ("Lofoten",'X')
+    }
+    SouthEastEurope : {
+        ("The Acropolis",'Y')
+        ("Santorini",'Z')
+        ("Dubrovnik",'*')
+        ("Meteora",'-')
+    }
+);
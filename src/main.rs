@@ -1,7 +1,8 @@
 //! Boomerang
 //! 
 //! Implements both a boomerang client and a boomerang server.
-//! The only rule set provided is the BoomerangAustralia rule set.
+//! The rule sets are listed in the [`rule_sets`] registry, the provided rule sets are
+//! BoomerangAustralia and BoomerangEurope.
 //! 
 //! ## Crates
 //! This crate provides all of the needed tools to run a boomerang australia game.
@@ -11,32 +12,97 @@
 //! The [`tui`] however is implemented for something similar to the boomerang australia game. 
 
 
-use std::{fs::File, io::Write, panic::set_hook, sync::Arc};
+use std::{
+    fs::File,
+    panic::set_hook,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
 
-use clap::{Parser, ValueEnum};
-use log::{error, info};
-use server::engine;
-use tokio::{net::TcpStream, sync::broadcast, time::Instant};
-use tui::{tui::TuiMonitor, ui::Ui};
+use clap::{builder::PossibleValuesParser, Args as ClapArgs, Parser, Subcommand, ValueEnum};
+use server::engine::{self, limits::ConnectionLimits};
+use tokio::{net::TcpStream, task::LocalSet};
+use tracing::{error, info};
+use tracing_subscriber::{fmt::writer::BoxMakeWriter, EnvFilter};
 
-use crate::australia::{
-    player::{manage_event, read_event},
-    protocol::Message,
-    rules::Australia,
-    tui::pages::{main_page::MainPage, map_page::DefaultTuiMap},
-    TuiDefaults,
+use crate::{
+    australia::bot::{
+        external::{ExternalCommand, DEFAULT_MOVE_TIME},
+        BotOptions, Difficulty,
+    },
+    australia::{
+        rules::{config::GameConfig, states::diagram::DiagramFormat},
+        score_sheet::SheetFormat,
+        simulation::{ReportFormat, SimulationOptions},
+    },
+    custom::rules::definition::{self, CardSet},
+    rule_sets::{RuleSet, ServerOptions},
 };
 mod australia;
+mod custom;
+mod europe;
+mod rule_sets;
 
 /// The modes that the app can run in
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum Mode {
     Server,
     Client,
+    /// A client without a ui that plays with a bot strategy
+    Bot,
+}
+
+/// The formats that the logs can be written in
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum LogFormat {
+    /// Human readable text
+    Pretty,
+    /// One json object per line
+    Json,
+}
+
+/// Commands that run instead of a [`Mode`]
+#[derive(Subcommand)]
+enum Command {
+    /// Plays games between bots offline and reports statistics
+    Simulate(SimulateArgs),
+    /// Prints the state machine of the rule set
+    Diagram(DiagramArgs),
+}
+
+#[derive(ClapArgs)]
+struct DiagramArgs {
+    /// Format of the diagram
+    #[arg(long, value_enum, default_value = "dot")]
+    format: DiagramFormat,
+}
+
+#[derive(ClapArgs)]
+struct SimulateArgs {
+    /// Number of games to play
+    #[arg(long, default_value = "1000")]
+    games: usize,
+    /// Number of players in every game
+    #[arg(long, default_value = "4")]
+    players: usize,
+    /// Strategies to seat, repeated until every seat is taken
+    #[arg(long, value_enum, value_delimiter = ',', default_value = "greedy")]
+    bots: Vec<Difficulty>,
+    /// Seed of the first game, the deck is shuffled randomly if omitted
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Format of the report
+    #[arg(long, value_enum, default_value = "json")]
+    format: ReportFormat,
+    /// File to write the report to, defaults to stdout
+    #[arg(long)]
+    output: Option<PathBuf>,
 }
 
 #[derive(Parser)]
 #[command(
+    subcommand_negates_reqs = true,
     author = "Ivar Jönsson <ivajns-9@student.ltu.se>",
     version = "0.0.1",
     about = "Boomerang Australia",
@@ -44,89 +110,285 @@ pub enum Mode {
 australia game."
 )]
 struct Args {
-    #[arg(short = 'm')]
-    mode: Mode,
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[arg(short = 'm', required = true)]
+    mode: Option<Mode>,
     #[arg(short = 'i', default_value = "0")]
     id: usize,
+    /// Address that the server listens on and the clients connect to
+    #[arg(long, default_value = "127.0.0.1:2047")]
+    address: String,
+    /// Number of headless players that the bot mode connects
+    #[arg(long, default_value = "1")]
+    players: usize,
+    /// Maximum number of concurrent connections to the server
+    #[arg(long, default_value = "16")]
+    max_connections: usize,
+    /// Maximum number of messages a client may send per second
+    #[arg(long, default_value = "20")]
+    max_messages: usize,
+    /// Maximum size of a single message in bytes
+    #[arg(long, default_value = "4096")]
+    max_frame_size: usize,
+    /// Seconds a client may wait for a seat before it is disconnected
+    #[arg(long, default_value = "120")]
+    idle_timeout: u64,
+    /// The rule set that the server runs, clients learn it when they connect
+    #[arg(long, global = true, default_value = rule_sets::RULE_SETS[0].name, value_parser = PossibleValuesParser::new(rule_sets::names()))]
+    rules: String,
+    /// Game configuration file in TOML, the rule set defaults are used if omitted
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// Deck definition in TOML or JSON that the custom rule set is played with, the built in
+    /// australia deck is played if omitted
+    #[arg(long, global = true)]
+    deck: Option<PathBuf>,
+    /// Number of bots that are seated with the remote players in every game
+    #[arg(long, default_value = "0")]
+    bots: usize,
+    /// Address of the server admin socket, bots can be added through it with `bots <count>`.
+    /// It is not authenticated, so only bind it to a trusted interface
+    #[arg(long)]
+    admin_address: Option<String>,
+    /// How well the bots play, both in the server and in the bot mode
+    #[arg(long, value_enum, default_value = "greedy")]
+    bot_difficulty: Difficulty,
+    /// External bot program and its arguments, plays instead of the built in bots
+    #[arg(long)]
+    bot_command: Option<String>,
+    /// Checks that no card is lost or duplicated after every state transition, and stops
+    /// the game if one is. Always done in debug builds
+    #[arg(long)]
+    check_invariants: bool,
+    /// SQLite database that the server records finished games and player ratings in,
+    /// created if it does not exist
+    #[arg(long)]
+    profiles: Option<PathBuf>,
+    /// Directory that the server writes the score sheet of every finished game to
+    #[arg(long)]
+    score_sheets: Option<PathBuf>,
+    /// Format of the score sheets
+    #[arg(long, value_enum, default_value = "json")]
+    score_sheet_format: SheetFormat,
+    /// Nickname that the client or bot is rated under, bots are numbered if there are several
+    #[arg(long)]
+    nickname: Option<String>,
+    /// Milliseconds that an external bot has to answer a request
+    #[arg(long, default_value_t = DEFAULT_MOVE_TIME.as_millis() as u64)]
+    bot_move_time: u64,
+    /// Format of the log output
+    #[arg(long, value_enum, default_value = "pretty")]
+    log_format: LogFormat,
+    /// Log level or filter directives, e.g. `debug` or `info,server=trace`, defaults to `info`,
+    /// or `warn` for commands that log to stderr
+    #[arg(long)]
+    log_level: Option<String>,
+    /// File to write the logs to, defaults to `{mode}_{id}_boomerang.log`, commands log to
+    /// stderr
+    #[arg(long)]
+    log_file: Option<PathBuf>,
 }
 
-async fn player_main() {
-    let (writer, reader) = tokio::sync::broadcast::channel::<Message>(32);
-    let (feedback_writer, feedback_reader) = tokio::sync::broadcast::channel::<Message>(32);
-
-    let join_handle = {
-        let main_page = MainPage::new();
-        let map_page = DefaultTuiMap::new();
-        let ui = Arc::new(TuiDefaults::init(main_page, map_page));
-        TuiDefaults::subscribe(ui.clone(), reader, feedback_writer);
-        let ui_ref_clone = ui.clone();
-        tokio::spawn(async move {
-            TuiDefaults::start(ui_ref_clone).await;
-        })
-    };
+impl Args {
+    fn server_options(&self) -> ServerOptions {
+        ServerOptions {
+            config: self.config.clone(),
+            bots: self.bots,
+            bot: self.bot_options(),
+            admin: self.admin_address.clone(),
+            check_invariants: self.check_invariants,
+            profiles: self.profiles.clone(),
+            score_sheets: self.score_sheets.clone(),
+            score_sheet_format: self.score_sheet_format,
+        }
+    }
 
-    let stream = match TcpStream::connect("127.0.0.1:2047").await {
+    fn bot_options(&self) -> BotOptions {
+        let move_time = Duration::from_millis(self.bot_move_time);
+        BotOptions {
+            difficulty: self.bot_difficulty,
+            command: self
+                .bot_command
+                .as_deref()
+                .and_then(|command| ExternalCommand::parse(command, move_time)),
+        }
+    }
+
+    fn limits(&self) -> ConnectionLimits {
+        ConnectionLimits {
+            max_connections: self.max_connections,
+            max_messages: self.max_messages,
+            rate_window: Duration::from_secs(1),
+            max_frame_size: self.max_frame_size,
+            idle_timeout: Duration::from_secs(self.idle_timeout),
+        }
+    }
+
+    /// Returns the name of the mode or command that is run
+    fn name(&self) -> String {
+        match (&self.command, self.mode) {
+            (Some(Command::Simulate(_)), _) => "Simulate".to_owned(),
+            (Some(Command::Diagram(_)), _) => "Diagram".to_owned(),
+            (None, mode) => format!("{:?}", mode.unwrap_or(Mode::Client)),
+        }
+    }
+
+    /// Installs the global tracing subscriber
+    ///
+    /// The logs of a mode are written to a file since the client uses stdout for the ui. A
+    /// command prints its result on stdout, so it logs to stderr unless a file is given.
+    fn init_tracing(&self) {
+        let writer = match (&self.log_file, &self.command) {
+            (Some(path), _) => BoxMakeWriter::new(Mutex::new(
+                File::create(path).expect("Failed to create log file"),
+            )),
+            (None, Some(_)) => BoxMakeWriter::new(std::io::stderr),
+            (None, None) => {
+                let path = format!("{}_{:?}_boomerang.log", self.name(), self.id);
+                BoxMakeWriter::new(Mutex::new(
+                    File::create(path).expect("Failed to create log file"),
+                ))
+            }
+        };
+        let level = match (&self.log_level, &self.log_file, &self.command) {
+            (Some(level), _, _) => level.as_str(),
+            // Every simulated state transition is logged at info
+            (None, None, Some(_)) => "warn",
+            (None, _, _) => "info",
+        };
+        let filter = EnvFilter::try_new(level).expect("Invalid log level");
+
+        let builder = tracing_subscriber::fmt()
+            .with_env_filter(filter)
+            .with_writer(writer)
+            .with_ansi(false)
+            .with_file(true)
+            .with_line_number(true);
+        match self.log_format {
+            LogFormat::Pretty => builder.pretty().init(),
+            LogFormat::Json => builder.json().init(),
+        }
+    }
+}
+
+/// Connects to the server and reads which rule set it runs
+async fn connect(address: &str) -> (TcpStream, &'static RuleSet) {
+    let mut stream = match TcpStream::connect(address).await {
         Ok(val) => val,
         Err(e) => {
-            println!("{:?}", e);
+            error!(error = ?e, "Could not connect to the server");
             panic!();
         }
     };
-    let (read_part, write_part) = stream.into_split();
-    let (broadcast_writer, broadcast_receiver) = broadcast::channel(32);
-    let _handle = tokio::spawn(async move { read_event(read_part, broadcast_writer).await });
-    let handle = tokio::spawn(async move {
-        manage_event(writer, feedback_reader, broadcast_receiver, write_part).await
-    });
-    info!("Started player");
-    join_handle.await.unwrap();
-    handle.await.unwrap();
+    let rule_set = match rule_sets::handshake(&mut stream).await {
+        Ok(rule_set) => rule_set,
+        Err(e) => {
+            error!(error = %e, "Handshake failed");
+            panic!();
+        }
+    };
+    info!(rule_set = rule_set.name, "Connected");
+    (stream, rule_set)
+}
+
+async fn player_main(address: &str, nickname: Option<String>) {
+    let (stream, rule_set) = connect(address).await;
+    (rule_set.client)(stream, nickname).await;
+}
+
+/// Connects `players` headless players that play until the server closes the connection
+async fn bot_main(address: &str, players: usize, options: BotOptions, nickname: Option<String>) {
+    // The rule set tasks are not `Send`, so every player runs on this thread
+    let local = LocalSet::new();
+    let players = players.max(1);
+    for idx in 0..players {
+        let (stream, rule_set) = connect(address).await;
+        // Nicknames are unique in a lobby
+        let nickname = match players {
+            1 => nickname.clone(),
+            _ => nickname.as_ref().map(|name| format!("{} {}", name, idx + 1)),
+        };
+        local.spawn_local((rule_set.bot)(stream, options.clone(), nickname));
+    }
+    local.await;
 }
 
-async fn server_main() {
-    println!("Running as server");
-    let listener = match std::net::TcpListener::bind("127.0.0.1:2047") {
+async fn server_main(
+    address: &str,
+    rule_set: &RuleSet,
+    limits: ConnectionLimits,
+    options: ServerOptions,
+) {
+    info!(rule_set = rule_set.name, "Running as server");
+    let listener = match std::net::TcpListener::bind(address) {
         Ok(val) => val,
         Err(e) => {
-            println!("{:?}", e);
+            error!(error = ?e, "Could not bind the listener");
             panic!();
         }
     };
-    type Rules = Australia<4, 2>;
-    engine::manager::<Rules, 4, 4>(listener).await;
-    println!("Hello world");
-    loop {}
+    (rule_set.server)(listener, limits, options).await;
+}
+
+/// Loads the deck that the custom rule set is played with, exits if it is invalid
+fn load_deck(path: &Path) {
+    let card_set = match CardSet::load(path) {
+        Ok(card_set) => card_set,
+        Err(e) => {
+            eprintln!("Could not load the deck {:?}, {}", path, e);
+            std::process::exit(1);
+        }
+    };
+    info!(deck = card_set.definition().name, cards = card_set.len(), "Deck loaded");
+    // Nothing has been played yet, so no other deck is in use
+    definition::install(card_set);
+}
+
+/// Plays the simulation and writes the report
+fn simulate_main(rule_set: &RuleSet, config: Option<&PathBuf>, args: &SimulateArgs) {
+    let config = match config {
+        Some(path) => match GameConfig::load(path) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Could not load the game config, {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => GameConfig::default(),
+    };
+    let options = SimulationOptions {
+        games: args.games,
+        players: args.players,
+        strategies: args.bots.clone(),
+        config,
+        seed: args.seed,
+    };
+    info!(rule_set = rule_set.name, ?options, "Simulating");
+    let report = match (rule_set.simulate)(&options) {
+        Ok(report) => report.write(args.format),
+        Err(e) => {
+            eprintln!("Simulation failed, {}", e);
+            std::process::exit(1);
+        }
+    };
+    match &args.output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, report) {
+                eprintln!("Could not write the report to {:?}, {}", path, e);
+                std::process::exit(1);
+            }
+        }
+        None => print!("{}", report),
+    }
 }
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
-    let log_file = File::create(format!("{:?}_{:?}_boomerang.log", args.mode, args.id))
-        .expect("Failed to create log file");
-
-    let started = Instant::now();
-
-    env_logger::builder()
-        .format(move |buf, record| {
-            let now = Instant::now();
-            let since_start = now.duration_since(started.clone()).as_secs();
-            writeln!(
-                buf,
-                "[T: {} Log level : {:.6}] [{}] {} [{}:{}]",
-                since_start,
-                record.level(),
-                record.args(),
-                record.target(),
-                record.file().unwrap_or("unknown file"),
-                record.line().unwrap_or(0)
-            )
-        })
-        .write_style(env_logger::WriteStyle::Never)
-        .filter(None, log::LevelFilter::Info)
-        .target(env_logger::Target::Pipe(Box::new(log_file)))
-        .init();
+    args.init_tracing();
 
-    info!("App started with args : -m {:?}", args.mode);
+    info!(mode = args.name(), id = args.id, "App started");
 
     set_hook(Box::new(|panic_info| {
         let location = panic_info.location();
@@ -140,8 +402,32 @@ async fn main() {
         error!(target: "my_panic_handler", "Panic occurred: {} at {:?}", message, location);
     }));
 
-    match args.mode {
-        Mode::Server => server_main().await,
-        Mode::Client => player_main().await,
+    // The parser only accepts registered names
+    let rule_set = rule_sets::get(&args.rules).unwrap();
+    if let Some(path) = &args.deck {
+        load_deck(path);
+    }
+    match &args.command {
+        Some(Command::Simulate(simulate)) => {
+            return simulate_main(rule_set, args.config.as_ref(), simulate)
+        }
+        Some(Command::Diagram(diagram)) => return print!("{}", (rule_set.diagram)(diagram.format)),
+        None => {}
+    }
+    // The parser requires a mode when no command is given
+    match args.mode.unwrap() {
+        Mode::Server => {
+            server_main(&args.address, rule_set, args.limits(), args.server_options()).await
+        }
+        Mode::Client => player_main(&args.address, args.nickname.clone()).await,
+        Mode::Bot => {
+            bot_main(
+                &args.address,
+                args.players,
+                args.bot_options(),
+                args.nickname.clone(),
+            )
+            .await
+        }
     }
 }
@@ -0,0 +1,218 @@
+//! Defines the registry of rule sets that the app can serve and play.
+//!
+//! The server selects a rule set with `--rules <name>` and names it in a handshake
+//! that is sent to every client when they connect, the client then loads the matching ui.
+
+use std::{future::Future, net::TcpListener, path::PathBuf, pin::Pin};
+
+use server::engine::{event::BackendEvent, limits::ConnectionLimits};
+use tokio::{io::AsyncReadExt, net::TcpStream};
+
+use crate::{
+    australia::{
+        self,
+        bot::BotOptions,
+        rules::states::diagram::DiagramFormat,
+        score_sheet::SheetFormat,
+        simulation::{Report, SimulationError, SimulationOptions},
+    },
+    custom, europe,
+};
+
+/// The longest handshake that the client accepts
+const MAX_HANDSHAKE: usize = 256;
+
+pub type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Settings for a server, collected from the command line
+#[derive(Debug, Clone, Default)]
+pub struct ServerOptions {
+    /// Game configuration file in TOML, the rule set defaults are used if omitted
+    pub config: Option<PathBuf>,
+    /// Number of bots that are seated with the remote players
+    pub bots: usize,
+    /// How the bots play
+    pub bot: BotOptions,
+    /// Address of the admin socket, see [`server::engine::admin`], none is opened if omitted
+    pub admin: Option<String>,
+    /// Checks the game invariants in release builds as well
+    pub check_invariants: bool,
+    /// Database that finished games and player ratings are kept in
+    pub profiles: Option<PathBuf>,
+    /// Directory that the score sheets of finished games are written to
+    pub score_sheets: Option<PathBuf>,
+    pub score_sheet_format: SheetFormat,
+}
+
+/// A [`RuleEngine`](server::engine::rules::RuleEngine) and [`Tui`](tui::tui::Tui) pair
+/// that can be selected at runtime.
+pub struct RuleSet {
+    /// The name used to select the rule set
+    pub name: &'static str,
+    /// Runs the server for this rule set
+    pub server: fn(TcpListener, ConnectionLimits, ServerOptions) -> Task,
+    /// Runs the client for this rule set on a stream that has completed the [`handshake`],
+    /// with the nickname that the player is rated under
+    pub client: fn(TcpStream, Option<String>) -> Task,
+    /// Runs a headless client that plays with the given bot, with the nickname that it is
+    /// rated under
+    pub bot: fn(TcpStream, BotOptions, Option<String>) -> Task,
+    /// Plays games between bots without a server
+    pub simulate: fn(&SimulationOptions) -> Result<Report, SimulationError>,
+    /// Draws the state machine of the rule set
+    pub diagram: fn(DiagramFormat) -> String,
+    /// Fingerprint of the content that is loaded at runtime, see [`handshake`]
+    pub fingerprint: fn() -> Option<String>,
+}
+
+/// All of the rule sets that are available, the first one is the default
+pub const RULE_SETS: &[RuleSet] = &[
+    RuleSet {
+        name: australia::NAME,
+        server: |listener, limits, options| Box::pin(australia::server(listener, limits, options)),
+        client: |stream, nickname| Box::pin(australia::client(stream, nickname)),
+        bot: |stream, options, nickname| Box::pin(australia::bot(stream, options, nickname)),
+        simulate: australia::simulate,
+        diagram: australia::diagram,
+        fingerprint: australia::fingerprint,
+    },
+    RuleSet {
+        name: europe::NAME,
+        server: |listener, limits, options| Box::pin(europe::server(listener, limits, options)),
+        client: |stream, nickname| Box::pin(europe::client(stream, nickname)),
+        bot: |stream, options, nickname| Box::pin(europe::bot(stream, options, nickname)),
+        simulate: europe::simulate,
+        diagram: europe::diagram,
+        fingerprint: europe::fingerprint,
+    },
+    RuleSet {
+        name: custom::NAME,
+        server: |listener, limits, options| Box::pin(custom::server(listener, limits, options)),
+        client: |stream, nickname| Box::pin(custom::client(stream, nickname)),
+        bot: |stream, options, nickname| Box::pin(custom::bot(stream, options, nickname)),
+        simulate: custom::simulate,
+        diagram: custom::diagram,
+        fingerprint: custom::fingerprint,
+    },
+];
+
+#[derive(Debug)]
+pub enum HandshakeError {
+    /// The connection was closed or could not be read from
+    Io(std::io::Error),
+    /// The server sent something other than a rule set
+    Invalid,
+    /// The server runs a rule set that this client does not know
+    UnknownRuleSet(String),
+    /// The server plays the rule set with other content, such as another deck
+    Mismatch(String),
+}
+
+impl std::fmt::Display for HandshakeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "could not read the handshake, {}", e),
+            Self::Invalid => write!(f, "the server sent an invalid handshake"),
+            Self::UnknownRuleSet(name) => write!(f, "unknown rule set {:?}", name),
+            Self::Mismatch(name) => write!(
+                f,
+                "the server plays {:?} with other content, start with the same --deck",
+                name
+            ),
+        }
+    }
+}
+
+/// Returns the names of all registered rule sets
+pub fn names() -> Vec<&'static str> {
+    RULE_SETS.iter().map(|rule_set| rule_set.name).collect()
+}
+
+/// Returns the rule set with the given name, if any
+pub fn get(name: &str) -> Option<&'static RuleSet> {
+    RULE_SETS.iter().find(|rule_set| rule_set.name == name)
+}
+
+/// Reads the handshake that the server sends on connect and returns the matching rule set.
+///
+/// The rule set is only returned if its fingerprint matches the one that the server sent.
+/// This reads byte by byte so that no game events are consumed from the stream.
+pub async fn handshake(stream: &mut TcpStream) -> Result<&'static RuleSet, HandshakeError> {
+    let mut frame = Vec::new();
+    loop {
+        match stream.read_u8().await.map_err(HandshakeError::Io)? {
+            0 => break,
+            byte => frame.push(byte),
+        }
+        if frame.len() > MAX_HANDSHAKE {
+            return Err(HandshakeError::Invalid);
+        }
+    }
+    let (name, fingerprint) = parse_handshake(&frame)?;
+    select(name, fingerprint)
+}
+
+fn parse_handshake(frame: &[u8]) -> Result<(String, Option<String>), HandshakeError> {
+    match serde_json::from_slice(frame) {
+        Ok(BackendEvent::RuleSet(name, fingerprint)) => Ok((name, fingerprint)),
+        _ => Err(HandshakeError::Invalid),
+    }
+}
+
+/// Returns the rule set if it is known and plays the same content as the server
+fn select(
+    name: String,
+    fingerprint: Option<String>,
+) -> Result<&'static RuleSet, HandshakeError> {
+    let rule_set = get(&name).ok_or_else(|| HandshakeError::UnknownRuleSet(name.clone()))?;
+    if (rule_set.fingerprint)() != fingerprint {
+        return Err(HandshakeError::Mismatch(name));
+    }
+    Ok(rule_set)
+}
+
+#[cfg(test)]
+mod test {
+    use server::engine::event::BackendEvent;
+
+    use super::{get, names, parse_handshake, select, HandshakeError};
+
+    #[test]
+    fn test_registry() {
+        for name in names() {
+            assert_eq!(get(name).unwrap().name, name);
+        }
+        assert!(get("australia").is_some());
+        assert!(get("europe").is_some());
+        assert!(get("no such rules").is_none());
+    }
+
+    #[test]
+    fn test_parse_handshake() {
+        let frame: Vec<u8> = BackendEvent::RuleSet("australia".to_owned(), None).into();
+        assert_eq!(parse_handshake(&frame).unwrap(), ("australia".to_owned(), None));
+
+        let frame: Vec<u8> = BackendEvent::Connected(1).into();
+        assert!(parse_handshake(&frame).is_err());
+        assert!(parse_handshake(b"garbage").is_err());
+    }
+
+    #[test]
+    fn test_select() {
+        assert_eq!(select("australia".to_owned(), None).unwrap().name, "australia");
+        let custom = get("custom").unwrap();
+        assert!(select("custom".to_owned(), (custom.fingerprint)()).is_ok());
+        assert!(matches!(
+            select("custom".to_owned(), Some("0".repeat(16))),
+            Err(HandshakeError::Mismatch(_))
+        ));
+        assert!(matches!(
+            select("australia".to_owned(), Some("0".repeat(16))),
+            Err(HandshakeError::Mismatch(_))
+        ));
+        assert!(matches!(
+            select("no such rules".to_owned(), None),
+            Err(HandshakeError::UnknownRuleSet(_))
+        ));
+    }
+}
@@ -14,7 +14,7 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use log::info;
+use tracing::info;
 use ratatui::{
     prelude::{Backend, Constraint, CrosstermBackend, Direction, Layout, Rect},
     widgets::{Block, Borders},
@@ -68,12 +68,13 @@ pub struct Tui<
     MainPage: TuiPage,
     MapPage: TuiPage,
     ShowPage: ShowPageTrait,
+    LeaderboardPage: TuiPage,
     InfoPopup: Popup,
     QueryPopup: Popup,
     EndScreen: Popup,
 > {
     terminal: Terminal,
-    paginate: Paginate<MainPage, MapPage, ShowPage>,
+    paginate: Paginate<MainPage, MapPage, ShowPage, LeaderboardPage>,
     show_popup: bool,
     info: Option<InfoPopup>,
     query: Option<QueryPopup>,
@@ -84,10 +85,11 @@ impl<
         MainPage: TuiPage,
         MapPage: TuiPage,
         ShowPage: ShowPageTrait,
+        LeaderboardPage: TuiPage,
         InfoPopup: Popup,
         QueryPopup: Popup,
         EndScreen: Popup,
-    > Tui<MainPage, MapPage, ShowPage, InfoPopup, QueryPopup, EndScreen>
+    > Tui<MainPage, MapPage, ShowPage, LeaderboardPage, InfoPopup, QueryPopup, EndScreen>
 {
     pub fn show_info(&mut self, info: InfoPopup) -> Result<(), TuiError> {
         if self.show_popup {
@@ -131,18 +133,22 @@ impl<
     pub fn final_result(&mut self, screen: EndScreen) {
         self.end_screen = Some(screen);
     }
+    pub fn clear_final_result(&mut self) {
+        self.end_screen = None;
+    }
 }
 
 impl<
         MainPage: TuiPage,
         MapPage: TuiPage,
         ShowPage: ShowPageTrait,
+        LeaderboardPage: TuiPage,
         InfoPopup: Popup,
         QueryPopup: Popup,
         EndScreen: Popup,
-    > Tui<MainPage, MapPage, ShowPage, InfoPopup, QueryPopup, EndScreen>
+    > Tui<MainPage, MapPage, ShowPage, LeaderboardPage, InfoPopup, QueryPopup, EndScreen>
 {
-    pub fn paginate(&mut self) -> &mut Paginate<MainPage, MapPage, ShowPage> {
+    pub fn paginate(&mut self) -> &mut Paginate<MainPage, MapPage, ShowPage, LeaderboardPage> {
         &mut self.paginate
     }
 }
@@ -151,10 +157,11 @@ impl<
         MainPage: TuiPage,
         MapPage: TuiPage,
         ShowPage: ShowPageTrait,
+        LeaderboardPage: TuiPage,
         InfoPopup: Popup,
         QueryPopup: Popup,
         EndScreen: Popup,
-    > Tui<MainPage, MapPage, ShowPage, InfoPopup, QueryPopup, EndScreen>
+    > Tui<MainPage, MapPage, ShowPage, LeaderboardPage, InfoPopup, QueryPopup, EndScreen>
 {
     // Sets up the terminal to use the crossterm backend
     fn setup_terminal() -> Result<Terminal, Box<dyn Error>> {
@@ -190,7 +197,7 @@ impl<
 
     fn draw<B: Backend>(
         frame: &mut Frame<B>,
-        paginate: &mut Paginate<MainPage, MapPage, ShowPage>,
+        paginate: &mut Paginate<MainPage, MapPage, ShowPage, LeaderboardPage>,
     ) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -252,14 +259,19 @@ impl<
         StartPage: TuiPage + Send + 'static,
         MapPage: TuiPage + Send + 'static,
         ShowPage: ShowPageTrait + Send + 'static,
+        LeaderboardPage: TuiPage + Send + 'static,
         InfoPopup: Popup + Send + 'static,
         QueryPopup: Popup + Send + 'static,
         EndScreen: Popup + Send + 'static,
-    > Tui<StartPage, MapPage, ShowPage, InfoPopup, QueryPopup, EndScreen>
+    > Tui<StartPage, MapPage, ShowPage, LeaderboardPage, InfoPopup, QueryPopup, EndScreen>
 {
-    pub fn init(mainpage: StartPage, map_page: MapPage) -> RwLock<Box<Self>> {
+    pub fn init(
+        mainpage: StartPage,
+        map_page: MapPage,
+        leaderboard: LeaderboardPage,
+    ) -> RwLock<Box<Self>> {
         let ret: Self = Self {
-            paginate: Paginate::new(mainpage, map_page),
+            paginate: Paginate::new(mainpage, map_page, leaderboard),
             terminal: Self::setup_terminal().unwrap(),
             show_popup: false,
             query: None,
@@ -275,10 +287,12 @@ impl<
         StartPage: TuiPage + Send + Sync + 'static,
         MapPage: TuiPage + Send + Sync + 'static,
         ShowPage: ShowPageTrait + Send + Sync + 'static,
+        LeaderboardPage: TuiPage + Send + Sync + 'static,
         InfoPopup: Popup + Send + Sync + 'static,
         QueryPopup: Popup + Send + Sync + 'static,
         EndScreen: Popup + Send + Sync + 'static,
-    > ui::Ui for Tui<StartPage, MapPage, ShowPage, InfoPopup, QueryPopup, EndScreen>
+    > ui::Ui
+    for Tui<StartPage, MapPage, ShowPage, LeaderboardPage, InfoPopup, QueryPopup, EndScreen>
 {
     async fn start(ui: Arc<RwLock<Box<Self>>>)
     where
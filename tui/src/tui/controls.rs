@@ -93,10 +93,11 @@ impl<
         StartPage: TuiPage + Send + 'static,
         MapPage: TuiPage + Send + 'static,
         ShowPage: ShowPageTrait + Send + 'static,
+        LeaderboardPage: TuiPage + Send + 'static,
         InfoPopup: Popup + Send + 'static,
         QueryPopup: Popup + Send + 'static,
         EndScreen: Popup + Send + 'static,
-    > Tui<StartPage, MapPage, ShowPage, InfoPopup, QueryPopup, EndScreen>
+    > Tui<StartPage, MapPage, ShowPage, LeaderboardPage, InfoPopup, QueryPopup, EndScreen>
 {
     /// Manages user input
     ///
@@ -1,4 +1,4 @@
-use log::info;
+use tracing::info;
 use ratatui::{
     prelude::{Backend, Rect},
     style::{Style, Stylize},
@@ -8,15 +8,19 @@ use ratatui::{
 
 use crate::tui::{controls::EventApi, show_page::ShowPage as ShowPageTrait, TuiPage};
 
-pub struct Paginate<MainPage: TuiPage, MapPage: TuiPage, ShowPage: ShowPageTrait>(
-    MainPage,
-    MapPage,
-    Vec<ShowPage>,
-    usize,
-);
+/// The tabs of the ui, the main page, the map, the leaderboard and one show page per player
+pub struct Paginate<
+    MainPage: TuiPage,
+    MapPage: TuiPage,
+    ShowPage: ShowPageTrait,
+    LeaderboardPage: TuiPage,
+>(MainPage, MapPage, Vec<ShowPage>, usize, LeaderboardPage);
 
-impl<MainPage: TuiPage, MapPage: TuiPage, ShowPage: ShowPageTrait> EventApi
-    for Paginate<MainPage, MapPage, ShowPage>
+/// The number of tabs that come before the show pages
+const FIXED_PAGES: usize = 3;
+
+impl<MainPage: TuiPage, MapPage: TuiPage, ShowPage: ShowPageTrait, LeaderboardPage: TuiPage>
+    EventApi for Paginate<MainPage, MapPage, ShowPage, LeaderboardPage>
 {
     fn handle_input(&mut self, control: crate::tui::controls::Controls) {
         match control {
@@ -26,8 +30,9 @@ impl<MainPage: TuiPage, MapPage: TuiPage, ShowPage: ShowPageTrait> EventApi
                 match self.3 {
                     0 => self.0.handle_input(control),
                     1 => self.1.handle_input(control),
+                    2 => self.4.handle_input(control),
                     mut x => {
-                        x -= 2;
+                        x -= FIXED_PAGES;
                         info!("Trying to draw show page {:?}", x);
                         if x < self.2.len() {
                             self.2[x].handle_input(control);
@@ -40,29 +45,23 @@ impl<MainPage: TuiPage, MapPage: TuiPage, ShowPage: ShowPageTrait> EventApi
     }
 }
 
-impl<MainPage: TuiPage, MapPage: TuiPage, ShowPage: ShowPageTrait>
-    Paginate<MainPage, MapPage, ShowPage>
+impl<MainPage: TuiPage, MapPage: TuiPage, ShowPage: ShowPageTrait, LeaderboardPage: TuiPage>
+    Paginate<MainPage, MapPage, ShowPage, LeaderboardPage>
 {
     pub fn increment(&mut self) {
-        self.3 = match self.3 {
-            0 => 1,
-            mut x => {
-                x += 1;
-                if x > self.2.len() + 1 {
-                    x = 0;
-                }
-                x
-            }
+        self.3 = match self.3 + 1 {
+            x if x >= self.2.len() + FIXED_PAGES => 0,
+            x => x,
         }
     }
     pub fn decrement(&mut self) {
         self.3 = match self.3 {
-            0 => self.2.len() + 1,
+            0 => self.2.len() + FIXED_PAGES - 1,
             x => x - 1,
         }
     }
-    pub fn new(mainpage: MainPage, mappage: MapPage) -> Self {
-        Self(mainpage, mappage, Vec::new(), 0)
+    pub fn new(mainpage: MainPage, mappage: MapPage, leaderboard: LeaderboardPage) -> Self {
+        Self(mainpage, mappage, Vec::new(), 0, leaderboard)
     }
     pub fn add_show_page(&mut self, page: ShowPage) {
         self.2.push(page);
@@ -82,7 +81,8 @@ impl<MainPage: TuiPage, MapPage: TuiPage, ShowPage: ShowPageTrait>
     }
     pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, pagination_area: Rect, block: Rect) {
         // We need to draw either
-        let mut titles: Vec<&str> = vec![self.0.get_title(), self.1.get_title()];
+        let mut titles: Vec<&str> =
+            vec![self.0.get_title(), self.1.get_title(), self.4.get_title()];
         for page in &self.2 {
             titles.push(page.get_title())
         }
@@ -98,8 +98,9 @@ impl<MainPage: TuiPage, MapPage: TuiPage, ShowPage: ShowPageTrait>
         match self.3 {
             0 => self.0.draw(frame, block),
             1 => self.1.draw(frame, block),
+            2 => self.4.draw(frame, block),
             mut x => {
-                x -= 2;
+                x -= FIXED_PAGES;
                 info!("Trying to draw show page {:?}", x);
                 if x < self.2.len() {
                     self.2[x].draw(frame, block);
@@ -115,4 +116,7 @@ impl<MainPage: TuiPage, MapPage: TuiPage, ShowPage: ShowPageTrait>
     pub fn map_page(&mut self) -> &mut MapPage {
         &mut self.1
     }
+    pub fn leaderboard_page(&mut self) -> &mut LeaderboardPage {
+        &mut self.4
+    }
 }
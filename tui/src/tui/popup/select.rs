@@ -1,4 +1,4 @@
-use log::info;
+use tracing::info;
 use ratatui::{
     prelude::{Alignment, Constraint, Direction, Layout},
     style::Stylize,